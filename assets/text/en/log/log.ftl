@@ -40,6 +40,7 @@ no-ranged-weapon-equipped = No ranged weapon equipped!
 no-target = {$chara} could not find any target.
 target-chara = {$chara} targeted {$target}.
 attack-evade = {$chara} evaded.
+attack-critical = Critical hit on {$chara}!
 damaged-chara = {$chara} was damaged ({$damage}).
 arrow-hit = The arrow hit {$chara}.
 throw-item = {$chara} threw one {$item}.
@@ -48,6 +49,8 @@ killed-by-ranged-attack = {$chara} was killed.
 killed-by-explosion = {$chara} was killed.
 killed-by-poison-damage = {$chara} was killed by poison.
 killed-by-starve-damage = {$chara} starved to death.
+killed-by-status-damage = {$chara} died from the status effect.
+killed-by-trap = {$chara} was killed by a trap.
 
 # Messages about character action
 
@@ -57,12 +60,19 @@ item-pickup = {$chara} picked up {$item}.
 item-pick-up-plant = {$item} is a living plant and cannot be moved.
 item-drop = {$chara} dropped {$item}.
 item-owned-by-others = {$item} is owned by others.
+theft-steal = {$chara} stole {$item}.
+theft-witnessed = {$chara} witnessed the theft!
 drink-item = {$chara} drank a {$item}.
 eat-item = {$chara} ate a {$item}.
 harvest-plant = {$chara} harvested {$item} x {$n}.
 harvest-plant-not-ready = {$item} cannot be harvested yet.
 use-active-skill-magic = {$chara} used magic "{$active_skill}".
 use-active-skill-special = {$chara} used special skill "{$active_skill}".
+active-skill-failed = {$chara} failed to use "{$active_skill}".
+active-skill-unavailable = "{$active_skill}" cannot be used now.
+active-skill-learned = {$chara} learned "{$active_skill}".
+active-skill-already-learned = {$chara} already knows "{$active_skill}".
+recover-mp = {$chara}'s mana was restored ({$value}).
 
 # Messages about using tools
 
@@ -72,6 +82,7 @@ building-shortage-material = Need {$item} x {$n} more to build it.
 chopping-no-tree = There is no tree to chop.
 chopping-not-adjacent-tile = Need to specify an adjacent tile to chop.
 mining-not-adjacent-tile = Need to specify an adjacent tile to mine.
+disarm-not-adjacent-tile = Need to specify an adjacent tile to disarm.
 
 # Messages about using items
 inventory-item-rotten = {$item} x {$n} in your inventory were rotten.
@@ -88,16 +99,32 @@ scanned = {$chara} was scanned.
 not-scanned = {$chara} isn't scanned.
 asleep = {$chara} is asleep.
 poison-damage = {$chara} was damaged by poison ({$damage}).
+status-inflicted = {$chara} is affected by {$status}.
+status-expired = {$chara} recovered from {$status}.
+status-damage = {$chara} was damaged by {$status} ({$damage}).
+status-prevent-action = {$chara} cannot act because of {$status}.
 shop-lack-of-money = {$chara} do not have enough money to buy it.
+shop-lack-of-funds = {$chara} does not have enough money to buy it.
+shop-refuse = {$chara} refuses to trade with you.
+shop-refuse-stolen = {$chara} does not buy stolen goods.
 
 # Messages about quest
 
 quest-complete-slay_monsters = Quest completed! Slayed {$monster} x {$n}.
+quest-complete = Quest completed! ({$quest})
+quest-failed = Quest failed... ({$quest})
+quest-escort-party-full = There is no room in your party for the escort.
 quest-reward-receive-money = Recieved {$money} gold as the quest reward.
+quest-reward-receive-item = Recieved {$item} as the quest reward.
+story-quest-start = New quest: {$quest}
+story-quest-updated = Quest updated: {$quest}
+story-quest-complete = Quest completed! ({$quest})
 
 # Messages about factions
 faction-relation-improve = Relations with {$faction} improved by {$value}.
 faction-relation-lower = Relations with {$faction} lowered by {$value}.
+faction-rank-changed = Your reputation with {$faction} is now {$rank}.
+faction-turn-hostile = {$faction} became hostile to you.
 
 # Messages about creation
 
@@ -117,8 +144,82 @@ harvest-plant = {$chara} harvested {$item} x {$n}.
 
 # Messages about party
 party-add-chara = {$chara} attended your party.
+party-full = There is no room in your party.
+party-hire = {$chara} was hired for {$wage} gold a day.
+party-hire-lack-of-money = You need {$amount} gold to pay the first wage.
+party-leave = {$chara} left your party.
+party-order-follow = You ordered {$chara} to follow you.
+party-order-wait = You ordered {$chara} to wait here.
+party-order-attack-target = You ordered {$chara} to attack your target.
+party-order-no-attack = You ordered {$chara} not to attack.
+party-wage-paid = You paid {$amount} gold in wages.
+party-wage-unpaid = You could not pay the wage of {$chara}. {$chara} is getting dissatisfied.
+
+# Messages about livestock
+livestock-born = {$chara} was born.
+livestock-feed = You fed {$item} to {$chara}.
+livestock-no-base = You need your base to keep livestock.
+livestock-no-food = You have no food that {$chara} eats.
+livestock-sent-to-base = {$chara} was sent to your base.
+livestock-tame = {$chara} was tamed!
+livestock-tame-failure = You failed to tame {$chara}.
+livestock-tame-no-item = You need {$item} to tame it.
+
+# Messages about player's base
+base-raid = Raiders are attacking your base!
+base-raid-stolen = Raiders stole {$item} from {$site}.
+base-visitor = {$chara} visited your base.
+
+# Messages about resting
+rest-finish = {$chara} finished resting.
+rest-hostile-in-view = You cannot rest while enemies are in sight.
+rest-interrupted-damage = {$chara} was interrupted by an attack!
+rest-interrupted-hostile = {$chara} noticed an enemy and stopped resting.
+rest-interrupted-hungry = {$chara} is too hungry to keep resting.
+rest-invalid-turns = Input the number of turns to wait.
+rest-no-need = {$chara} doesn't need to rest.
+rest-start = {$chara} started resting.
+rest-start-sleep = {$chara} went to sleep.
+rest-start-wait = {$chara} waits for {$turns} turns.
+rest-too-hungry = {$chara} is too hungry to rest.
+rest-wake-up = {$chara} woke up.
+
+# Messages about traps
+search-found-secret-passage = You found a secret passage!
+search-found-trap = You found a trap ({$trap})!
+search-nothing = You found nothing.
+search-start = You search around carefully.
+trap-alarm-ringing = An alarm rings loudly!
+trap-avoided = {$chara} avoided the trap ({$trap}).
+trap-disarm-failed = {$chara} failed to disarm the trap ({$trap}).
+trap-disarm-no-trap = There is no known trap.
+trap-disarmed = {$chara} disarmed the trap ({$trap}).
+trap-fall = {$chara} fell into a pit!
+trap-teleported = {$chara} was teleported.
+trap-triggered = {$chara} triggered a trap ({$trap})!
+
+# Messages about doors
+door-broken = The door was broken.
+door-cannot-close = Something is blocking the door.
+door-close = {$chara} closed the door.
+door-lock-pick-failed = {$chara} failed to pick the lock.
+door-lock-picked = {$chara} picked the lock.
+door-locked = The door is locked.
+door-open = {$chara} opened the door.
+door-unlock-key = {$chara} unlocked the door with {$item}.
+
+# Messages about wilderness
+wilderness-encounter = Someone is approaching...
+
+# Messages about travel between regions
+travel-not-enough-money = You don't have enough money.
+travel-portal = {$player} went through the portal to {$region}.
+travel-ship = {$player} sailed to {$region}.
+
+# Messages about dungeons
+dungeon-reclaimed = Someone has settled in this abandoned place.
+
 
-# Messages about script
 
 player-receive-item = {$chara} received {$item} x {$n}.
 player-receive-money = {$chara} received {$amount} gold.
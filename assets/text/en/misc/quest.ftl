@@ -1,2 +1,18 @@
 quest-slay_monsters = Slay monsters ({$monster})
+quest-fetch = Fetch items ({$item})
+quest-deliver = Deliver items ({$item})
+quest-escort = Escort ({$chara})
+quest-explore = Explore a dungeon
+quest-retrieve = Retrieve a relic ({$item})
+quest-craft = Craft items ({$item})
+quest_state-completed = (Completed)
+quest_state-failed = (Failed)
 desc-quest-slay_monsters = Slay "{$monster}" x {$n}.
+desc-quest-fetch = Bring "{$item}" x {$n}.
+desc-quest-deliver = Deliver "{$item}" x {$n} to {$site}.
+desc-quest-escort = Escort {$chara} to {$site}.
+desc-quest-explore = Reach floor {$floor} of {$site}.
+desc-quest-retrieve = Retrieve "{$item}" from floor {$floor} of {$site}.
+desc-quest-craft = Craft "{$item}" x {$n}.
+desc-quest-reward = Reward: {$money} gold
+desc-quest-deadline = Deadline: {$days} days
@@ -15,6 +15,15 @@ duration-minutes = minutes
 dungeon_kind-cave = Cave
 dungeon_kind-ruin = Ruin
 
+# Element
+
+element-physical = Physical
+element-fire = Fire
+element-cold = Cold
+element-shock = Shock
+element-poison = Poison
+element-spirit = Spirit
+
 # CharaStatus
 
 chara_status-hungry = Hungry
@@ -29,6 +38,14 @@ chara_status-asleep = Asleep
 chara_status-poisoned = Poisoned
 chara_status-work = Working
 
+# TrapKind
+
+trap-damage = Spike
+trap-poison = Poison needle
+trap-teleport = Teleport
+trap-alarm = Alarm
+trap-pit = Pit
+
 # ItemKind
 
 item_kind-potion = Potion
@@ -57,6 +74,8 @@ skill_kind-mining = Mining
 skill_kind-plants = Plants
 skill_kind-animal = Animals
 skill_kind-leadership = Leadership
+skill_kind-negotiation = Negotiation
+skill_kind-lockpicking = Lockpicking
 
 # WeaponKind
 
@@ -93,3 +112,13 @@ medical_effect-none = No effect
 medical_effect-heal = Heals HP
 medical_effect-sleep = Sleep
 medical_effect-poison = Poison
+
+# Reputation rank
+
+reputation_rank-hated = Hated
+reputation_rank-hostile = Hostile
+reputation_rank-unfriendly = Unfriendly
+reputation_rank-neutral = Neutral
+reputation_rank-friendly = Friendly
+reputation_rank-honored = Honored
+reputation_rank-exalted = Exalted
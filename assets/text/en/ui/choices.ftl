@@ -13,10 +13,23 @@ item_menu-information = Information
 item_menu-drop_all = Drop all
 item_menu-register-as-shortcut = Register as shortcut
 item_menu-select-building = Select building
+party-menu-follow = Follow me
+party-menu-wait = Wait here
+party-menu-attack-target = Attack my target
+party-menu-no-attack = Don't attack
+party-menu-status = Status and equipment
+party-menu-dismiss = Dismiss
+rest-menu-wait = Wait for some turns
+rest-menu-until-healed = Rest until healed
+rest-menu-sleep = Sleep until morning
 tile-menu-chop = Chop a tree
+tile-menu-close-door = Close the door
 tile-menu-down-stairs = Go down the stairs
 tile-menu-enter-site = Enter this site
+tile-menu-sail = Sail to another region
+tile-menu-enter-portal = Enter the portal
 tile-menu-enter-wilderness = Enter wilderness
+tile-menu-feed = Feed
 tile-menu-exit-to-region-map = Exit to region map
 tile-menu-deconstruct = Deconstruct
 tile-menu-harvest = Harvest
@@ -24,6 +37,13 @@ tile-menu-information = Information
 tile-menu-target = Target
 tile-menu-move-to-next-map = Move to next map
 tile-menu-pick-up-items = Pick up items
+tile-menu-party-member = Party member
+tile-menu-pick-lock = Pick the lock
+tile-menu-rest = Rest
+tile-menu-search = Search
+tile-menu-sleep = Sleep in the bed
+tile-menu-steal = Steal
+tile-menu-tame = Tame
 tile-menu-start-centering = Centering
 tile-menu-stop-centering = Stop centering
 tile-menu-up-stairs = Go up the stairs
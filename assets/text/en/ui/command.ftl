@@ -4,6 +4,7 @@ command-open_equip_win = Equipment
 command-open_exit_win = Open Exit Window
 command-open_game_info_win = Game Information
 command-open_help_win = Help
+command-open_quest_win = Quests
 command-open_status_win = Status
 command-open_item_menu = Item Menu
 command-pick_up_item = Pick Up Item
@@ -1 +1,4 @@
 item-charges = charges
+item-stolen = stolen
+shop-funds = Shop {$funds}G / {$money}G
+shop-price-breakdown = {$base}G CHA {$cha} Skill {$skill} Relation {$relation} Supply {$supply}
@@ -3,7 +3,10 @@ dialog-exit_to_regionmap = Do you want to exit to region map?
 dialog-gameover = You die...
 dialog-move_floor = Do you want to move from this floor?
 dialog-enter_site = Do you want to enter {$site_name}?
+dialog-sail = Do you want to sail to {$region} for {$fare} gold?
+dialog-enter-portal = Do you want to go through the portal to {$region}?
 dialog-undertake_quest = Do you undertake this quest?
+dialog-dismiss_party_member = Do you dismiss {$chara}?
 newgame-choose_class = Choose your class
 newgame-choose_trait = Choose your traits
 newgame-input_player_name = Please input your name
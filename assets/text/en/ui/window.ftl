@@ -28,6 +28,9 @@ button_text-creation-start = Start
 button_text-next = Next
 button_text-start = Start
 label_text-status-carry = Carry
+label_text-faction-ally = Allies
+label_text-faction-rival = Rivals
+label_text-faction-war = At war
 label_text-status-faction = Faction
 label_text-status-travel_speed = Travel Speed
 label_text-play_time = Play Time
@@ -39,4 +42,5 @@ label_text-creation-not-enough-ingredients = Not enough ingredients
 label_text-creation-required_skill = Required Skill
 list_header-faction = Faction
 list_header-relation = Relation
+list_header-reputation = Reputation
 list_item_text-creation-no_ingredient = No available {$group}
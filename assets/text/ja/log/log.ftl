@@ -40,6 +40,7 @@ no-ranged-weapon-equipped = 遠隔武器を装備していない。
 no-target = {$chara}はターゲットを見つけられなかった。
 target-chara = {$chara}は{$target}をターゲットにした。
 attack-evade = {$chara}は攻撃を避けた。
+attack-critical = {$chara}に会心の一撃！
 damaged-chara = {$chara}はダメージを受けた({$damage})。
 arrow-hit = 矢は{$chara}に命中した。
 throw-item = {$chara}は{$item}を投げた。
@@ -48,6 +49,8 @@ killed-by-ranged-attack = {$chara}は倒された。
 killed-by-explosion = {$chara}は爆死した。
 killed-by-poison-damage = {$chara}は毒により死んだ。
 killed-by-starve-damage = {$chara}は餓死した。
+killed-by-status-damage = {$chara}は状態異常により死んだ。
+killed-by-trap = {$chara}は罠で死んだ。
 
 # Messages about character action
 
@@ -57,12 +60,19 @@ item-pickup = {$chara}は{$item}を拾った。
 item-pick-up-plant = {$item}は植物であり、拾うことはできない。
 item-drop = {$chara}は{$item}を床に置いた。
 item-owned-by-others = {$item}は他者の所有物である。
+theft-steal = {$chara}は{$item}を盗んだ。
+theft-witnessed = {$chara}に盗みを目撃された！
 drink-item = {$chara}は{$item}を飲み干した。
 eat-item = {$chara}は{$item}を食べた。
 harvest-plant = {$chara}は{$item} x {$n}を収穫した。
 harvest-plant-not-ready = {$item}はまだ収穫できないようだ。
 use-active-skill-magic = {$chara}は"{$active_skill}"を使用した。
 use-active-skill-special = {$chara}は"{$active_skill}"を使用した。
+active-skill-failed = {$chara}は"{$active_skill}"の使用に失敗した。
+active-skill-unavailable = "{$active_skill}"は今は使用できない。
+active-skill-learned = {$chara}は"{$active_skill}"を習得した。
+active-skill-already-learned = {$chara}は既に"{$active_skill}"を習得している。
+recover-mp = {$chara}のMPが回復した({$value})。
 
 # Messages about using tools
 
@@ -72,6 +82,7 @@ building-shortage-material = 建築には{$item} x {$n}が必要だ。
 chopping-no-tree = 切り倒す木が無い。
 chopping-not-adjacent-tile = 木を切り倒すには隣接タイルを指定しなければならない。
 mining-not-adjacent-tile = 掘るには隣接タイルを指定しなければならない。
+disarm-not-adjacent-tile = 解除するには隣接タイルを指定しなければならない。
 
 # Messages about using items
 inventory-item-rotten = 荷物の中の{$item} x {$n}が腐敗した。
@@ -88,16 +99,32 @@ scanned = {$chara}のスキャンが完了した。
 not-scanned = {$chara}はまだスキャンされていない。
 asleep = {$chara}は眠っている。
 poison-damage = {$chara}は毒のダメージを受けた({$damage})。
+status-inflicted = {$chara}は{$status}状態になった。
+status-expired = {$chara}の{$status}状態が治った。
+status-damage = {$chara}は{$status}のダメージを受けた({$damage})。
+status-prevent-action = {$chara}は{$status}のため行動できない。
 shop-lack-of-money = {$chara}はそれを買うのに十分なお金を持っていない。
+shop-lack-of-funds = {$chara}にはそれを買い取るだけのお金がない。
+shop-refuse = {$chara}は取引を拒んだ。
+shop-refuse-stolen = {$chara}は盗品を買い取らない。
 
 # Messages about quest
 
 quest-complete-slay_monsters = {$monster}を{$n}体始末した。クエストを達成した。
+quest-complete = クエストを達成した。({$quest})
+quest-failed = クエストに失敗した…({$quest})
+quest-escort-party-full = 護衛対象を加える空きがパーティーにない。
 quest-reward-receive-money = {$money}ゴールドを報酬として受け取った。
+quest-reward-receive-item = {$item}を報酬として受け取った。
+story-quest-start = 新たなクエスト: {$quest}
+story-quest-updated = クエストが進行した: {$quest}
+story-quest-complete = クエストを達成した。({$quest})
 
 # Messages about factions
 faction-relation-improve = {$faction}との関係が改善した ({$value})。
 faction-relation-lower = {$faction}との関係が悪化した (-{$value})。
+faction-rank-changed = {$faction}からの評判が{$rank}になった。
+faction-turn-hostile = {$faction}が敵対した。
 
 # Messages about creation
 
@@ -115,8 +142,82 @@ harvest-plant = {$chara}は{$item} x {$n}を収穫した。
 
 # Messages about party
 party-add-chara = {$chara}があなたに付いてくことになった！
+party-full = パーティーに空きがない。
+party-hire = {$chara}を一日{$wage}ゴールドで雇った。
+party-hire-lack-of-money = 最初の賃金として{$amount}ゴールドが必要だ。
+party-leave = {$chara}はパーティーから去った。
+party-order-follow = {$chara}に付いてくるよう命じた。
+party-order-wait = {$chara}にここで待つよう命じた。
+party-order-attack-target = {$chara}にターゲットを攻撃するよう命じた。
+party-order-no-attack = {$chara}に攻撃しないよう命じた。
+party-wage-paid = 賃金として{$amount}ゴールドを支払った。
+party-wage-unpaid = {$chara}に賃金を支払えなかった。{$chara}は不満を募らせている。
+
+# Messages about livestock
+livestock-born = {$chara}が生まれた。
+livestock-feed = {$chara}に{$item}を与えた。
+livestock-no-base = 家畜を飼うには拠点が必要だ。
+livestock-no-food = {$chara}が食べる餌を持っていない。
+livestock-sent-to-base = {$chara}は拠点に送られた。
+livestock-tame = {$chara}を手懐けた！
+livestock-tame-failure = {$chara}を手懐けるのに失敗した。
+livestock-tame-no-item = 手懐けるには{$item}が必要だ。
+
+# Messages about player's base
+base-raid = 拠点が襲撃を受けている！
+base-raid-stolen = {$site}から{$item}が盗まれた。
+base-visitor = {$chara}が拠点を訪ねてきた。
+
+# Messages about resting
+rest-finish = {$chara}は休憩を終えた。
+rest-hostile-in-view = 敵が見えている間は休めない。
+rest-interrupted-damage = {$chara}は攻撃を受けて飛び起きた！
+rest-interrupted-hostile = {$chara}は敵に気付いて休憩をやめた。
+rest-interrupted-hungry = {$chara}は空腹で休んでいられない。
+rest-invalid-turns = 待つターン数を入力してください。
+rest-no-need = {$chara}は休む必要がない。
+rest-start = {$chara}は休憩を始めた。
+rest-start-sleep = {$chara}は眠りについた。
+rest-start-wait = {$chara}は{$turns}ターン待つことにした。
+rest-too-hungry = {$chara}は空腹で休めない。
+rest-wake-up = {$chara}は目を覚ました。
+
+# Messages about traps
+search-found-secret-passage = 隠し通路を見つけた！
+search-found-trap = 罠({$trap})を見つけた！
+search-nothing = 何も見つからなかった。
+search-start = 辺りを注意深く調べた。
+trap-alarm-ringing = 警報がけたたましく鳴り響いた！
+trap-avoided = {$chara}は罠({$trap})を避けた。
+trap-disarm-failed = {$chara}は罠({$trap})の解除に失敗した。
+trap-disarm-no-trap = 既知の罠がない。
+trap-disarmed = {$chara}は罠({$trap})を解除した。
+trap-fall = {$chara}は落とし穴に落ちた！
+trap-teleported = {$chara}は転移させられた。
+trap-triggered = {$chara}は罠({$trap})を作動させた！
+
+# Messages about doors
+door-broken = 扉が壊れた。
+door-cannot-close = 何かが扉を塞いでいる。
+door-close = {$chara}は扉を閉めた。
+door-lock-pick-failed = {$chara}は鍵をこじ開けるのに失敗した。
+door-lock-picked = {$chara}は鍵をこじ開けた。
+door-locked = 扉には鍵がかかっている。
+door-open = {$chara}は扉を開けた。
+door-unlock-key = {$chara}は{$item}で扉の鍵を開けた。
+
+# Messages about wilderness
+wilderness-encounter = 何者かが近づいてくる…
+
+# Messages about travel between regions
+travel-not-enough-money = お金が足りない。
+travel-portal = {$player}はポータルを通って{$region}へ移動した。
+travel-ship = {$player}は{$region}へ航海した。
+
+# Messages about dungeons
+dungeon-reclaimed = 打ち捨てられたこの場所に何者かが住み着いたようだ。
+
 
-# Messages about script
 
 player-receive-item = {$chara}は{$item} x {$n}を受け取った。
 player-receive-money = {$chara}は{$amount}ゴールドを受け取った。
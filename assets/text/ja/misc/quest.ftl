@@ -1,2 +1,18 @@
 quest-slay_monsters = モンスター討伐 ({$monster})
+quest-fetch = 調達 ({$item})
+quest-deliver = 配達 ({$item})
+quest-escort = 護衛 ({$chara})
+quest-explore = ダンジョン探索
+quest-retrieve = 遺物の回収 ({$item})
+quest-craft = 製作 ({$item})
+quest_state-completed = (達成)
+quest_state-failed = (失敗)
 desc-quest-slay_monsters = {$monster}を{$n}体倒す。
+desc-quest-fetch = 「{$item}」を{$n}個持ってくる。
+desc-quest-deliver = 「{$item}」{$n}個を{$site}へ届ける。
+desc-quest-escort = {$chara}を{$site}まで護衛する。
+desc-quest-explore = {$site}の{$floor}階に到達する。
+desc-quest-retrieve = {$site}の{$floor}階から「{$item}」を回収する。
+desc-quest-craft = 「{$item}」を{$n}個製作する。
+desc-quest-reward = 報酬: {$money}ゴールド
+desc-quest-deadline = 期限: {$days}日
@@ -15,6 +15,15 @@ duration-minutes = 分
 dungeon_kind-cave = 洞窟
 dungeon_kind-ruin = 遺跡
 
+# Element
+
+element-physical = 物理
+element-fire = 火炎
+element-cold = 冷気
+element-shock = 電撃
+element-poison = 毒
+element-spirit = 霊
+
 # CharaStatus
 
 chara_status-hungry = 空腹
@@ -29,6 +38,14 @@ chara_status-asleep = 睡眠
 chara_status-poisoned = 毒
 chara_status-work = 作業中
 
+# TrapKind
+
+trap-damage = 棘
+trap-poison = 毒針
+trap-teleport = 転移
+trap-alarm = 警報
+trap-pit = 落とし穴
+
 # ItemKind
 
 item_kind-potion = 薬
@@ -57,6 +74,8 @@ skill_kind-mining = 採掘
 skill_kind-plants = 栽培
 skill_kind-animal = 動物
 skill_kind-leadership = 統率
+skill_kind-negotiation = 交渉
+skill_kind-lockpicking = 開錠
 
 # WeaponKind
 
@@ -93,3 +112,13 @@ medical_effect-none = 効果なし
 medical_effect-heal = HP回復
 medical_effect-sleep = 睡眠
 medical_effect-poison = 毒
+
+# Reputation rank
+
+reputation_rank-hated = 憎悪
+reputation_rank-hostile = 敵対
+reputation_rank-unfriendly = 非友好
+reputation_rank-neutral = 中立
+reputation_rank-friendly = 友好
+reputation_rank-honored = 尊敬
+reputation_rank-exalted = 崇拝
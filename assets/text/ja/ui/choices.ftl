@@ -13,10 +13,23 @@ item_menu-information = 情報
 item_menu-drop_all = 全部置く
 item_menu-register-as-shortcut = ショートカット登録
 item_menu-select-building = 建造物選択
+party-menu-follow = 付いてこい
+party-menu-wait = ここで待て
+party-menu-attack-target = ターゲットを攻撃しろ
+party-menu-no-attack = 攻撃するな
+party-menu-status = ステータスと装備
+party-menu-dismiss = 解雇する
+rest-menu-wait = 数ターン待つ
+rest-menu-until-healed = 回復するまで休む
+rest-menu-sleep = 朝まで眠る
 tile-menu-chop = 木を切る
+tile-menu-close-door = 扉を閉める
 tile-menu-down-stairs = 階段を降りる
 tile-menu-enter-site = 入る
+tile-menu-sail = 航海する
+tile-menu-enter-portal = ポータルに入る
 tile-menu-enter-wilderness = 野外に入る
+tile-menu-feed = 餌をやる
 tile-menu-exit-to-region-map = 外に出る
 tile-menu-deconstruct = 解体する
 tile-menu-harvest = 収穫する
@@ -24,6 +37,13 @@ tile-menu-information = 情報
 tile-menu-target = ターゲット
 tile-menu-move-to-next-map = 次のマップへ
 tile-menu-pick-up-items = 拾う
+tile-menu-party-member = パーティーメンバー
+tile-menu-pick-lock = 鍵をこじ開ける
+tile-menu-rest = 休む
+tile-menu-search = 調べる
+tile-menu-sleep = ベッドで眠る
+tile-menu-steal = 盗む
+tile-menu-tame = 手懐ける
 tile-menu-start-centering = センタリング
 tile-menu-stop-centering = センタリングをやめる
 tile-menu-up-stairs = 階段を上がる
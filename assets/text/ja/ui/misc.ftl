@@ -1 +1,4 @@
 item-charges = チャージ回数
+item-stolen = 盗品
+shop-funds = 店 {$funds}G / {$money}G
+shop-price-breakdown = 基本{$base}G 魅力{$cha} 交渉{$skill} 関係{$relation} 需給{$supply}
@@ -3,7 +3,10 @@ dialog-exit_to_regionmap = リージョンマップに戻りますか?
 dialog-gameover = やられてしまった…。
 dialog-move_floor = この階から移動しますか?
 dialog-enter_site = {$site_name}に入りますか?
+dialog-sail = {$fare}ゴールドを払って{$region}へ航海しますか?
+dialog-enter-portal = ポータルを通って{$region}へ移動しますか?
 dialog-undertake_quest = このクエストを受諾しますか?
+dialog-dismiss_party_member = {$chara}を解雇しますか?
 newgame-choose_class = クラスを選択して下さい
 newgame-choose_trait = 特性を選択して下さい
 newgame-input_player_name = プレイヤー名を入力して下さい
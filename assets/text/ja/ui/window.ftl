@@ -28,6 +28,9 @@ button_text-creation-start = スタート
 button_text-next = 次へ
 button_text-start = スタート
 label_text-status-carry = 運搬力
+label_text-faction-ally = 同盟
+label_text-faction-rival = 対立
+label_text-faction-war = 交戦
 label_text-status-faction = 所属
 label_text-status-travel_speed = 移動速度
 label_text-play_time = プレイ時間
@@ -39,4 +42,5 @@ label_text-creation-not-enough-ingredients = 原材料不足
 label_text-creation-required_skill = 必要スキル
 list_header-faction = 派閥
 list_header-relation = 関係値
+list_header-reputation = 評判
 list_item_text-creation-no_ingredient = 利用不能な素材 {$group}
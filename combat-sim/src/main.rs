@@ -0,0 +1,469 @@
+#![warn(
+    rust_2018_compatibility,
+    rust_2018_idioms,
+    future_incompatible,
+    nonstandard_style
+)]
+
+extern crate rusted_ruins_common as common;
+extern crate rusted_ruins_rng as rng;
+extern crate rusted_ruins_rules as rules;
+
+use common::basic::WAIT_TIME_NUMERATOR;
+use common::gamedata::*;
+use common::gobj;
+use common::objholder::{CharaTemplateIdx, ItemIdx};
+use rules::combat::Combat;
+use rules::RULES;
+use std::env;
+use std::path::PathBuf;
+
+/// Stop a fight if it does not finish in this number of actions.
+const MAX_ACTIONS: u32 = 10000;
+
+/// Weapons and bare hands attack by physical element as in the game
+const ATTACK_ELEMENT: Element = Element::Physical;
+
+fn main() {
+    let matches = create_matches();
+
+    let assets_dir = if let Some(dir) = matches.value_of("assets") {
+        PathBuf::from(dir)
+    } else if let Some(dir) = env::var_os("RUSTED_RUINS_ASSETS_DIR") {
+        PathBuf::from(dir)
+    } else {
+        PathBuf::from("assets")
+    };
+    gobj::init(vec![assets_dir.join("paks")]);
+    rules::init(&assets_dir, None);
+    rng::reseed(matches.is_present("fixed-seed"));
+
+    let n: u32 = matches
+        .value_of("trials")
+        .unwrap()
+        .parse()
+        .expect("invalid number of trials");
+    let combatants = [
+        Combatant::new(
+            matches.value_of("CHARA1").unwrap(),
+            matches.values_of("equip1").into_iter().flatten(),
+            parse_skill_level(matches.value_of("skill1").unwrap()),
+        ),
+        Combatant::new(
+            matches.value_of("CHARA2").unwrap(),
+            matches.values_of("equip2").into_iter().flatten(),
+            parse_skill_level(matches.value_of("skill2").unwrap()),
+        ),
+    ];
+
+    let mut stats = [Stats::default(), Stats::default()];
+    for _ in 0..n {
+        fight(&RULES.combat, &combatants, &mut stats);
+    }
+
+    for (combatant, stats) in combatants.iter().zip(stats.iter()) {
+        stats.print(combatant, n);
+    }
+}
+
+fn parse_skill_level(s: &str) -> u32 {
+    s.parse().expect("invalid skill level")
+}
+
+/// Weapon used by a combatant
+struct Weapon {
+    kind: WeaponKind,
+    power: BasePower,
+}
+
+/// Parameters of a character for combat simulation.
+/// Modifiers are collected in the same way as the total modifier of characters in the game.
+struct Combatant {
+    id: String,
+    max_hp: i32,
+    str: u16,
+    vit: u16,
+    dex: u16,
+    spd: u16,
+    skill_level: u32,
+    weapon: Option<Weapon>,
+    /// Equipment defence for each element
+    defence: ElementArray<u32>,
+    /// Element protection from the template, class, race and equipment
+    protection: ElementArray<i16>,
+}
+
+impl Combatant {
+    fn new<'a, I: Iterator<Item = &'a str>>(id: &str, equips: I, skill_level: u32) -> Combatant {
+        let idx: CharaTemplateIdx = gobj::id_to_idx_checked(id)
+            .unwrap_or_else(|| panic!("unknown character template \"{}\"", id));
+        let ct = gobj::get_obj(idx);
+        let class = RULES.classes.get(ct.class);
+
+        let mut attr = class.attr;
+        let mut protection = ElementArray::default();
+        add_element_protection(&mut protection, &ct.element_protection);
+        add_element_protection(&mut protection, &class.element_protection);
+        let mut modifiers = Vec::new();
+        if let Some(race) = RULES.races.get(&ct.race) {
+            add_element_protection(&mut protection, &race.element_protection);
+            for race_trait in &race.traits {
+                if let CharaTrait::Id(id) = race_trait {
+                    modifiers.extend(RULES.chara_traits.get(id).modifiers.iter());
+                }
+            }
+        }
+
+        let mut weapon = None;
+        let mut defence: ElementArray<u32> = ElementArray::default();
+        for item_id in equips {
+            let idx: ItemIdx = gobj::id_to_idx_checked(item_id)
+                .unwrap_or_else(|| panic!("unknown item \"{}\"", item_id));
+            let item_obj = gobj::get_obj(idx);
+            for attr in &item_obj.attrs {
+                match (item_obj.kind, attr) {
+                    (ItemKind::Weapon(kind), ItemObjAttr::WeaponPower(power)) => {
+                        weapon = Some(Weapon {
+                            kind,
+                            power: *power,
+                        });
+                    }
+                    (_, ItemObjAttr::Defence(def)) => {
+                        for e in &ELEMENTS {
+                            defence[*e] = defence[*e].saturating_add(def[*e].into());
+                        }
+                    }
+                    (_, ItemObjAttr::CharaModifiers(m)) => {
+                        modifiers.extend(m.iter());
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        for modifier in modifiers {
+            match modifier {
+                CharaModifier::Str(value) => attr.str += value,
+                CharaModifier::Vit(value) => attr.vit += value,
+                CharaModifier::Dex(value) => attr.dex += value,
+                CharaModifier::Spd(value) => attr.spd += value,
+                CharaModifier::ElementProtection(element, value) if *element != Element::None => {
+                    protection[*element] += *value as i16;
+                }
+                _ => (),
+            }
+        }
+
+        let base = &ct.base_attr;
+        let base_hp = (base.base_hp + attr.base_hp).max(1);
+        Combatant {
+            id: id.to_owned(),
+            max_hp: ((skill_level as i32 + 8) * base_hp / 8).max(1),
+            str: (base.str + attr.str).max(1) as u16,
+            vit: (base.vit + attr.vit).max(1) as u16,
+            dex: (base.dex + attr.dex).max(1) as u16,
+            spd: (base.spd + attr.spd).max(1) as u16,
+            skill_level,
+            weapon,
+            defence,
+            protection,
+        }
+    }
+
+    fn weapon_kind(&self) -> Option<WeaponKind> {
+        self.weapon.as_ref().map(|weapon| weapon.kind)
+    }
+
+    fn weapon_power(&self, c: &Combat) -> f32 {
+        if let Some(weapon) = &self.weapon {
+            let base = weapon.power.0.into_inner();
+            let var = weapon.power.1.into_inner();
+            let min = (base - var).max(0.0);
+            let max = base + var;
+            if max > min {
+                rng::gen_range(min..max)
+            } else {
+                min
+            }
+        } else {
+            self.skill_level as f32 * c.bare_hand_power_factor
+                + c.bare_hand_power_base
+                + (1.0 + rng::gen_range(0.0..c.bare_hand_power_var))
+        }
+    }
+
+    fn hit_probability(&self, target: &Combatant, c: &Combat) -> f32 {
+        let hit_power = c.hit_power(self.dex, self.skill_level);
+        let evasion_power = c.evasion_power(target.skill_level, target.dex);
+        c.hit_probability(hit_power, evasion_power)
+    }
+
+    fn critical_probability(&self, c: &Combat) -> f32 {
+        c.critical_probability(self.dex, self.skill_level, self.weapon_kind())
+    }
+
+    /// Damage before critical hit judgement by given weapon power
+    fn damage(&self, target: &Combatant, c: &Combat, weapon_power: f32) -> f32 {
+        let is_ranged = matches!(self.weapon_kind(), Some(kind) if !kind.is_melee());
+        let attr = if is_ranged { self.dex } else { self.str };
+        c.damage(
+            c.attack_power(attr, self.skill_level) * weapon_power,
+            c.defence_power(
+                target.defence[ATTACK_ELEMENT],
+                target.vit,
+                target.skill_level,
+            ),
+            target.protection[ATTACK_ELEMENT],
+        )
+    }
+
+    /// Attack the target once and returns damage
+    fn attack(&self, target: &Combatant, c: &Combat, stats: &mut Stats) -> i32 {
+        stats.attacks += 1;
+        if !rng::gen_bool(self.hit_probability(target, c)) {
+            return 0;
+        }
+        stats.hits += 1;
+
+        let mut damage = self.damage(target, c, self.weapon_power(c));
+        if rng::gen_bool(self.critical_probability(c)) {
+            stats.criticals += 1;
+            damage *= c.critical_multiplier(self.weapon_kind());
+        }
+        let damage = damage.floor() as i32;
+        stats.damage += damage as u64;
+        damage
+    }
+}
+
+fn add_element_protection(
+    protection: &mut ElementArray<i16>,
+    element_protection: &ElementArray<ElementProtection>,
+) {
+    for e in &ELEMENTS {
+        protection[*e] += element_protection[*e].value() as i16;
+    }
+}
+
+#[derive(Default)]
+struct Stats {
+    attacks: u64,
+    hits: u64,
+    criticals: u64,
+    damage: u64,
+    wins: u32,
+    /// Sum of turns needed to kill the opponent in won fights
+    turns_to_kill: u64,
+}
+
+impl Stats {
+    fn print(&self, combatant: &Combatant, n: u32) {
+        let ratio = |a: u64, b: u64| if b > 0 { a as f64 / b as f64 } else { 0.0 };
+
+        println!("{}", combatant.id);
+        println!(
+            "  HP {}, weapon {:?}, defence {}, skill level {}",
+            combatant.max_hp,
+            combatant.weapon_kind(),
+            combatant.defence[ATTACK_ELEMENT],
+            combatant.skill_level,
+        );
+        println!("  hit rate        {:.3}", ratio(self.hits, self.attacks));
+        println!("  critical rate   {:.3}", ratio(self.criticals, self.hits));
+        println!("  damage per turn {:.2}", ratio(self.damage, self.attacks));
+        println!("  win rate        {:.3}", ratio(self.wins.into(), n.into()));
+        println!(
+            "  time to kill    {:.2} turns",
+            ratio(self.turns_to_kill, self.wins.into())
+        );
+    }
+}
+
+/// Simulate one fight until one of the combatants dies
+fn fight(c: &Combat, combatants: &[Combatant; 2], stats: &mut [Stats; 2]) {
+    let mut hp = [combatants[0].max_hp, combatants[1].max_hp];
+    let mut wait_time = [0, 0];
+    let mut turns = [0, 0];
+
+    for _ in 0..MAX_ACTIONS {
+        let i = if wait_time[0] <= wait_time[1] { 0 } else { 1 };
+        let j = 1 - i;
+        wait_time[i] += WAIT_TIME_NUMERATOR / combatants[i].spd as u32;
+        turns[i] += 1;
+
+        hp[j] -= combatants[i].attack(&combatants[j], c, &mut stats[i]);
+        if hp[j] <= 0 {
+            stats[i].wins += 1;
+            stats[i].turns_to_kill += turns[i];
+            return;
+        }
+    }
+}
+
+fn create_matches() -> clap::ArgMatches<'static> {
+    use clap::{App, Arg};
+
+    App::new("rusted-ruins-combat-sim")
+        .about("Combat simulator for balancing Rusted Ruins")
+        .arg(
+            Arg::with_name("assets")
+                .long("assets")
+                .value_name("DIR")
+                .help("Set assets directory that includes paks and rules")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("trials")
+                .short("n")
+                .long("trials")
+                .value_name("N")
+                .help("The number of fights")
+                .takes_value(true)
+                .default_value("10000"),
+        )
+        .arg(
+            Arg::with_name("fixed-seed")
+                .long("fixed-seed")
+                .help("Use fixed random seed"),
+        )
+        .arg(
+            Arg::with_name("equip1")
+                .long("equip1")
+                .value_name("ITEM")
+                .help("Equipment of the first character")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+        )
+        .arg(
+            Arg::with_name("equip2")
+                .long("equip2")
+                .value_name("ITEM")
+                .help("Equipment of the second character")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+        )
+        .arg(
+            Arg::with_name("skill1")
+                .long("skill1")
+                .value_name("LEVEL")
+                .help("Skill level of the first character")
+                .takes_value(true)
+                .default_value("10"),
+        )
+        .arg(
+            Arg::with_name("skill2")
+                .long("skill2")
+                .value_name("LEVEL")
+                .help("Skill level of the second character")
+                .takes_value(true)
+                .default_value("10"),
+        )
+        .arg(
+            Arg::with_name("CHARA1")
+                .help("Character template id")
+                .index(1)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("CHARA2")
+                .help("Character template id")
+                .index(2)
+                .required(true),
+        )
+        .get_matches()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ordered_float::NotNan;
+    use rules::combat::{CombatFormula, Critical};
+
+    fn combat() -> Combat {
+        Combat {
+            skill_base: 8.0,
+            throw_range_factor: 1,
+            throw_range_max: 10,
+            bare_hand_power_base: 1.0,
+            bare_hand_power_factor: 0.1,
+            bare_hand_power_var: 1.0,
+            detection_range: 5,
+            detection_factor: 1.0,
+            formula: CombatFormula::default(),
+            critical: Critical::default(),
+        }
+    }
+
+    fn combatant(weapon: Option<Weapon>, defence: u32, protection: i16) -> Combatant {
+        let mut defence_array = ElementArray::default();
+        defence_array[Element::Physical] = defence;
+        // Defence and protection of other elements must not affect physical attacks
+        defence_array[Element::Fire] = 1000;
+        let mut protection_array = ElementArray::default();
+        protection_array[Element::Physical] = protection;
+        protection_array[Element::Fire] = 100;
+        Combatant {
+            id: "test".into(),
+            max_hp: 100,
+            str: 4,
+            vit: 2,
+            dex: 1,
+            spd: 100,
+            skill_level: 0,
+            weapon,
+            defence: defence_array,
+            protection: protection_array,
+        }
+    }
+
+    fn sword() -> Weapon {
+        Weapon {
+            kind: WeaponKind::Sword,
+            power: BasePower(NotNan::new(10.0).unwrap(), NotNan::new(0.0).unwrap()),
+        }
+    }
+
+    #[test]
+    fn expected_rates() {
+        let c = combat();
+        let attacker = combatant(Some(sword()), 0, 0);
+        let target = combatant(None, 4, 25);
+
+        // hit power 1 * (0 + 8) = 8, evasion power 1 + 0 + 1 * 0.125 = 1.125
+        let hit = attacker.hit_probability(&target, &c);
+        assert!((hit - 1.0 / (1.0 + (-6.875f32 * 0.125).exp())).abs() < 1e-6);
+        assert!((hit - 0.7025).abs() < 1e-3);
+
+        // 0.02 + 1 * 0.001 + 0 * 0.002
+        assert!((attacker.critical_probability(&c) - 0.021).abs() < 1e-6);
+
+        // attack power 4^2 * 8^1.5 * 10, defence power (4 + 16) * 2 * 8, protection 25%
+        let damage = attacker.damage(&target, &c, attacker.weapon_power(&c));
+        let expected = 16.0 * 8.0f32.powf(1.5) * 10.0 / 320.0 * 0.75;
+        assert!((damage - expected).abs() < 1e-3);
+        assert!((damage - 8.485).abs() < 1e-2);
+    }
+
+    #[test]
+    fn simulated_rates() {
+        let c = combat();
+        let combatants = [
+            combatant(Some(sword()), 0, 0),
+            combatant(Some(sword()), 4, 25),
+        ];
+        let mut stats = [Stats::default(), Stats::default()];
+        rng::reseed_with(0);
+        for _ in 0..1000 {
+            fight(&c, &combatants, &mut stats);
+        }
+
+        let stats = &stats[0];
+        let hit_rate = stats.hits as f32 / stats.attacks as f32;
+        let critical_rate = stats.criticals as f32 / stats.hits as f32;
+        assert!(stats.attacks > 5000);
+        assert!((hit_rate - combatants[0].hit_probability(&combatants[1], &c)).abs() < 0.02);
+        assert!((critical_rate - combatants[0].critical_probability(&c)).abs() < 0.01);
+    }
+}
@@ -1,10 +1,13 @@
-use super::defs::{ActiveSkillId, ActiveSkillOrigin, Recipe};
+use super::defs::{ActiveSkillId, ActiveSkillOrigin, ElementArray, ElementProtection, Recipe};
 use super::faction::FactionId;
-use super::item::{EquipItemList, EquipSlotKind, Item, ItemList, ItemLocation, MaterialName};
+use super::item::{
+    EquipItemList, EquipSlotKind, Item, ItemList, ItemLocation, ItemTime, MaterialName,
+};
 use super::map::MapId;
 use super::modifier::CharaTotalModifier;
 use super::site::SiteId;
 use super::skill::{CreationKind, SkillKind, SkillList};
+use super::time::Time;
 use super::{traits::*, unknown_id_err, UniqueId};
 use crate::basic::{ArrayStringId, BonusLevel};
 use crate::objholder::{CharaTemplateIdx, ItemIdx};
@@ -30,6 +33,9 @@ pub struct CharaTemplateObject {
     /// Default AI kind for this character
     pub default_ai_kind: NpcAiKind,
     pub base_attr: CharaBaseAttr,
+    /// Intrinsic element protection of this character
+    #[serde(default)]
+    pub element_protection: ElementArray<ElementProtection>,
     pub skill_bonus: HashMap<SkillKind, BonusLevel>,
     /// Learned active skills.
     pub active_skills: Vec<ActiveSkillId>,
@@ -71,14 +77,22 @@ pub struct Chara {
     pub ai: CharaAi,
     pub hp: i32,
     pub sp: f32,
+    #[serde(default)]
+    pub mp: i32,
     pub tm: Box<CharaTotalModifier>,
     pub morale: Morale,
     pub traits: Vec<(CharaTraitOrigin, CharaTrait)>,
     pub status: Vec<CharaStatus>,
     pub skills: SkillList,
     pub active_skills: Vec<(ActiveSkillOrigin, ActiveSkillId)>,
+    /// Remaining turns until active skills become available again
+    #[serde(default)]
+    pub active_skill_cooldowns: HashMap<ActiveSkillId, u16>,
     /// When talked, execute this script
     pub trigger_talk: Option<String>,
+    /// State as livestock if this character is tamed
+    #[serde(default)]
+    pub livestock: Option<Box<Livestock>>,
 }
 
 /// Character attributes
@@ -88,6 +102,9 @@ pub struct Chara {
 pub struct CharaAttributes {
     /// Max HP
     pub max_hp: i32,
+    /// Max MP
+    #[serde(default)]
+    pub max_mp: i32,
     /// Strength
     pub str: u16,
     /// Vitality
@@ -162,6 +179,12 @@ pub enum CharaStatus {
         needed_turn: u16,
         work: Work,
     },
+    /// Status effect defined in rules. Continues until cured if turn_left is None.
+    Defined {
+        id: String,
+        turn_left: Option<u16>,
+        stack: u16,
+    },
 }
 
 impl CharaStatus {
@@ -170,6 +193,7 @@ impl CharaStatus {
             &CharaStatus::Asleep { turn_left } | &CharaStatus::Work { turn_left, .. } => {
                 Some(turn_left)
             }
+            &CharaStatus::Defined { turn_left, .. } => turn_left,
             _ => None,
         }
     }
@@ -179,6 +203,7 @@ impl CharaStatus {
             CharaStatus::Asleep { turn_left } | CharaStatus::Work { turn_left, .. } => {
                 Some(turn_left)
             }
+            CharaStatus::Defined { turn_left, .. } => turn_left.as_mut(),
             _ => None,
         }
     }
@@ -196,6 +221,22 @@ pub enum Work {
         item_idx: ItemIdx,
         il: ItemLocation,
     },
+    Rest {
+        kind: RestKind,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum RestKind {
+    /// Wait for the given turns
+    Wait,
+    /// Rest until HP and MP are recovered
+    UntilHealed,
+    /// Sleep until the given time. Bed quality affects recovery.
+    Sleep {
+        until: Time,
+        bed_quality: Option<i8>,
+    },
 }
 
 impl Default for Chara {
@@ -213,17 +254,32 @@ impl Default for Chara {
             ai: CharaAi::default(),
             hp: 100,
             sp: 0.0,
+            mp: 0,
             morale: Morale::default(),
             tm: Box::default(),
             traits: Vec::new(),
             status: Vec::new(),
             skills: SkillList::default(),
             active_skills: Vec::new(),
+            active_skill_cooldowns: HashMap::new(),
             trigger_talk: None,
+            livestock: None,
         }
     }
 }
 
+/// State of a tamed creature.
+/// Remaining durations are updated like time stamps of items.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Livestock {
+    /// Time until producing items
+    pub product_time: ItemTime,
+    /// Time until the next breeding chance
+    pub breed_time: ItemTime,
+    /// Time until getting hungry
+    pub food_time: ItemTime,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 pub enum CharaId {
     /// Player is unique character in the game
@@ -355,12 +411,12 @@ impl CharaHolder {
         .is_some()
     }
 
-    pub(crate) fn remove_chara(&mut self, cid: CharaId) {
+    pub(crate) fn remove_chara(&mut self, cid: CharaId) -> Option<Chara> {
         match cid {
             CharaId::OnMap { .. } => &mut self.on_map,
             _ => &mut self.c,
         }
-        .remove(&cid);
+        .remove(&cid)
     }
 
     pub(crate) fn replace_on_map_chara(
@@ -48,10 +48,22 @@ impl<T> IndexMut<Element> for ElementArray<T> {
     }
 }
 
+/// Protection against an element in percent.
+/// Positive values mean resistance, and negative values mean weakness.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct ElementProtection(i8);
 
+impl ElementProtection {
+    pub fn new(value: i8) -> Self {
+        ElementProtection(value)
+    }
+
+    pub fn value(self) -> i8 {
+        self.0
+    }
+}
+
 /// A recipe for creation
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
 pub struct Recipe {
@@ -78,6 +90,8 @@ pub enum ToolEffect {
     Build,
     Chop,
     Mine,
+    /// Disarm traps
+    Disarm,
 }
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
@@ -149,6 +163,12 @@ pub struct ActiveSkill {
     pub cost_sp: u32,
     #[serde(default)]
     pub cost_mp: u32,
+    /// Turns until this skill can be used again
+    #[serde(default)]
+    pub cooldown: u16,
+    /// Skill level required to use this skill without failure
+    #[serde(default)]
+    pub difficulty: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
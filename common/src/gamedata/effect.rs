@@ -80,11 +80,15 @@ impl Default for TargetMode {
 }
 
 /// Effect defines the game effect of items, magics, or other active skills.
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
 pub enum StatusEffect {
     Asleep,
     Poison,
     Scanned,
+    /// Status effect defined in rules
+    Defined {
+        id: String,
+    },
 }
 
 /// Animation kind for this effect.
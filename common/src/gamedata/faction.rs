@@ -79,6 +79,12 @@ pub struct FactionRelation(i16);
 const FACTION_RELATION_MAX: i16 = 10000;
 const FACTION_RELATION_MIN: i16 = -10000;
 
+impl FactionRelation {
+    pub fn new(value: i16) -> FactionRelation {
+        FactionRelation(value.clamp(FACTION_RELATION_MIN, FACTION_RELATION_MAX))
+    }
+}
+
 impl From<FactionRelation> for i16 {
     fn from(relation: FactionRelation) -> i16 {
         relation.0
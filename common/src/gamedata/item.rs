@@ -354,6 +354,10 @@ pub enum ItemListLocation {
     Shop {
         cid: super::CharaId,
     },
+    /// Shared storage of a player's base
+    BaseStorage {
+        sid: super::site::SiteId,
+    },
     Container {
         ill: ItemListLocationExceptContainer,
         i: u32,
@@ -369,6 +373,9 @@ impl ItemListLocation {
             ItemListLocation::Chara { cid } => ItemListLocationExceptContainer::Chara { cid },
             ItemListLocation::Equip { cid } => ItemListLocationExceptContainer::Equip { cid },
             ItemListLocation::Shop { cid } => ItemListLocationExceptContainer::Shop { cid },
+            ItemListLocation::BaseStorage { sid } => {
+                ItemListLocationExceptContainer::BaseStorage { sid }
+            }
             ItemListLocation::Container { .. } => {
                 panic!("tried to get item list in an item in container")
             }
@@ -397,6 +404,9 @@ impl From<ItemListLocationExceptContainer> for ItemListLocation {
             ItemListLocationExceptContainer::Chara { cid } => ItemListLocation::Chara { cid },
             ItemListLocationExceptContainer::Equip { cid } => ItemListLocation::Equip { cid },
             ItemListLocationExceptContainer::Shop { cid } => ItemListLocation::Shop { cid },
+            ItemListLocationExceptContainer::BaseStorage { sid } => {
+                ItemListLocation::BaseStorage { sid }
+            }
         }
     }
 }
@@ -407,6 +417,7 @@ pub enum ItemListLocationExceptContainer {
     Chara { cid: super::chara::CharaId },
     Equip { cid: super::chara::CharaId },
     Shop { cid: super::CharaId },
+    BaseStorage { sid: super::site::SiteId },
 }
 
 pub type ItemLocation = (ItemListLocation, u32);
@@ -511,6 +522,13 @@ impl ItemList {
             .sum::<u32>()
     }
 
+    /// Count items which satisfy the condition
+    pub fn count_if<F: Fn(&Item) -> bool>(&self, f: F) -> u32 {
+        self.iter()
+            .filter_map(|(item, n)| if f(item) { Some(n) } else { None })
+            .sum::<u32>()
+    }
+
     /// Retains item by given number
     pub fn retain<F: FnMut(&Item, u32) -> u32>(&mut self, mut f: F, reverse_order: bool) {
         let iter = std::mem::take(&mut self.items).into_iter();
@@ -566,6 +584,23 @@ impl ItemList {
         );
         assert_eq!(need_consumed, 0);
     }
+
+    /// Consume items which satisfy the condition
+    pub fn consume_if<F: Fn(&Item) -> bool>(&mut self, consume: u32, f: F) {
+        let mut need_consumed = consume;
+        self.retain(
+            |item, n| {
+                if !f(item) {
+                    return n;
+                }
+                let consumed = need_consumed.min(n);
+                need_consumed -= consumed;
+                n - consumed
+            },
+            false,
+        );
+        assert_eq!(need_consumed, 0);
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
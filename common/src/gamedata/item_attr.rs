@@ -2,6 +2,7 @@ use crate::item_selector::ItemSelector;
 
 use super::defs::*;
 use super::effect::Effect;
+use super::modifier::CharaModifier;
 use super::time::{Duration, Time};
 use super::{item::*, BasePower, UniqueId, UniqueIdGenerator};
 use std::cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd};
@@ -43,6 +44,8 @@ pub enum ItemObjAttr {
     WeaponPower(BasePower),
     /// Defence for armor items
     Defence(ElementArray<u16>),
+    /// Modifiers for the character equipping this item
+    CharaModifiers(Vec<CharaModifier>),
     /// Effect for food or potion items
     Medical {
         power: BasePower,
@@ -64,6 +67,8 @@ pub enum ItemObjAttr {
     Tool(ToolEffect),
     /// For usable items
     Use(UseEffect),
+    /// Key to open locked doors whose lock level is lower than or equal to this
+    Key(u16),
     /// Facility type for creation and additional quality.
     Facility {
         #[serde(rename = "type")]
@@ -82,6 +87,8 @@ pub enum ItemObjAttr {
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
 pub enum ContainerFunction {
     PreventRot,
+    /// Shares the contents with other storage containers in the same player's base
+    SharedStorage,
     Converter {
         kind: String,
     },
@@ -123,6 +130,10 @@ pub enum ItemAttr {
     /// Title for readable items
     Title(String),
     BuildObj(BuildObj),
+    /// Stolen from this faction
+    Stolen(super::faction::FactionId),
+    /// Given or placed for a quest. Only usable to complete the quest.
+    QuestItem,
 }
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
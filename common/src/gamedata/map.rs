@@ -1,5 +1,6 @@
 use crate::basic::{ArrayStringId, MAX_ITEM_FOR_DRAW, N_TILE_IMG_LAYER};
 use crate::gamedata::chara::{Chara, CharaId};
+use crate::gamedata::faction::FactionId;
 use crate::gamedata::item::{Item, ItemList};
 use crate::gamedata::region::RegionId;
 use crate::gamedata::site::{DungeonKind, SiteId};
@@ -85,12 +86,52 @@ pub enum SpecialTileKind {
     SiteSymbol {
         kind: SiteSymbolKind,
     },
+    /// Trap triggered by characters stepping on it
+    Trap {
+        kind: TrapKind,
+        /// Characters of this faction avoid the trap
+        faction: FactionId,
+        /// Hidden traps are not shown until found
+        hidden: bool,
+    },
+    /// A wall that becomes a passage when found
+    SecretPassage,
+    /// Port on region map to sail to another region
+    Port {
+        rid: RegionId,
+    },
+    /// Portal on region map to travel to another region
+    Portal {
+        rid: RegionId,
+    },
 }
 
 impl SpecialTileKind {
     pub fn is_none(&self) -> bool {
         matches!(*self, SpecialTileKind::None)
     }
+
+    /// Returns true if this is not shown until found
+    pub fn is_hidden(&self) -> bool {
+        matches!(
+            *self,
+            SpecialTileKind::Trap { hidden: true, .. } | SpecialTileKind::SecretPassage
+        )
+    }
+
+    /// Returns true if this is a trap of the faction. Its members avoid the trap.
+    pub fn is_trap_of(&self, faction: FactionId) -> bool {
+        matches!(*self, SpecialTileKind::Trap { faction: f, .. } if f == faction)
+    }
+
+    /// Returns this as observed by the player. Hidden features are not observed.
+    pub fn observed(&self) -> SpecialTileKind {
+        if self.is_hidden() {
+            SpecialTileKind::None
+        } else {
+            *self
+        }
+    }
 }
 
 impl Default for SpecialTileKind {
@@ -105,6 +146,16 @@ pub enum StairsKind {
     DownStairs,
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum TrapKind {
+    Damage,
+    Poison,
+    Teleport,
+    Alarm,
+    /// Drops characters to the lower floor
+    Pit,
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct SiteSymbolKind(ArrayStringId);
@@ -127,10 +178,19 @@ impl SpecialTileKind {
                 StairsKind::UpStairs => "!upstairs",
             },
             SpecialTileKind::SiteSymbol { ref kind } => kind.0.as_str(),
+            SpecialTileKind::Trap { hidden: false, .. } => TRAP_OBJ_ID,
+            SpecialTileKind::Port { .. } => "!port",
+            SpecialTileKind::Portal { .. } => "!portal",
+            SpecialTileKind::Trap { hidden: true, .. } | SpecialTileKind::SecretPassage => {
+                return None;
+            }
         })
     }
 }
 
+/// Id of SpecialTileObject for revealed traps
+pub const TRAP_OBJ_ID: &str = "!trap";
+
 /// If stairs or boundaries have this value, they are connected to region map
 pub const FLOOR_OUTSIDE: u32 = 0xFFFFFFFF;
 
@@ -148,6 +208,40 @@ pub struct TileInfo {
     pub item_list: ItemList,
     pub chara: Option<CharaId>,
     pub special: SpecialTileKind,
+    #[serde(default)]
+    pub door: Option<Door>,
+}
+
+/// Door which can be opened, closed, locked and broken
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Door {
+    pub state: DoorState,
+    /// Difficulty to pick the lock
+    pub lock_level: u16,
+    /// Broken by wall damage
+    pub hp: u16,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum DoorState {
+    Open,
+    Closed,
+    Locked,
+}
+
+impl DoorState {
+    /// Closed doors block movement and vision
+    pub fn is_closed(self) -> bool {
+        self != DoorState::Open
+    }
+
+    /// Convert to id of SpecialTileObject
+    pub fn obj_id(self) -> &'static str {
+        match self {
+            DoorState::Open => "!door-open",
+            DoorState::Closed | DoorState::Locked => "!door-closed",
+        }
+    }
 }
 
 /// The data for map drawing
@@ -160,6 +254,8 @@ pub struct ObservedTileInfo {
     pub deco: Option<DecoIdx>,
     pub items: ArrayVec<(ItemIdx, u32), MAX_ITEM_FOR_DRAW>,
     pub special: SpecialTileKind,
+    #[serde(default)]
+    pub door: Option<DoorState>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -195,6 +291,10 @@ pub enum Destination {
     MapIdWithPos(MapId, Vec2d),
     MapIdWithEntrance(MapId, u32),
     MapId(MapId),
+    /// Sail to the port of the region
+    Ship(RegionId),
+    /// Travel to the portal of the region
+    Portal(RegionId),
 }
 
 impl Default for TileInfo {
@@ -207,6 +307,7 @@ impl Default for TileInfo {
             item_list: ItemList::default(),
             chara: None,
             special: SpecialTileKind::None,
+            door: None,
         }
     }
 }
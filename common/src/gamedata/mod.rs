@@ -162,9 +162,9 @@ impl GameData {
         }
     }
 
-    /// Remove specified character from game.
+    /// Remove specified character from game, and returns the removed data.
     /// If the character is in the current map, remove from map data
-    pub fn remove_chara(&mut self, cid: CharaId) {
+    pub fn remove_chara(&mut self, cid: CharaId) -> Option<Chara> {
         match cid {
             CharaId::Player => {
                 panic!();
@@ -174,10 +174,13 @@ impl GameData {
                 map.remove_chara(cid);
             }
             _ => {
-                self.get_current_map_mut().remove_chara(cid);
+                let map = self.get_current_map_mut();
+                if map.chara_pos(cid).is_some() {
+                    map.remove_chara(cid);
+                }
             }
         }
-        self.chara.remove_chara(cid);
+        self.chara.remove_chara(cid)
     }
 
     pub fn add_site(
@@ -238,6 +241,14 @@ impl GameData {
             ItemListLocation::Equip { cid } => self.chara.get(cid).equip.list(),
             ItemListLocation::OnMap { mid, pos } => &self.region.get_map(mid).tile[pos].item_list,
             ItemListLocation::Shop { cid } => &self.get_shop(cid).items,
+            ItemListLocation::BaseStorage { sid } => {
+                &self
+                    .region
+                    .get_site(sid)
+                    .player_base()
+                    .expect("invalid ItemListLocation for base storage")
+                    .storage
+            }
             ItemListLocation::Container { ill, i } => {
                 let item = self.get_item((ill.into(), i));
 
@@ -265,6 +276,14 @@ impl GameData {
                 &mut self.region.get_map_mut(mid).tile[pos].item_list
             }
             ItemListLocation::Shop { cid } => &mut self.get_shop_mut(cid).items,
+            ItemListLocation::BaseStorage { sid } => {
+                &mut self
+                    .region
+                    .get_site_mut(sid)
+                    .player_base_mut()
+                    .expect("invalid ItemListLocation for base storage")
+                    .storage
+            }
             ItemListLocation::Container { ill, i } => {
                 let item = self.get_item_mut((ill.into(), i));
 
@@ -353,6 +372,9 @@ impl GameData {
             SiteContent::Town { ref town } => town
                 .get_shop(id)
                 .unwrap_or_else(|| panic!("Shop #{} doesnot exit in {:?}", id, sid)),
+            SiteContent::AutoGenDungeon { ref shops, .. } => shops
+                .get(&id)
+                .unwrap_or_else(|| panic!("Shop #{} doesnot exit in {:?}", id, sid)),
             _ => unreachable!("Tried to get shop in a site that is not town"),
         }
     }
@@ -367,6 +389,9 @@ impl GameData {
             SiteContent::Town { ref mut town } => town
                 .get_shop_mut(id)
                 .unwrap_or_else(|| panic!("Shop #{} doesnot exit in {:?}", id, sid)),
+            SiteContent::AutoGenDungeon { ref mut shops, .. } => shops
+                .get_mut(&id)
+                .unwrap_or_else(|| panic!("Shop #{} doesnot exit in {:?}", id, sid)),
             _ => unreachable!("Tried to get shop in a site that is not town"),
         }
     }
@@ -1,3 +1,4 @@
+use super::defs::{Element, ElementArray};
 use super::skill::SkillKind;
 use derivative::Derivative;
 use fnv::FnvHashMap;
@@ -12,6 +13,9 @@ pub enum CharaModifier {
     Wil(i16),
     Cha(i16),
     Spd(i16),
+    ViewRange(i16),
+    /// Protection against the element in percent
+    ElementProtection(Element, i8),
 }
 
 /// Summed effect of modifiers a character received by properties, status, and other factors.
@@ -27,6 +31,9 @@ pub struct CharaTotalModifier {
     pub wil: i16,
     pub cha: i16,
     pub spd: i16,
+    pub view_range: i16,
+    /// Summed element protection in percent
+    pub element_protection: ElementArray<i16>,
     #[derivative(Default(value = "1.0"))]
     pub spd_factor: f32,
     pub skill_level: FnvHashMap<SkillKind, (f32, i32)>,
@@ -6,6 +6,12 @@ pub struct Player {
     money: i64,
     pub party: fnv::FnvHashSet<CharaId>,
     pub party_dead: fnv::FnvHashSet<CharaId>,
+    /// Contracts with hired party members
+    #[serde(default)]
+    pub employment: fnv::FnvHashMap<CharaId, Employment>,
+    /// Orders given to party members. Not included members follow the player.
+    #[serde(default)]
+    pub party_orders: fnv::FnvHashMap<CharaId, PartyOrder>,
 }
 
 impl Player {
@@ -29,4 +35,31 @@ impl Player {
     pub fn has_money(&self, a: i64) -> bool {
         self.money >= a
     }
+
+    pub fn party_order(&self, cid: CharaId) -> PartyOrder {
+        self.party_orders.get(&cid).copied().unwrap_or_default()
+    }
+}
+
+/// Contract with a hired party member
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Employment {
+    /// Paid daily
+    pub wage: i64,
+    /// The member leaves the party when this becomes zero
+    pub loyalty: u32,
+}
+
+/// Order to a party member
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug, Serialize, Deserialize)]
+pub enum PartyOrder {
+    /// Follow the player
+    #[default]
+    Follow,
+    /// Wait at the current position
+    Wait,
+    /// Attack the player's target
+    AttackTarget,
+    /// Do not attack enemies
+    NoAttack,
 }
@@ -1,5 +1,8 @@
 use super::defs::Reward;
-use crate::objholder::CharaTemplateIdx;
+use super::site::SiteId;
+use super::time::Time;
+use super::CharaId;
+use crate::objholder::{CharaTemplateIdx, ItemIdx};
 use std::slice::{Iter, IterMut};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -7,16 +10,22 @@ pub enum QuestState {
     Active,
     Completed,
     RewardReceived,
+    Failed,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct QuestHolder {
     quests: Vec<(QuestState, Quest)>,
+    #[serde(default)]
+    story_quests: Vec<StoryQuest>,
 }
 
 impl Default for QuestHolder {
     fn default() -> Self {
-        QuestHolder { quests: Vec::new() }
+        QuestHolder {
+            quests: Vec::new(),
+            story_quests: Vec::new(),
+        }
     }
 }
 
@@ -37,10 +46,78 @@ impl QuestHolder {
         self.quests
             .retain(|&(state, _)| state != QuestState::RewardReceived);
     }
+
+    pub fn remove_failed(&mut self) {
+        self.quests
+            .retain(|&(state, _)| state != QuestState::Failed);
+    }
+
+    pub fn iter_story_quests(&self) -> Iter<'_, StoryQuest> {
+        self.story_quests.iter()
+    }
+
+    pub fn story_quest(&self, id: &str) -> Option<&StoryQuest> {
+        self.story_quests.iter().find(|q| q.id == id)
+    }
+
+    /// Set the stage of the story quest. Start the quest if it is not started.
+    pub fn set_story_quest_stage(&mut self, id: &str, stage: &str, time: Time, end: bool) {
+        let quest = if let Some(i) = self.story_quests.iter().position(|q| q.id == id) {
+            &mut self.story_quests[i]
+        } else {
+            self.story_quests.push(StoryQuest {
+                id: id.to_owned(),
+                stage: String::new(),
+                completed: false,
+                history: Vec::new(),
+            });
+            self.story_quests.last_mut().unwrap()
+        };
+        quest.stage = stage.to_owned();
+        quest.completed = end;
+        quest.history.push((time, stage.to_owned()));
+    }
 }
 
+/// Progress of a story quest
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
-pub enum Quest {
+pub struct StoryQuest {
+    /// Id of StoryQuestObject
+    pub id: String,
+    /// Name of the current stage
+    pub stage: String,
+    pub completed: bool,
+    /// Passed stages and the time they started
+    pub history: Vec<(Time, String)>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(from = "QuestCompat")]
+pub struct Quest {
+    /// Id of the quest template
+    pub id: String,
+    pub kind: QuestKind,
+    pub reward: Reward,
+    /// The quest fails if it is not completed before this time
+    pub deadline: Option<Time>,
+}
+
+/// Loads both the current format and the enum format used before quest templates
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum QuestCompat {
+    Current {
+        id: String,
+        kind: QuestKind,
+        reward: Reward,
+        #[serde(default)]
+        deadline: Option<Time>,
+    },
+    Legacy(LegacyQuest),
+}
+
+#[derive(Deserialize)]
+enum LegacyQuest {
     SlayMonsters {
         reward: Reward,
         idx: CharaTemplateIdx,
@@ -49,10 +126,74 @@ pub enum Quest {
     },
 }
 
+impl From<QuestCompat> for Quest {
+    fn from(quest: QuestCompat) -> Quest {
+        match quest {
+            QuestCompat::Current {
+                id,
+                kind,
+                reward,
+                deadline,
+            } => Quest {
+                id,
+                kind,
+                reward,
+                deadline,
+            },
+            QuestCompat::Legacy(LegacyQuest::SlayMonsters {
+                reward,
+                idx,
+                goal,
+                killed,
+            }) => Quest {
+                id: String::new(),
+                kind: QuestKind::SlayMonsters { idx, goal, killed },
+                reward,
+                deadline: None,
+            },
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum QuestKind {
+    SlayMonsters {
+        idx: CharaTemplateIdx,
+        goal: u32,
+        killed: u32,
+    },
+    /// Bring items to a town
+    Fetch { idx: ItemIdx, n: u32 },
+    /// Carry given items to the destination town
+    Deliver {
+        idx: ItemIdx,
+        n: u32,
+        destination: SiteId,
+    },
+    /// Take a character joined to the party to the destination town
+    Escort {
+        cid: Option<CharaId>,
+        idx: CharaTemplateIdx,
+        destination: SiteId,
+    },
+    /// Reach the floor of the site
+    Explore { sid: SiteId, floor: u32 },
+    /// Bring back a relic placed on the floor of the site
+    Retrieve {
+        idx: ItemIdx,
+        sid: SiteId,
+        floor: u32,
+        placed: bool,
+    },
+    Craft {
+        idx: ItemIdx,
+        goal: u32,
+        crafted: u32,
+    },
+}
+
 impl Quest {
     pub fn reward(&self) -> &Reward {
-        match self {
-            Quest::SlayMonsters { reward, .. } => reward,
-        }
+        &self.reward
     }
 }
@@ -58,6 +58,14 @@ impl RegionHolder {
         self.0.get_mut(&rid)
     }
 
+    /// Search region by its name
+    pub fn get_id_by_name(&self, name: &str) -> Option<RegionId> {
+        self.0
+            .iter()
+            .find(|(_, region)| region.name == name)
+            .map(|(rid, _)| *rid)
+    }
+
     pub fn get_site(&self, sid: SiteId) -> &Site {
         let region = self
             .0
@@ -208,6 +216,14 @@ impl RegionHolder {
         }
     }
 
+    pub fn visit_all_sites<F: FnMut(SiteId, &Site)>(&self, mut f: F) {
+        for region in self.0.values() {
+            for (&sid, siteinfo) in &region.sites {
+                f(sid, &siteinfo.site);
+            }
+        }
+    }
+
     /// Covnert map path to MapId and pos.
     pub fn path_to_map_id_and_pos(&self, path: &str) -> Option<(MapId, Vec2d)> {
         fn floor_and_pos(a: &str) -> Option<(u32, Vec2d)> {
@@ -315,6 +331,11 @@ impl Region {
         None
     }
 
+    /// Iterate over sites on the region
+    pub fn iter_sites(&self) -> impl Iterator<Item = (SiteId, &Site)> + '_ {
+        self.sites.iter().map(|(sid, sinfo)| (*sid, &sinfo.site))
+    }
+
     pub fn get_map(&self) -> &Map {
         &self.map
     }
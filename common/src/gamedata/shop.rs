@@ -1,8 +1,45 @@
-use crate::gamedata::item::ItemList;
+use crate::gamedata::item::{ItemKindRough, ItemList};
+use fnv::FnvHashMap;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Shop {
     pub items: ItemList,
     /// Shop level is used to choose shop items
     pub level: u32,
+    /// Money the shop owner can pay for items. Refilled on restock.
+    /// Shops loaded from older saves without funds have FUNDS_UNSET until they are refilled.
+    #[serde(default = "funds_unset")]
+    pub funds: i64,
+    /// Local supply of each item group. Positive values mean oversupply.
+    #[serde(default)]
+    pub supply: FnvHashMap<ItemKindRough, i32>,
+    /// Fences buy stolen items
+    #[serde(default)]
+    pub fence: bool,
+    /// Shop kind to restock shops of wandering merchants
+    #[serde(default)]
+    pub shop_kind: String,
+}
+
+fn funds_unset() -> i64 {
+    Shop::FUNDS_UNSET
+}
+
+impl Shop {
+    pub const FUNDS_UNSET: i64 = -1;
+
+    pub fn new(level: u32) -> Shop {
+        Shop {
+            items: ItemList::default(),
+            level,
+            funds: 0,
+            supply: FnvHashMap::default(),
+            fence: false,
+            shop_kind: String::new(),
+        }
+    }
+
+    pub fn supply(&self, kind: ItemKindRough) -> i32 {
+        self.supply.get(&kind).copied().unwrap_or(0)
+    }
 }
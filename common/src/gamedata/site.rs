@@ -1,8 +1,12 @@
+use super::faction::FactionId;
+use super::item::ItemList;
 use super::map::{Map, MapId};
 use super::region::RegionId;
+use super::shop::Shop;
 use super::town::Town;
 use crate::basic::ArrayStringId;
 use filebox::FileBox;
+use fnv::FnvHashMap;
 use geom::Vec2d;
 
 pub type BoxedMap = FileBox<Map>;
@@ -29,6 +33,12 @@ pub enum SiteContent {
     AutoGenDungeon {
         /// It is used for map generation, enemy race weighting, etc.
         dungeon_kind: DungeonKind,
+        /// Shops of wandering merchants in this dungeon
+        #[serde(default)]
+        shops: FnvHashMap<u32, Shop>,
+        /// The faction which reclaimed this dungeon
+        #[serde(default)]
+        faction: Option<FactionId>,
     },
     /// Town consists of residents and shops, etc.
     Town {
@@ -36,7 +46,11 @@ pub enum SiteContent {
         town: Box<Town>,
     },
     /// Player's base
-    Player { kind: PlayerBaseKind },
+    Player {
+        kind: PlayerBaseKind,
+        #[serde(default)]
+        base: Box<PlayerBase>,
+    },
     /// Temporary map
     Temp {
         return_map: MapId,
@@ -91,6 +105,20 @@ impl Site {
         self.id.as_ref().map(|s| s.as_ref())
     }
 
+    pub fn player_base(&self) -> Option<&PlayerBase> {
+        match self.content {
+            SiteContent::Player { ref base, .. } => Some(base),
+            _ => None,
+        }
+    }
+
+    pub fn player_base_mut(&mut self) -> Option<&mut PlayerBase> {
+        match self.content {
+            SiteContent::Player { ref mut base, .. } => Some(base),
+            _ => None,
+        }
+    }
+
     pub fn floor_num(&self) -> u32 {
         self.map.len() as u32
     }
@@ -162,3 +190,10 @@ impl DungeonKind {
 pub enum PlayerBaseKind {
     Normal,
 }
+
+/// Data for player's base
+#[derive(Clone, Default, Debug, Serialize, Deserialize)]
+pub struct PlayerBase {
+    /// Items shared by storage containers in this base
+    pub storage: ItemList,
+}
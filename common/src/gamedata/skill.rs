@@ -278,6 +278,8 @@ define_skill_kind! {
         Plants, "plants",
         Animals, "animals",
         Leadership, "leadership",
+        Negotiation, "negotiation",
+        Lockpicking, "lockpicking",
     }
     melee_weapon_kind = 0x0400;
     {
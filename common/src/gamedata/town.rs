@@ -1,5 +1,6 @@
 use crate::gamedata::quest::Quest;
 use crate::gamedata::shop::*;
+use crate::gamedata::time::Time;
 use fnv::FnvHashMap;
 use std::collections::hash_map::{Values, ValuesMut};
 
@@ -8,6 +9,16 @@ pub struct Town {
     id: String,
     shops: FnvHashMap<u32, Shop>,
     pub quests: Vec<Quest>,
+    /// Economic scale of this town. Shop level and item quality grow with it.
+    #[serde(default)]
+    pub economy_level: u32,
+    /// Growth points to the next economy level
+    #[serde(default)]
+    pub economy_exp: u32,
+    #[serde(default)]
+    pub last_restock: Option<Time>,
+    #[serde(default)]
+    pub last_quest_update: Option<Time>,
 }
 
 impl Town {
@@ -16,6 +27,10 @@ impl Town {
             id: id.to_owned(),
             shops: FnvHashMap::default(),
             quests: Vec::new(),
+            economy_level: 0,
+            economy_exp: 0,
+            last_restock: None,
+            last_quest_update: None,
         }
     }
 
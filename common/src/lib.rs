@@ -33,3 +33,4 @@ pub mod piece_pattern;
 pub mod regiongen;
 pub mod saveload;
 pub mod sitegen;
+pub mod storyquest;
@@ -27,6 +27,7 @@ pub enum Object {
     RegionGen(RegionGenObject),
     Script(ScriptObject),
     SiteGen(SiteGenObject),
+    StoryQuest(StoryQuestObject),
 }
 
 #[derive(Serialize, Deserialize)]
@@ -149,6 +150,7 @@ pub struct Icon {
 pub use crate::maptemplate::MapTemplateObject;
 pub use crate::regiongen::RegionGenObject;
 pub use crate::sitegen::SiteGenObject;
+pub use crate::storyquest::StoryQuestObject;
 
 macro_rules! impl_object {
     ( $($i:ty),* ) => {
@@ -175,7 +177,8 @@ impl_object!(
     MapTemplateObject,
     RegionGenObject,
     SiteGenObject,
-    ScriptObject
+    ScriptObject,
+    StoryQuestObject
 );
 
 impl Object {
@@ -194,6 +197,7 @@ impl Object {
             Object::RegionGen(ref o) => &o.id,
             Object::Script(ref o) => &o.id,
             Object::SiteGen(ref o) => &o.id,
+            Object::StoryQuest(ref o) => &o.id,
         }
     }
 }
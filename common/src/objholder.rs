@@ -192,7 +192,8 @@ impl_objholder! {
     {MapTemplate, MapTemplateObject, map_template, MapTemplateIdx},
     {RegionGen, RegionGenObject, region_gen, RegionGenIdx},
     {Script, ScriptObject, script, ScriptIdx},
-    {SiteGen, SiteGenObject, site_gen, SiteGenIdx}
+    {SiteGen, SiteGenObject, site_gen, SiteGenIdx},
+    {StoryQuest, StoryQuestObject, story_quest, StoryQuestIdx}
 }
 
 pub trait ObjectIndex: Sized {
@@ -46,4 +46,7 @@ pub struct ShopGenData {
     pub shop_kind: String,
     #[serde(default)]
     pub selector: String,
+    /// Fences buy stolen items
+    #[serde(default)]
+    pub fence: bool,
 }
@@ -0,0 +1,29 @@
+/// Hold data for a story quest that proceeds by scripts
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StoryQuestObject {
+    pub id: String,
+    /// The first stage starts when the quest is started
+    pub stages: Vec<StoryQuestStage>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct StoryQuestStage {
+    /// Name used by scripts to specify the stage
+    pub name: String,
+    /// Text id of the objective shown in the quest window
+    pub objective: String,
+    /// Text id recorded in the journal when the stage starts
+    pub journal: String,
+    /// Stages that can follow this stage. Any stage can follow if empty.
+    #[serde(default)]
+    pub next: Vec<String>,
+    /// The quest is completed when it reaches this stage
+    #[serde(default)]
+    pub end: bool,
+}
+
+impl StoryQuestObject {
+    pub fn stage(&self, name: &str) -> Option<&StoryQuestStage> {
+        self.stages.iter().find(|stage| stage.name == name)
+    }
+}
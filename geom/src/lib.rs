@@ -685,6 +685,15 @@ impl HDirection {
     pub fn is_none(&self) -> bool {
         *self == HDirection::None
     }
+
+    #[inline]
+    pub fn reverse(&self) -> HDirection {
+        match *self {
+            HDirection::None => HDirection::None,
+            HDirection::Left => HDirection::Right,
+            HDirection::Right => HDirection::Left,
+        }
+    }
 }
 
 impl Default for VDirection {
@@ -719,6 +728,15 @@ impl VDirection {
     pub fn is_none(&self) -> bool {
         *self == VDirection::None
     }
+
+    #[inline]
+    pub fn reverse(&self) -> VDirection {
+        match *self {
+            VDirection::None => VDirection::None,
+            VDirection::Up => VDirection::Down,
+            VDirection::Down => VDirection::Up,
+        }
+    }
 }
 
 impl Default for HDirection {
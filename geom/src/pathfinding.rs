@@ -1,5 +1,6 @@
 use crate::{Array2d, Direction, Vec2d};
-use std::collections::VecDeque;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
 
 pub struct PathFinding<F> {
     w: u32,
@@ -72,6 +73,72 @@ impl<F: Fn(Vec2d) -> bool> PathFinding<F> {
         route.reverse();
         Some(route)
     }
+
+    /// Calculate route by Dijkstra's algorithm.
+    /// `cost` gives the additional cost to enter the tile.
+    pub fn route_with_cost<C: Fn(Vec2d) -> u32>(
+        &self,
+        start: Vec2d,
+        end: Vec2d,
+        cost: C,
+    ) -> Option<Vec<Vec2d>> {
+        let mut heap = BinaryHeap::new();
+        let mut dist: Array2d<Option<u32>> = Array2d::new(self.w, self.h, None);
+        let mut check_map: Array2d<Option<Direction>> = Array2d::new(self.w, self.h, None);
+        let mut done: Array2d<bool> = Array2d::new(self.w, self.h, false);
+
+        dist[start] = Some(0);
+        check_map[start] = Some(Direction::NONE);
+        heap.push(Reverse((0, 0, start.0, start.1)));
+
+        while let Some(Reverse((d, step, x, y))) = heap.pop() {
+            let pos = Vec2d(x, y);
+            if done[pos] {
+                continue;
+            }
+            done[pos] = true;
+
+            if pos == end {
+                break;
+            }
+
+            if step >= self.max_step || !(self.map)(pos) {
+                continue;
+            }
+
+            for &dir in Direction::EIGHT_DIRS.iter() {
+                let next_pos = pos + dir.as_vec();
+                if !self.is_inside(next_pos) || done[next_pos] {
+                    continue;
+                }
+                let next_d = d + 1 + cost(next_pos);
+                if dist[next_pos].map(|old| next_d < old).unwrap_or(true) {
+                    dist[next_pos] = Some(next_d);
+                    check_map[next_pos] = Some(dir);
+                    heap.push(Reverse((next_d, step + 1, next_pos.0, next_pos.1)));
+                }
+            }
+        }
+
+        if !done[end] {
+            return None;
+        }
+
+        let mut route = Vec::new();
+        let mut pos = end;
+
+        loop {
+            route.push(pos);
+            if pos == start {
+                break;
+            }
+            let dir = check_map[pos]?;
+            pos = pos - dir.as_vec();
+        }
+
+        route.reverse();
+        Some(route)
+    }
 }
 
 #[cfg(test)]
@@ -94,4 +161,35 @@ mod pathfinding_test {
 
         assert!(route.is_none());
     }
+
+    #[test]
+    fn pathfinding_with_cost_test() {
+        // The straight route passes a costly tile, so the detour is chosen
+        let map = [[1, 1, 1], [1, 2, 1], [1, 1, 1]];
+        let is_passable = |pos: Vec2d| map[pos.1 as usize][pos.0 as usize] != 0;
+        let cost = |pos: Vec2d| {
+            if map[pos.1 as usize][pos.0 as usize] == 2 {
+                5
+            } else {
+                0
+            }
+        };
+
+        let route = PathFinding::new(3, 3, 100, is_passable)
+            .route_with_cost(Vec2d(0, 0), Vec2d(2, 2), cost)
+            .unwrap();
+        assert_eq!(route.first(), Some(&Vec2d(0, 0)));
+        assert_eq!(route.last(), Some(&Vec2d(2, 2)));
+        assert!(!route.contains(&Vec2d(1, 1)));
+
+        let route = PathFinding::new(3, 3, 100, is_passable)
+            .route_with_cost(Vec2d(0, 0), Vec2d(2, 2), |_| 0)
+            .unwrap();
+        assert_eq!(route, vec![Vec2d(0, 0), Vec2d(1, 1), Vec2d(2, 2)]);
+
+        let map = [[1, 1, 1], [0, 0, 0], [1, 1, 1]];
+        let route = PathFinding::new(3, 3, 100, |pos| map[pos.1 as usize][pos.0 as usize] != 0)
+            .route_with_cost(Vec2d(0, 0), Vec2d(2, 2), |_| 0);
+        assert!(route.is_none());
+    }
 }
@@ -1,12 +1,16 @@
-use crate::Vec2d;
+use crate::{LineIter, RectIter, Vec2d};
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum ShapeKind {
     OneTile,
     Line,
     Circle,
-    // Sector,
-    // All,
+    /// Cone spreading from the origin. Angle is given in degrees.
+    Sector {
+        angle: u32,
+    },
+    Ring,
+    All,
 }
 
 impl Default for ShapeKind {
@@ -17,24 +21,58 @@ impl Default for ShapeKind {
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum Shape {
-    OneTile { pos: Vec2d },
-    Line { start: Vec2d, end: Vec2d },
-    Circle { center: Vec2d, radius: u32 },
+    OneTile {
+        pos: Vec2d,
+    },
+    Line {
+        start: Vec2d,
+        end: Vec2d,
+    },
+    Circle {
+        center: Vec2d,
+        radius: u32,
+    },
+    /// Tiles in the circle whose direction from center is within angle / 2 from the direction
+    /// to target.
+    Sector {
+        center: Vec2d,
+        target: Vec2d,
+        radius: u32,
+        angle: u32,
+    },
+    /// Tiles on the circumference.
+    Ring {
+        center: Vec2d,
+        radius: u32,
+    },
+    /// All tiles from (0, 0) to (w - 1, h - 1).
+    All {
+        w: u32,
+        h: u32,
+    },
 }
 
 impl Shape {
     pub fn is_inside(&self, p: Vec2d) -> bool {
         match *self {
             Shape::OneTile { pos } => pos == p,
-            Shape::Line { .. } => unimplemented!(),
+            Shape::Line { start, end } => LineIter::new(start, end).any(|pos| pos == p),
             Shape::Circle { center, radius } => (center.mdistance(p) as u32) < radius,
+            Shape::Sector {
+                center,
+                target,
+                radius,
+                angle,
+            } => is_inside_sector(center, target, radius, angle, p),
+            Shape::Ring { center, radius } => is_inside_ring(center, radius, p),
+            Shape::All { w, h } => p.0 >= 0 && p.1 >= 0 && p.0 < w as i32 && p.1 < h as i32,
         }
     }
 
     pub fn iter(&self) -> Vec<Vec2d> {
         match *self {
             Shape::OneTile { pos } => vec![pos],
-            Shape::Line { .. } => unimplemented!(),
+            Shape::Line { start, end } => LineIter::new(start, end).collect(),
             Shape::Circle { center, radius } => {
                 if radius == 0 {
                     return vec![center];
@@ -42,13 +80,118 @@ impl Shape {
                 let radius = radius as i32;
                 let r = radius as f32 + 0.5;
                 let r2 = r * r;
-                super::RectIter::new(
+                RectIter::new(
                     center - Vec2d::new(radius, radius),
                     center + Vec2d::new(radius, radius),
                 )
                 .filter(|pos| pos.distance2(center) < r2)
                 .collect()
             }
+            Shape::Sector {
+                center,
+                target,
+                radius,
+                angle,
+            } => {
+                let r = radius as i32;
+                RectIter::new(center - Vec2d::new(r, r), center + Vec2d::new(r, r))
+                    .filter(|pos| is_inside_sector(center, target, radius, angle, *pos))
+                    .collect()
+            }
+            Shape::Ring { center, radius } => {
+                let r = radius as i32 + 1;
+                RectIter::new(center - Vec2d::new(r, r), center + Vec2d::new(r, r))
+                    .filter(|pos| is_inside_ring(center, radius, *pos))
+                    .collect()
+            }
+            Shape::All { w, h } => {
+                if w == 0 || h == 0 {
+                    return vec![];
+                }
+                RectIter::new((0, 0), (w as i32 - 1, h as i32 - 1)).collect()
+            }
         }
     }
 }
+
+fn is_inside_sector(center: Vec2d, target: Vec2d, radius: u32, angle: u32, p: Vec2d) -> bool {
+    if p == center {
+        return true;
+    }
+    let r = radius as f32 + 0.5;
+    if p.distance2(center) >= r * r {
+        return false;
+    }
+    if target == center || angle >= 360 {
+        return true;
+    }
+
+    let v = p - center;
+    let d = target - center;
+    let dot = (v.0 * d.0 + v.1 * d.1) as f32;
+    let cos = dot / (p.distance2(center).sqrt() * target.distance2(center).sqrt());
+    let half_angle = (angle as f32 / 2.0).to_radians();
+    // Small epsilon to include tiles just on the edge of the sector
+    cos >= half_angle.cos() - 1.0e-4
+}
+
+fn is_inside_ring(center: Vec2d, radius: u32, p: Vec2d) -> bool {
+    if radius == 0 {
+        return p == center;
+    }
+    let d2 = p.distance2(center);
+    let inner = radius as f32 - 0.5;
+    let outer = radius as f32 + 0.5;
+    inner * inner <= d2 && d2 < outer * outer
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn line() {
+        let shape = Shape::Line {
+            start: Vec2d(0, 0),
+            end: Vec2d(3, 0),
+        };
+        assert_eq!(
+            shape.iter(),
+            &[Vec2d(0, 0), Vec2d(1, 0), Vec2d(2, 0), Vec2d(3, 0)]
+        );
+        assert!(shape.is_inside(Vec2d(2, 0)));
+        assert!(!shape.is_inside(Vec2d(2, 1)));
+    }
+
+    #[test]
+    fn sector() {
+        let shape = Shape::Sector {
+            center: Vec2d(0, 0),
+            target: Vec2d(5, 0),
+            radius: 3,
+            angle: 90,
+        };
+        let tiles = shape.iter();
+        assert!(tiles.contains(&Vec2d(3, 0)));
+        assert!(tiles.contains(&Vec2d(2, 2)));
+        assert!(!tiles.contains(&Vec2d(-1, 0)));
+        assert!(!tiles.contains(&Vec2d(1, 2)));
+        assert!(tiles.iter().all(|p| shape.is_inside(*p)));
+    }
+
+    #[test]
+    fn ring_and_all() {
+        let ring = Shape::Ring {
+            center: Vec2d(0, 0),
+            radius: 2,
+        };
+        let tiles = ring.iter();
+        assert!(tiles.contains(&Vec2d(2, 0)));
+        assert!(!tiles.contains(&Vec2d(0, 0)));
+        assert!(!tiles.contains(&Vec2d(1, 0)));
+
+        let all = Shape::All { w: 3, h: 2 };
+        assert_eq!(all.iter().len(), 6);
+        assert!(!all.is_inside(Vec2d(3, 0)));
+    }
+}
@@ -24,6 +24,7 @@ pub fn build_object(input: Input) -> Result<Object, Error> {
         "region_gen" => build_region_gen_object(input).map(Object::RegionGen),
         "script" => build_script_object(input).map(Object::Script),
         "site_gen" => build_site_gen_object(input).map(Object::SiteGen),
+        "story_quest" => build_story_quest_object(input).map(Object::StoryQuest),
         _ => {
             bail!("Unknown object_type");
         }
@@ -143,6 +144,7 @@ fn build_chara_template_object(input: Input) -> Result<CharaTemplateObject, Erro
         default_ai_kind: chara_dep_input.default_ai_kind,
         skill_bonus: chara_dep_input.skill_bonus,
         base_attr,
+        element_protection: chara_dep_input.element_protection,
         active_skills: chara_dep_input.active_skills,
         equips: chara_dep_input.equips,
     })
@@ -195,3 +197,27 @@ fn build_site_gen_object(input: Input) -> Result<SiteGenObject, Error> {
         shops: sg.shops,
     })
 }
+
+fn build_story_quest_object(input: Input) -> Result<StoryQuestObject, Error> {
+    let sq = get_optional_field!(input, story_quest);
+
+    if sq.stages.is_empty() {
+        bail!("story quest \"{}\" has no stages", input.id);
+    }
+    for stage in &sq.stages {
+        for next in &stage.next {
+            if !sq.stages.iter().any(|s| s.name == *next) {
+                bail!(
+                    "unknown next stage \"{}\" in stage \"{}\"",
+                    next,
+                    stage.name
+                );
+            }
+        }
+    }
+
+    Ok(StoryQuestObject {
+        id: input.id,
+        stages: sq.stages,
+    })
+}
@@ -1,6 +1,10 @@
 use common::basic::BonusLevel;
-use common::gamedata::{self, ActiveSkillId, CharaClass, EquipSlotKind, FactionId, SkillKind};
+use common::gamedata::{
+    self, ActiveSkillId, CharaClass, ElementArray, ElementProtection, EquipSlotKind, FactionId,
+    SkillKind,
+};
 use common::sitegen;
+use common::storyquest;
 use geom::Vec2d;
 use std::collections::HashMap;
 
@@ -26,6 +30,8 @@ pub struct Input {
     pub script: Option<ScriptDepInput>,
     #[serde(default, with = "::serde_with::rust::unwrap_or_skip")]
     pub site_gen: Option<SiteGenDepInput>,
+    #[serde(default, with = "::serde_with::rust::unwrap_or_skip")]
+    pub story_quest: Option<StoryQuestDepInput>,
 }
 
 /// If input has specified optional field, return it. If not, return error.
@@ -87,6 +93,8 @@ pub struct CharaTemplateDepInput {
     pub active_skills: Vec<ActiveSkillId>,
     #[serde(default)]
     pub equips: Vec<(EquipSlotKind, String, u32)>,
+    #[serde(default)]
+    pub element_protection: ElementArray<ElementProtection>,
     pub base_hp: i32,
     pub str: u16,
     pub vit: u16,
@@ -189,3 +197,9 @@ pub struct SiteGenDepInput {
 pub struct ScriptDepInput {
     pub script: String,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StoryQuestDepInput {
+    pub stages: Vec<storyquest::StoryQuestStage>,
+}
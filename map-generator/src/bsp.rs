@@ -0,0 +1,151 @@
+use super::{Entrance, GeneratedMap, RoomRect, TileKind};
+use geom::*;
+use rand::seq::IteratorRandom;
+use rng::{gen_bool, gen_range, GameRng};
+
+/// Dungeon generator by binary space partitioning
+pub struct Bsp {
+    /// Leaves are not split if their width or height is smaller than twice of this
+    pub min_leaf_size: u32,
+    pub min_room_size: u32,
+    pub max_depth: u32,
+}
+
+#[derive(Clone, Copy)]
+struct Rect {
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+}
+
+impl Rect {
+    fn center(&self) -> Vec2d {
+        Vec2d(self.x + self.w / 2, self.y + self.h / 2)
+    }
+}
+
+impl Bsp {
+    pub fn write_to_map(&self, gm: &mut GeneratedMap) {
+        for p in gm.tile.iter_idx() {
+            gm.tile[p] = TileKind::Wall;
+        }
+
+        // The outermost tiles are always walls
+        let root = Rect {
+            x: 1,
+            y: 1,
+            w: gm.size.0 - 2,
+            h: gm.size.1 - 2,
+        };
+        let mut rooms = Vec::new();
+        self.split(gm, root, 0, &mut rooms);
+
+        let mut rooms_with_stairs: [usize; 2] = [0, 0];
+        (0..rooms.len()).choose_multiple_fill(&mut GameRng, &mut rooms_with_stairs);
+        let e0 = random_pos_in_room(&rooms[rooms_with_stairs[0]]);
+        let e1 = if rooms.len() >= 2 {
+            Some(random_pos_in_room(&rooms[rooms_with_stairs[1]]))
+        } else {
+            None
+        };
+        gm.entrance = Entrance::Stairs(e0, e1);
+    }
+
+    /// Split given area recursively, and dig rooms and corridors.
+    /// Returns a room in this area to be connected with its sibling area.
+    fn split(&self, gm: &mut GeneratedMap, area: Rect, depth: u32, rooms: &mut Vec<Rect>) -> Rect {
+        let min = self.min_leaf_size as i32;
+        let can_split_w = area.w >= min * 2;
+        let can_split_h = area.h >= min * 2;
+
+        if depth >= self.max_depth || (!can_split_w && !can_split_h) {
+            let room = self.create_room(area);
+            for p in RectIter::new((room.x, room.y), (room.x + room.w - 1, room.y + room.h - 1)) {
+                gm.tile[p] = TileKind::Floor;
+            }
+            rooms.push(room);
+            gm.rooms.push(RoomRect {
+                top_left: Vec2d(room.x, room.y),
+                bottom_right: Vec2d(room.x + room.w - 1, room.y + room.h - 1),
+            });
+            return room;
+        }
+
+        let split_vertical = if can_split_w && can_split_h {
+            if area.w > area.h {
+                true
+            } else if area.h > area.w {
+                false
+            } else {
+                gen_bool(0.5)
+            }
+        } else {
+            can_split_w
+        };
+
+        let (a, b) = if split_vertical {
+            let w = gen_range(min..=(area.w - min));
+            (
+                Rect { w, ..area },
+                Rect {
+                    x: area.x + w,
+                    w: area.w - w,
+                    ..area
+                },
+            )
+        } else {
+            let h = gen_range(min..=(area.h - min));
+            (
+                Rect { h, ..area },
+                Rect {
+                    y: area.y + h,
+                    h: area.h - h,
+                    ..area
+                },
+            )
+        };
+
+        let room_a = self.split(gm, a, depth + 1, rooms);
+        let room_b = self.split(gm, b, depth + 1, rooms);
+        dig_corridor(gm, room_a.center(), room_b.center());
+
+        if gen_bool(0.5) {
+            room_a
+        } else {
+            room_b
+        }
+    }
+
+    /// Create a room in the area leaving one tile margin for walls
+    fn create_room(&self, area: Rect) -> Rect {
+        let max_w = (area.w - 1).max(1);
+        let max_h = (area.h - 1).max(1);
+        let min_w = (self.min_room_size as i32).clamp(1, max_w);
+        let min_h = (self.min_room_size as i32).clamp(1, max_h);
+        let w = gen_range(min_w..=max_w);
+        let h = gen_range(min_h..=max_h);
+        Rect {
+            x: area.x + gen_range(0..=(max_w - w)),
+            y: area.y + gen_range(0..=(max_h - h)),
+            w,
+            h,
+        }
+    }
+}
+
+/// Dig an L-shaped corridor
+fn dig_corridor(gm: &mut GeneratedMap, start: Vec2d, end: Vec2d) {
+    let corner = if gen_bool(0.5) {
+        Vec2d(end.0, start.1)
+    } else {
+        Vec2d(start.0, end.1)
+    };
+    for p in LineIter::new(start, corner).chain(LineIter::new(corner, end)) {
+        gm.tile[p] = TileKind::Floor;
+    }
+}
+
+fn random_pos_in_room(room: &Rect) -> Vec2d {
+    Vec2d(room.x + gen_range(0..room.w), room.y + gen_range(0..room.h))
+}
@@ -0,0 +1,123 @@
+use super::fractal::{create_reach_map, fill_unreachable_and_set_entrance, pick_passable_tile};
+use super::{GeneratedMap, TileKind};
+use geom::*;
+use rng::gen_bool;
+
+const MAX_TRY: usize = 64;
+
+/// Cave generator by cellular automata
+pub struct Cave {
+    /// Initial probability of wall tiles
+    pub wall_prob: f32,
+    /// A floor tile becomes a wall if it has this number of wall neighbours or more
+    pub birth: u32,
+    /// A wall tile remains if it has this number of wall neighbours or more
+    pub survival: u32,
+    pub iterations: u32,
+}
+
+impl Cave {
+    pub fn write_to_map(&self, gm: &mut GeneratedMap, stairs: bool) {
+        let mut result = None;
+
+        for _ in 0..MAX_TRY {
+            self.fill_random(gm);
+            for _ in 0..self.iterations {
+                self.step(gm);
+            }
+
+            let start = pick_passable_tile_checked(gm);
+            let start = if let Some(start) = start {
+                start
+            } else {
+                continue;
+            };
+            let (reach_map, n_reachable_tile) = create_reach_map(gm, start);
+            // Stairs need a reachable tile other than the start
+            if n_reachable_tile == 0 {
+                continue;
+            }
+            result = Some((gm.tile.clone(), start, reach_map));
+            // If reachable tiles are too few, create map again
+            if n_reachable_tile >= (gm.size.0 * gm.size.1) as u32 / 4 {
+                break;
+            }
+        }
+
+        let (start, reach_map) = if let Some((tile, start, reach_map)) = result {
+            gm.tile = tile;
+            (start, reach_map)
+        } else {
+            // Give up caves, and make an open space surrounded by walls
+            for p in gm.tile.iter_idx() {
+                gm.tile[p] = if is_edge(gm.size, p) {
+                    TileKind::Wall
+                } else {
+                    TileKind::Floor
+                };
+            }
+            // Maps without inner tiles have only the center tile
+            let start = pick_passable_tile_checked(gm).unwrap_or_else(|| {
+                let center = Vec2d(gm.size.0 / 2, gm.size.1 / 2);
+                gm.tile[center] = TileKind::Floor;
+                center
+            });
+            (start, create_reach_map(gm, start).0)
+        };
+
+        fill_unreachable_and_set_entrance(gm, start, &reach_map, stairs);
+    }
+
+    fn fill_random(&self, gm: &mut GeneratedMap) {
+        let size = gm.size;
+        for p in gm.tile.iter_idx() {
+            gm.tile[p] = if is_edge(size, p) || gen_bool(self.wall_prob) {
+                TileKind::Wall
+            } else {
+                TileKind::Floor
+            };
+        }
+    }
+
+    fn step(&self, gm: &mut GeneratedMap) {
+        let size = gm.size;
+        let prev = gm.tile.clone();
+
+        for p in prev.iter_idx() {
+            if is_edge(size, p) {
+                continue;
+            }
+            let n_wall = count_wall_neighbours(&prev, p);
+            let wall = if prev[p] == TileKind::Wall {
+                n_wall >= self.survival
+            } else {
+                n_wall >= self.birth
+            };
+            gm.tile[p] = if wall {
+                TileKind::Wall
+            } else {
+                TileKind::Floor
+            };
+        }
+    }
+}
+
+fn is_edge(size: Vec2d, p: Vec2d) -> bool {
+    p.0 == 0 || p.1 == 0 || p.0 == size.0 - 1 || p.1 == size.1 - 1
+}
+
+/// Count walls in the 8 neighbours. Tiles outside the map are counted as walls.
+fn count_wall_neighbours(tile: &Array2d<TileKind>, center: Vec2d) -> u32 {
+    RectIter::new(center + (-1, -1), center + (1, 1))
+        .filter(|p| *p != center)
+        .filter(|p| tile.get(*p).map(|t| *t == TileKind::Wall).unwrap_or(true))
+        .count() as u32
+}
+
+fn pick_passable_tile_checked(gm: &GeneratedMap) -> Option<Vec2d> {
+    if gm.tile.iter().any(|t| t.is_passable()) {
+        Some(pick_passable_tile(gm))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,174 @@
+//! Guarantee that floor regions of generated maps are connected
+
+use super::{Entrance, GeneratedMap, TileKind};
+use geom::*;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// How to handle passable regions disconnected from the entrance
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum ConnectMethod {
+    /// Dig tunnels to connect regions
+    #[default]
+    Tunnel,
+    /// Fill regions with walls. Regions including stairs or exits are connected by tunnels.
+    Discard,
+}
+
+pub(crate) const FOUR_DIRS: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Label passable regions connected in four directions.
+/// Returns region ids for each tile and the number of regions.
+pub(crate) fn label_regions(gm: &GeneratedMap) -> (Array2d<Option<u32>>, u32) {
+    let mut labels = Array2d::new(gm.size.0 as u32, gm.size.1 as u32, None);
+    let mut n_region = 0;
+
+    for p in gm.tile.iter_idx() {
+        if !gm.tile[p].is_passable() || labels[p].is_some() {
+            continue;
+        }
+        labels[p] = Some(n_region);
+        let mut queue = VecDeque::new();
+        queue.push_back(p);
+        while let Some(p) = queue.pop_front() {
+            for dir in &FOUR_DIRS {
+                let next = p + *dir;
+                if gm.tile.in_range(next) && gm.tile[next].is_passable() && labels[next].is_none() {
+                    labels[next] = Some(n_region);
+                    queue.push_back(next);
+                }
+            }
+        }
+        n_region += 1;
+    }
+
+    (labels, n_region)
+}
+
+impl GeneratedMap {
+    /// The position where the player arrives
+    pub fn start_pos(&self) -> Vec2d {
+        match self.entrance {
+            Entrance::Pos(ref v) => v.first().copied().unwrap_or(Vec2d(0, 0)),
+            Entrance::Stairs(e0, _) => e0,
+        }
+    }
+
+    /// The position to go to the next floor or to exit
+    pub fn exit_pos(&self) -> Option<Vec2d> {
+        match self.entrance {
+            Entrance::Stairs(_, Some(e1)) => Some(e1),
+            _ => self.exit,
+        }
+    }
+
+    /// Positions of entrances, stairs and exits
+    pub fn key_positions(&self) -> Vec<Vec2d> {
+        let mut positions = match self.entrance {
+            Entrance::Pos(ref v) => v.to_vec(),
+            Entrance::Stairs(e0, e1) => std::iter::once(e0).chain(e1).collect(),
+        };
+        positions.extend(self.exit);
+        positions
+    }
+
+    /// Returns true if entrances, stairs and exits are mutually reachable
+    pub fn is_connected(&self) -> bool {
+        let (labels, _) = label_regions(self);
+        let positions = self.key_positions();
+        let first = if let Some(p) = positions.first() {
+            labels[*p]
+        } else {
+            return true;
+        };
+        first.is_some() && positions.iter().all(|p| labels[*p] == first)
+    }
+
+    /// Connect or discard passable regions that are not reachable from the entrance
+    pub fn connect_regions(&mut self, method: ConnectMethod) {
+        loop {
+            let (labels, n_region) = label_regions(self);
+            if n_region <= 1 {
+                return;
+            }
+            let main_region = labels[self.start_pos()].unwrap_or_else(|| largest_region(&labels));
+            let key_positions = self.key_positions();
+
+            let region = if let Some(region) = (0..n_region).find(|r| *r != main_region) {
+                region
+            } else {
+                return;
+            };
+            let has_key_pos = key_positions.iter().any(|p| labels[*p] == Some(region));
+
+            if method == ConnectMethod::Discard && !has_key_pos {
+                for p in self.tile.iter_idx() {
+                    if labels[p] == Some(region) {
+                        self.tile[p] = TileKind::Wall;
+                    }
+                }
+            } else if !self.dig_tunnel(&labels, region) {
+                // Not connectable, so discard it
+                for p in self.tile.iter_idx() {
+                    if labels[p] == Some(region) {
+                        self.tile[p] = TileKind::Wall;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Dig the shortest tunnel from the region to another passable region.
+    /// The outermost tiles are not dug.
+    fn dig_tunnel(&mut self, labels: &Array2d<Option<u32>>, region: u32) -> bool {
+        let size = self.size;
+        let is_inner = |p: Vec2d| 0 < p.0 && p.0 < size.0 - 1 && 0 < p.1 && p.1 < size.1 - 1;
+        let mut prev: Array2d<Option<Vec2d>> = Array2d::new(size.0 as u32, size.1 as u32, None);
+        let mut queue: VecDeque<Vec2d> = self
+            .tile
+            .iter_idx()
+            .filter(|p| labels[*p] == Some(region))
+            .collect();
+
+        while let Some(p) = queue.pop_front() {
+            for dir in &FOUR_DIRS {
+                let next = p + *dir;
+                if !self.tile.in_range(next) || labels[next] == Some(region) || prev[next].is_some()
+                {
+                    continue;
+                }
+                if labels[next].is_some() {
+                    // Reached another region
+                    let mut q = p;
+                    while labels[q] != Some(region) {
+                        self.tile[q] = TileKind::Floor;
+                        q = prev[q].unwrap();
+                    }
+                    return true;
+                }
+                if is_inner(next) {
+                    prev[next] = Some(p);
+                    queue.push_back(next);
+                }
+            }
+        }
+        false
+    }
+}
+
+fn largest_region(labels: &Array2d<Option<u32>>) -> u32 {
+    let mut counts: Vec<u32> = Vec::new();
+    for label in labels.iter().flatten() {
+        let label = *label as usize;
+        if counts.len() <= label {
+            counts.resize(label + 1, 0);
+        }
+        counts[label] += 1;
+    }
+    counts
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, n)| **n)
+        .map(|(i, _)| i as u32)
+        .unwrap_or(0)
+}
@@ -28,16 +28,16 @@ pub fn write_to_map(gm: &mut GeneratedMap, wall_weight: f32, enable_edge_bias: b
         }
     };
 
-    if stairs {
-        loop {
-            let end = pick_passable_tile(gm);
-            if start != end && reach_map[end] {
-                gm.entrance = Entrance::Stairs(start, Some(end));
-                break;
-            }
-        }
-    }
+    fill_unreachable_and_set_entrance(gm, start, &reach_map, stairs);
+}
 
+/// Write walls for unreachable tiles from the start, and set stairs or an entrance near the center
+pub(crate) fn fill_unreachable_and_set_entrance(
+    gm: &mut GeneratedMap,
+    start: Vec2d,
+    reach_map: &Array2d<bool>,
+    stairs: bool,
+) {
     // Write walls for unreachable tiles from the start
     for p in gm.tile.iter_idx() {
         if !reach_map[p] && gm.tile[p] != TileKind::Wall {
@@ -45,7 +45,20 @@ pub fn write_to_map(gm: &mut GeneratedMap, wall_weight: f32, enable_edge_bias: b
         }
     }
 
-    if !stairs {
+    if stairs {
+        // All passable tiles are reachable from the start here
+        let candidates: Vec<Vec2d> = gm
+            .tile
+            .iter_idx()
+            .filter(|p| *p != start && gm.tile[*p].is_passable())
+            .collect();
+        let end = if candidates.is_empty() {
+            dig_next_to(gm, start)
+        } else {
+            Some(candidates[gen_range(0..candidates.len())])
+        };
+        gm.entrance = Entrance::Stairs(start, end);
+    } else {
         for p in SpiralIter::new((gm.size.0 / 2, gm.size.1 / 2)) {
             if !gm.tile.in_range(p) {
                 break;
@@ -60,6 +73,18 @@ pub fn write_to_map(gm: &mut GeneratedMap, wall_weight: f32, enable_edge_bias: b
     }
 }
 
+/// Dig a floor tile next to the start when no other tile is reachable.
+/// Returns None if the map has no room for it.
+fn dig_next_to(gm: &mut GeneratedMap, start: Vec2d) -> Option<Vec2d> {
+    let size = gm.size;
+    let end = [(0, -1), (1, 0), (0, 1), (-1, 0)]
+        .iter()
+        .map(|d| start + *d)
+        .find(|p| p.0 >= 1 && p.1 >= 1 && p.0 < size.0 - 1 && p.1 < size.1 - 1)?;
+    gm.tile[end] = TileKind::Floor;
+    Some(end)
+}
+
 pub fn create_fractal(size: Vec2d, enable_edge_bias: bool) -> Array2d<f32> {
     let mut map = Array2d::new(size.0 as u32, size.1 as u32, 0.0);
 
@@ -148,7 +173,7 @@ fn calc_threshold(fractal: &Array2d<f32>, floor_ratio: f32) -> f32 {
 }
 
 /// Calculate tiles are reacheable from given tile
-fn create_reach_map(map: &GeneratedMap, start: Vec2d) -> (Array2d<bool>, u32) {
+pub(crate) fn create_reach_map(map: &GeneratedMap, start: Vec2d) -> (Array2d<bool>, u32) {
     let mut reachable = Array2d::new(map.size.0 as u32, map.size.1 as u32, false);
     let mut reachable_tile_count = 0;
 
@@ -190,7 +215,7 @@ fn create_reach_map(map: &GeneratedMap, start: Vec2d) -> (Array2d<bool>, u32) {
 }
 
 /// Pick one passable tile at random
-fn pick_passable_tile(map: &GeneratedMap) -> Vec2d {
+pub(crate) fn pick_passable_tile(map: &GeneratedMap) -> Vec2d {
     loop {
         let p = Vec2d(gen_range(0..map.size.0), gen_range(0..map.size.1));
 
@@ -1,7 +1,7 @@
 use geom::*;
 use rng::*;
 
-use super::{Entrance, GeneratedMap, TileKind};
+use super::{Entrance, GeneratedMap, RoomRect, TileKind};
 
 pub struct Lattice {
     nx: u32,
@@ -39,6 +39,15 @@ impl Lattice {
         );
         gm.entrance = Entrance::Stairs(e0, Some(e1));
 
+        // Each lattice cell is a room
+        for cell in Vec2d(self.nx as i32, self.ny as i32).iter_from_zero() {
+            let top_left = Vec2d(cell.0 * (ew_wall_len + 1), cell.1 * (ns_wall_len + 1));
+            gm.rooms.push(RoomRect {
+                top_left,
+                bottom_right: top_left + (ew_wall_len - 1, ns_wall_len - 1),
+            });
+        }
+
         // Write horizontal walls
         for b in 0..(self.ny as i32 - 1) {
             let j = (ns_wall_len + 1) * (b + 1) - 1;
@@ -14,10 +14,20 @@ use arrayvec::ArrayVec;
 use geom::*;
 use serde_derive::{Deserialize, Serialize};
 
+pub use connectivity::ConnectMethod;
+pub use metrics::{MapMetrics, MapRequirements};
+
 pub mod binary;
+pub mod metrics;
+pub mod vault;
+pub mod world;
 
+mod bsp;
+mod cave;
+mod connectivity;
 mod fractal;
 mod lattice;
+mod mine;
 mod rooms;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -49,6 +59,33 @@ pub struct GeneratedMap {
     pub tile: Array2d<TileKind>,
     pub entrance: Entrance,
     pub exit: Option<Vec2d>,
+    /// Rooms made by room based generators
+    pub rooms: Vec<RoomRect>,
+}
+
+/// Rectangular area of a generated room
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RoomRect {
+    pub top_left: Vec2d,
+    /// Included in the room
+    pub bottom_right: Vec2d,
+}
+
+impl RoomRect {
+    pub fn size(&self) -> Vec2d {
+        self.bottom_right - self.top_left + (1, 1)
+    }
+
+    pub fn contains(&self, pos: Vec2d) -> bool {
+        self.top_left.0 <= pos.0
+            && pos.0 <= self.bottom_right.0
+            && self.top_left.1 <= pos.1
+            && pos.1 <= self.bottom_right.1
+    }
+
+    pub fn iter(&self) -> RectIter {
+        RectIter::new(self.top_left, self.bottom_right)
+    }
 }
 
 impl GeneratedMap {
@@ -61,6 +98,7 @@ impl GeneratedMap {
             tile: Array2d::new(size.0 as u32, size.1 as u32, TileKind::Floor),
             entrance: Entrance::Pos(v),
             exit: None,
+            rooms: Vec::new(),
         }
     }
 }
@@ -94,6 +132,35 @@ pub enum MapGenParam {
         min_room_size: u32,
         n_room: u32,
     },
+    /// Caves generated by cellular automata
+    Cave {
+        w: u32,
+        h: u32,
+        stairs: bool,
+        /// Initial probability of wall tiles
+        wall_prob: f32,
+        /// A floor tile becomes a wall if it has this number of wall neighbours or more
+        birth: u32,
+        /// A wall tile remains if it has this number of wall neighbours or more
+        survival: u32,
+        iterations: u32,
+    },
+    /// Rooms and corridors generated by binary space partitioning
+    Bsp {
+        w: u32,
+        h: u32,
+        min_leaf_size: u32,
+        min_room_size: u32,
+        max_depth: u32,
+    },
+    /// Tunnels dug by drunkard's walk
+    Mine {
+        w: u32,
+        h: u32,
+        stairs: bool,
+        floor_ratio: f32,
+        n_walker: u32,
+    },
 }
 
 impl MapGenParam {
@@ -103,10 +170,44 @@ impl MapGenParam {
             MapGenParam::Lattice { w, h, .. } => (w, h),
             MapGenParam::Fractal { w, h, .. } => (w, h),
             MapGenParam::Rooms { w, h, .. } => (w, h),
+            MapGenParam::Cave { w, h, .. } => (w, h),
+            MapGenParam::Bsp { w, h, .. } => (w, h),
+            MapGenParam::Mine { w, h, .. } => (w, h),
         }
     }
 
+    /// Generate a map. Disconnected regions are connected by tunnels
+    /// only for generators that can produce them, so other maps stay the same for a seed.
     pub fn generate(&self) -> GeneratedMap {
+        let mut map = self.generate_raw();
+        if self.may_disconnect() {
+            map.connect_regions(ConnectMethod::Tunnel);
+        }
+        map
+    }
+
+    /// Generators that may leave passable regions disconnected
+    fn may_disconnect(&self) -> bool {
+        matches!(
+            self,
+            MapGenParam::Fractal { .. } | MapGenParam::Cave { .. } | MapGenParam::Mine { .. }
+        )
+    }
+
+    /// Generate maps until it satisfies the requirements
+    pub fn generate_with_requirements(&self, requirements: &MapRequirements) -> GeneratedMap {
+        let mut n_try = 1;
+        loop {
+            let mut map = self.generate_raw();
+            map.connect_regions(requirements.connect_method);
+            if n_try >= requirements.max_try || requirements.accepts(&map.metrics()) {
+                return map;
+            }
+            n_try += 1;
+        }
+    }
+
+    fn generate_raw(&self) -> GeneratedMap {
         match *self {
             MapGenParam::Flat { w, h } => GeneratedMap::new((w, h)),
             MapGenParam::Lattice {
@@ -146,6 +247,56 @@ impl MapGenParam {
                 rooms.write_to_map(&mut map);
                 map
             }
+            MapGenParam::Cave {
+                w,
+                h,
+                stairs,
+                wall_prob,
+                birth,
+                survival,
+                iterations,
+            } => {
+                let mut map = GeneratedMap::new((w, h));
+                let cave = cave::Cave {
+                    wall_prob,
+                    birth,
+                    survival,
+                    iterations,
+                };
+                cave.write_to_map(&mut map, stairs);
+                map
+            }
+            MapGenParam::Bsp {
+                w,
+                h,
+                min_leaf_size,
+                min_room_size,
+                max_depth,
+            } => {
+                let mut map = GeneratedMap::new((w, h));
+                let bsp = bsp::Bsp {
+                    min_leaf_size,
+                    min_room_size,
+                    max_depth,
+                };
+                bsp.write_to_map(&mut map);
+                map
+            }
+            MapGenParam::Mine {
+                w,
+                h,
+                stairs,
+                floor_ratio,
+                n_walker,
+            } => {
+                let mut map = GeneratedMap::new((w, h));
+                let mine = mine::Mine {
+                    floor_ratio,
+                    n_walker,
+                };
+                mine.write_to_map(&mut map, stairs);
+                map
+            }
         }
     }
 }
@@ -254,4 +405,399 @@ mod tests {
         let map = gen_param.generate();
         println!("Rooms map:\n{}", map);
     }
+
+    /// Assert that the stairs are on different tiles and connected
+    fn assert_stairs(map: &GeneratedMap) {
+        match map.entrance {
+            Entrance::Stairs(start, Some(end)) => {
+                assert_ne!(start, end, "\n{}", map);
+                assert!(map.metrics().path_length.is_some(), "\n{}", map);
+            }
+            _ => panic!("no stairs in the map:\n{}", map),
+        }
+    }
+
+    fn cave(w: u32, h: u32, wall_prob: f32) -> MapGenParam {
+        MapGenParam::Cave {
+            w,
+            h,
+            stairs: true,
+            wall_prob,
+            birth: 5,
+            survival: 4,
+            iterations: 4,
+        }
+    }
+
+    fn mine(w: u32, h: u32, floor_ratio: f32) -> MapGenParam {
+        MapGenParam::Mine {
+            w,
+            h,
+            stairs: true,
+            floor_ratio,
+            n_walker: 3,
+        }
+    }
+
+    #[test]
+    fn cave_map() {
+        // High wall probabilities leave few floor tiles, and 1.0 leaves none
+        let gen_params = [
+            cave(40, 30, 0.45),
+            cave(20, 15, 0.62),
+            cave(40, 30, 0.65),
+            cave(40, 30, 1.0),
+            cave(4, 3, 0.45),
+            cave(5, 5, 0.62),
+        ];
+        for seed in 0..20 {
+            rng::reseed_with(seed);
+            for gen_param in &gen_params {
+                assert_stairs(&gen_param.generate());
+            }
+        }
+
+        // No room for the end stairs
+        let map = cave(3, 3, 0.45).generate();
+        assert!(matches!(map.entrance, Entrance::Stairs(_, None)));
+    }
+
+    #[test]
+    fn bsp_map() {
+        let gen_param = MapGenParam::Bsp {
+            w: 40,
+            h: 30,
+            min_leaf_size: 6,
+            min_room_size: 3,
+            max_depth: 4,
+        };
+        for seed in 0..20 {
+            rng::reseed_with(seed);
+            assert_stairs(&gen_param.generate());
+        }
+
+        // A single room has only the start stairs
+        let gen_param = MapGenParam::Bsp {
+            w: 10,
+            h: 10,
+            min_leaf_size: 6,
+            min_room_size: 3,
+            max_depth: 4,
+        };
+        let map = gen_param.generate();
+        assert!(matches!(map.entrance, Entrance::Stairs(_, None)));
+    }
+
+    #[test]
+    fn mine_map() {
+        let gen_params = [mine(40, 30, 0.35), mine(40, 30, 0.0), mine(4, 3, 0.35)];
+        for seed in 0..20 {
+            rng::reseed_with(seed);
+            for gen_param in &gen_params {
+                assert_stairs(&gen_param.generate());
+            }
+        }
+
+        // No room for the end stairs
+        let map = mine(3, 3, 0.35).generate();
+        assert!(matches!(map.entrance, Entrance::Stairs(_, None)));
+
+        let gen_param = MapGenParam::Mine {
+            w: 40,
+            h: 30,
+            stairs: false,
+            floor_ratio: 0.35,
+            n_walker: 3,
+        };
+        let map = gen_param.generate();
+        assert!(matches!(map.entrance, Entrance::Pos(_)));
+    }
+
+    #[test]
+    fn metrics() {
+        // Floors around a wall and a dead end at the top
+        let mut map = GeneratedMap::new((5, 5));
+        for p in map.tile.iter_idx() {
+            map.tile[p] = TileKind::Wall;
+        }
+        for p in RectIter::new((1, 2), (3, 4)) {
+            map.tile[p] = TileKind::Floor;
+        }
+        map.tile[(2, 3)] = TileKind::Wall;
+        map.tile[(2, 1)] = TileKind::Floor;
+        map.entrance = Entrance::Stairs(Vec2d(2, 1), Some(Vec2d(2, 4)));
+
+        let metrics = map.metrics();
+        println!("Metrics map:\n{}\n{:?}", map, metrics);
+        assert_eq!(metrics.n_regions, 1);
+        assert_eq!(metrics.dead_ends, 1);
+        assert_eq!(metrics.loops, 1);
+        assert_eq!(metrics.path_length, Some(5));
+    }
+
+    #[test]
+    fn connect_regions() {
+        let new_map = || {
+            let mut map = GeneratedMap::new((9, 5));
+            for p in map.tile.iter_idx() {
+                map.tile[p] = TileKind::Wall;
+            }
+            map.tile[(1, 2)] = TileKind::Floor;
+            map.tile[(4, 1)] = TileKind::Floor;
+            map.tile[(7, 2)] = TileKind::Floor;
+            map.entrance = Entrance::Stairs(Vec2d(1, 2), Some(Vec2d(7, 2)));
+            map
+        };
+
+        let mut map = new_map();
+        assert!(!map.is_connected());
+        map.connect_regions(ConnectMethod::Tunnel);
+        assert!(map.is_connected());
+        assert_eq!(map.metrics().n_regions, 1);
+        assert_eq!(map.tile[(4, 1)], TileKind::Floor);
+
+        // Regions without stairs are discarded
+        let mut map = new_map();
+        map.connect_regions(ConnectMethod::Discard);
+        assert!(map.is_connected());
+        assert_eq!(map.metrics().n_regions, 1);
+        assert_eq!(map.tile[(4, 1)], TileKind::Wall);
+
+        let mut map = new_map();
+        map.entrance = Entrance::Stairs(Vec2d(1, 2), None);
+        map.connect_regions(ConnectMethod::Discard);
+        assert_eq!(map.metrics().n_regions, 1);
+        assert_eq!(map.tile[(7, 2)], TileKind::Wall);
+    }
+
+    #[test]
+    fn connectivity_for_many_seeds() {
+        let gen_params = [
+            MapGenParam::Lattice {
+                w: 19,
+                h: 15,
+                nx: 5,
+                ny: 4,
+                step_min: 3,
+                step_max: 7,
+                door_weight: 0.5,
+            },
+            MapGenParam::Fractal {
+                w: 24,
+                h: 24,
+                stairs: true,
+                edge: true,
+                wall_weight: 0.5,
+            },
+            MapGenParam::Rooms {
+                w: 30,
+                h: 30,
+                min_room_size: 4,
+                max_room_size: 7,
+                n_room: 6,
+            },
+            MapGenParam::Cave {
+                w: 30,
+                h: 24,
+                stairs: true,
+                wall_prob: 0.45,
+                birth: 5,
+                survival: 4,
+                iterations: 4,
+            },
+            MapGenParam::Bsp {
+                w: 30,
+                h: 24,
+                min_leaf_size: 6,
+                min_room_size: 3,
+                max_depth: 3,
+            },
+            MapGenParam::Mine {
+                w: 30,
+                h: 24,
+                stairs: true,
+                floor_ratio: 0.3,
+                n_walker: 2,
+            },
+        ];
+
+        for seed in 0..1000 {
+            rng::reseed_with(seed);
+            for gen_param in &gen_params {
+                let map = gen_param.generate();
+                let metrics = map.metrics();
+                assert!(map.is_connected(), "seed {}:\n{}", seed, map);
+                assert_eq!(metrics.n_regions, 1, "seed {}:\n{}", seed, map);
+                assert!(metrics.path_length.is_some(), "seed {}:\n{}", seed, map);
+            }
+        }
+    }
+
+    #[test]
+    fn generate_without_connection_pass() {
+        // Maps by generators that keep regions connected are not changed by generate()
+        let gen_params = [
+            MapGenParam::Flat { w: 10, h: 10 },
+            MapGenParam::Rooms {
+                w: 30,
+                h: 30,
+                min_room_size: 4,
+                max_room_size: 7,
+                n_room: 6,
+            },
+            MapGenParam::Bsp {
+                w: 30,
+                h: 24,
+                min_leaf_size: 6,
+                min_room_size: 3,
+                max_depth: 3,
+            },
+        ];
+        for seed in 0..20 {
+            for gen_param in &gen_params {
+                rng::reseed_with(seed);
+                let map = gen_param.generate();
+                rng::reseed_with(seed);
+                let raw = gen_param.generate_raw();
+                assert!(
+                    map.tile.iter().eq(raw.tile.iter()),
+                    "seed {}:\n{}",
+                    seed,
+                    map
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn generate_with_requirements() {
+        let gen_param = MapGenParam::Cave {
+            w: 40,
+            h: 30,
+            stairs: true,
+            wall_prob: 0.45,
+            birth: 5,
+            survival: 4,
+            iterations: 4,
+        };
+        let requirements = MapRequirements {
+            min_floor_ratio: 0.3,
+            min_path_length: 10,
+            ..MapRequirements::default()
+        };
+        let map = gen_param.generate_with_requirements(&requirements);
+        println!("Cave map with requirements:\n{}\n{:?}", map, map.metrics());
+    }
+
+    #[test]
+    fn vault_transform() {
+        use crate::vault::VaultTransform;
+        let size = Vec2d(3, 2);
+        let t = VaultTransform {
+            rotation: 1,
+            mirror: false,
+        };
+        assert_eq!(t.size(size), Vec2d(2, 3));
+        assert_eq!(t.apply(Vec2d(0, 0), size), Vec2d(1, 0));
+        assert_eq!(t.apply(Vec2d(2, 1), size), Vec2d(0, 2));
+        let t = VaultTransform {
+            rotation: 2,
+            mirror: true,
+        };
+        assert_eq!(t.apply(Vec2d(0, 0), size), Vec2d(0, 1));
+    }
+
+    #[test]
+    fn vault_map() {
+        use crate::connectivity::label_regions;
+        use crate::vault::{Vault, VaultTransform};
+        // A small room in a map filled with walls
+        let new_map = || {
+            let mut map = GeneratedMap::new((30, 20));
+            for p in map.tile.iter_idx() {
+                map.tile[p] = if p.0 >= 2 && p.0 <= 6 && p.1 >= 2 && p.1 <= 6 {
+                    TileKind::Floor
+                } else {
+                    TileKind::Wall
+                };
+            }
+            map.entrance = Entrance::Stairs(Vec2d(3, 3), None);
+            map
+        };
+
+        let mut tile = Array2d::new(5, 4, TileKind::Floor);
+        for p in tile.iter_idx() {
+            if p.0 == 0 || p.1 == 0 || p.0 == 4 || p.1 == 3 {
+                tile[p] = TileKind::Wall;
+            }
+        }
+        tile[(2, 0)] = TileKind::Door;
+        let vault = Vault {
+            tile,
+            connections: vec![],
+        };
+
+        for seed in 0..10 {
+            rng::reseed_with(seed);
+            let mut map = new_map();
+            let transform = VaultTransform::random(true, true);
+            let top_left = vault.place(&mut map, transform);
+            println!("Vault map (placed at {:?}):\n{}", top_left, map);
+            let top_left = top_left.expect("vault is not placed");
+
+            // The vault interior is reachable from the entrance
+            let interior = top_left + transform.apply(Vec2d(2, 2), Vec2d(5, 4));
+            assert_eq!(map.tile[interior], TileKind::Floor);
+            let (labels, _) = label_regions(&map);
+            assert!(labels[interior].is_some());
+            assert_eq!(labels[interior], labels[map.start_pos()]);
+        }
+    }
+
+    #[test]
+    fn world_map() {
+        use crate::world::{Terrain, WorldGenParam};
+        let gen_param = WorldGenParam::default();
+        rng::reseed_with(42);
+        let world = gen_param.generate();
+        rng::reseed_with(42);
+        let another = gen_param.generate();
+
+        assert_eq!(world.towns, another.towns);
+        assert!(world
+            .terrain
+            .iter_idx()
+            .all(|p| world.terrain[p] == another.terrain[p] && world.road[p] == another.road[p]));
+        for (i, town) in world.towns.iter().enumerate() {
+            assert!(world.terrain[*town].is_habitable());
+            for other in &world.towns[i + 1..] {
+                assert!(town.mdistance(*other) >= gen_param.min_town_distance as i32);
+            }
+        }
+
+        let mut s = String::new();
+        for y in 0..world.size.1 {
+            for x in 0..world.size.0 {
+                let p = Vec2d(x, y);
+                s.push(if world.towns.contains(&p) {
+                    'T'
+                } else if world.road[p] {
+                    '+'
+                } else if world.river[p] {
+                    '~'
+                } else {
+                    match world.terrain[p] {
+                        Terrain::Sea => ' ',
+                        Terrain::Coast => '.',
+                        Terrain::Plain => ',',
+                        Terrain::Forest => 'f',
+                        Terrain::Desert => 'd',
+                        Terrain::Mountain => '^',
+                    }
+                });
+            }
+            s.push('\n');
+        }
+        println!("World map:\n{}", s);
+    }
 }
@@ -0,0 +1,141 @@
+//! Quality metrics of generated maps
+
+use super::connectivity::{label_regions, ConnectMethod, FOUR_DIRS};
+use super::GeneratedMap;
+use geom::*;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct MapMetrics {
+    /// The ratio of passable tiles
+    pub floor_ratio: f32,
+    /// The number of separated passable regions
+    pub n_regions: u32,
+    /// Passable tiles that have only one passable neighbour
+    pub dead_ends: u32,
+    /// The number of independent loops. Open spaces are not counted as loops.
+    pub loops: u32,
+    /// The length of the shortest path from the entrance to the exit
+    pub path_length: Option<u32>,
+}
+
+/// Requirements for generated maps. Maps not satisfying them are generated again.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MapRequirements {
+    pub connect_method: ConnectMethod,
+    pub min_floor_ratio: f32,
+    pub max_floor_ratio: f32,
+    pub max_dead_ends: Option<u32>,
+    pub min_loops: u32,
+    /// Only checked if the map has an exit
+    pub min_path_length: u32,
+    /// The maximum number of generation. The last map is used if all maps are rejected.
+    pub max_try: u32,
+}
+
+impl Default for MapRequirements {
+    fn default() -> Self {
+        MapRequirements {
+            connect_method: ConnectMethod::default(),
+            min_floor_ratio: 0.0,
+            max_floor_ratio: 1.0,
+            max_dead_ends: None,
+            min_loops: 0,
+            min_path_length: 0,
+            max_try: 10,
+        }
+    }
+}
+
+impl MapRequirements {
+    pub fn accepts(&self, metrics: &MapMetrics) -> bool {
+        metrics.n_regions <= 1
+            && self.min_floor_ratio <= metrics.floor_ratio
+            && metrics.floor_ratio <= self.max_floor_ratio
+            && self
+                .max_dead_ends
+                .map(|max| metrics.dead_ends <= max)
+                .unwrap_or(true)
+            && self.min_loops <= metrics.loops
+            && metrics
+                .path_length
+                .map(|len| self.min_path_length <= len)
+                .unwrap_or(true)
+    }
+}
+
+impl GeneratedMap {
+    pub fn metrics(&self) -> MapMetrics {
+        let is_passable = |p: Vec2d| self.tile.in_range(p) && self.tile[p].is_passable();
+
+        let mut n_passable: i64 = 0;
+        let mut n_edge: i64 = 0;
+        let mut n_open_square: i64 = 0;
+        let mut dead_ends: u32 = 0;
+
+        for p in self.tile.iter_idx() {
+            if !is_passable(p) {
+                continue;
+            }
+            n_passable += 1;
+            let n_neighbour = FOUR_DIRS.iter().filter(|d| is_passable(p + **d)).count();
+            if n_neighbour == 1 {
+                dead_ends += 1;
+            }
+            if is_passable(p + (1, 0)) {
+                n_edge += 1;
+            }
+            if is_passable(p + (0, 1)) {
+                n_edge += 1;
+            }
+            if is_passable(p + (1, 0)) && is_passable(p + (0, 1)) && is_passable(p + (1, 1)) {
+                n_open_square += 1;
+            }
+        }
+
+        let (_, n_regions) = label_regions(self);
+        // Cyclomatic number of the passable tile graph except cycles around 2x2 open squares
+        let loops = (n_edge + n_regions as i64 - n_passable - n_open_square).max(0) as u32;
+        let n_tile = (self.size.0 * self.size.1).max(1);
+
+        MapMetrics {
+            floor_ratio: n_passable as f32 / n_tile as f32,
+            n_regions,
+            dead_ends,
+            loops,
+            path_length: self
+                .exit_pos()
+                .and_then(|exit| self.path_length(self.start_pos(), exit)),
+        }
+    }
+
+    /// The length of the shortest path between given tiles
+    pub fn path_length(&self, start: Vec2d, goal: Vec2d) -> Option<u32> {
+        if !self.tile.in_range(start) || !self.tile[start].is_passable() {
+            return None;
+        }
+        let mut dist: Array2d<Option<u32>> =
+            Array2d::new(self.size.0 as u32, self.size.1 as u32, None);
+        dist[start] = Some(0);
+        let mut queue = VecDeque::new();
+        queue.push_back(start);
+
+        while let Some(p) = queue.pop_front() {
+            let d = dist[p].unwrap();
+            if p == goal {
+                return Some(d);
+            }
+            for dir in &FOUR_DIRS {
+                let next = p + *dir;
+                if self.tile.in_range(next) && self.tile[next].is_passable() && dist[next].is_none()
+                {
+                    dist[next] = Some(d + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,60 @@
+use super::fractal::{create_reach_map, fill_unreachable_and_set_entrance};
+use super::{GeneratedMap, TileKind};
+use geom::*;
+use rng::gen_range;
+
+/// Mine generator by drunkard's walk
+pub struct Mine {
+    /// Walkers dig until the ratio of floor tiles reaches this
+    pub floor_ratio: f32,
+    /// The number of walkers. Each walker starts from the center.
+    pub n_walker: u32,
+}
+
+impl Mine {
+    pub fn write_to_map(&self, gm: &mut GeneratedMap, stairs: bool) {
+        let size = gm.size;
+        for p in gm.tile.iter_idx() {
+            gm.tile[p] = TileKind::Wall;
+        }
+
+        let start = Vec2d(size.0 / 2, size.1 / 2);
+        gm.tile[start] = TileKind::Floor;
+
+        // The outermost tiles are not dug
+        let n_inner_tile = ((size.0 - 2).max(0) * (size.1 - 2).max(0)) as u32;
+        // At least two floor tiles are needed for stairs
+        let n_target = ((n_inner_tile as f32 * self.floor_ratio) as u32)
+            .max(2)
+            .min(n_inner_tile);
+        let n_walker = self.n_walker.max(1);
+        let mut n_floor = 1;
+        let mut walkers = vec![start; n_walker as usize];
+
+        'dig_loop: loop {
+            for walker in walkers.iter_mut() {
+                if n_floor >= n_target {
+                    break 'dig_loop;
+                }
+                let dir = match gen_range(0..4) {
+                    0 => (0, -1),
+                    1 => (1, 0),
+                    2 => (0, 1),
+                    _ => (-1, 0),
+                };
+                let next = *walker + dir;
+                if next.0 < 1 || next.1 < 1 || next.0 >= size.0 - 1 || next.1 >= size.1 - 1 {
+                    continue;
+                }
+                *walker = next;
+                if gm.tile[next] == TileKind::Wall {
+                    gm.tile[next] = TileKind::Floor;
+                    n_floor += 1;
+                }
+            }
+        }
+
+        let (reach_map, _) = create_reach_map(gm, start);
+        fill_unreachable_and_set_entrance(gm, start, &reach_map, stairs);
+    }
+}
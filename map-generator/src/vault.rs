@@ -0,0 +1,162 @@
+//! Placing prefab rooms (vaults) into generated maps
+
+use super::connectivity::FOUR_DIRS;
+use super::{GeneratedMap, TileKind};
+use geom::*;
+use rng::gen_range;
+use std::collections::VecDeque;
+
+/// Rotation and mirroring applied to a vault
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct VaultTransform {
+    /// The number of clockwise quarter turns
+    pub rotation: u8,
+    /// Mirror horizontally before rotating
+    pub mirror: bool,
+}
+
+impl VaultTransform {
+    pub fn random(rotate: bool, mirror: bool) -> VaultTransform {
+        VaultTransform {
+            rotation: if rotate { gen_range(0..4) } else { 0 },
+            mirror: mirror && gen_range(0..2) == 1,
+        }
+    }
+
+    /// Vault size after this transform
+    pub fn size(self, size: Vec2d) -> Vec2d {
+        if self.rotation % 2 == 1 {
+            Vec2d(size.1, size.0)
+        } else {
+            size
+        }
+    }
+
+    /// Convert a position in the original vault of given size to the transformed position
+    pub fn apply(self, pos: Vec2d, size: Vec2d) -> Vec2d {
+        let mut pos = pos;
+        let mut size = size;
+        if self.mirror {
+            pos = Vec2d(size.0 - 1 - pos.0, pos.1);
+        }
+        for _ in 0..(self.rotation % 4) {
+            pos = Vec2d(size.1 - 1 - pos.1, pos.0);
+            size = Vec2d(size.1, size.0);
+        }
+        pos
+    }
+}
+
+/// Prefab room to be placed in a generated map
+pub struct Vault {
+    pub tile: Array2d<TileKind>,
+    /// Tiles to be connected to the outside of the vault.
+    /// If empty, passable tiles on the edge of the vault are used.
+    pub connections: Vec<Vec2d>,
+}
+
+impl Vault {
+    /// Place this vault into the space filled with walls, and dig corridors from its connection points.
+    /// Returns the top left position of the placed vault.
+    pub fn place(&self, gm: &mut GeneratedMap, transform: VaultTransform) -> Option<Vec2d> {
+        let orig_size: Vec2d = self.tile.size().into();
+        let size = transform.size(orig_size);
+
+        let connections: Vec<Vec2d> = if self.connections.is_empty() {
+            self.tile
+                .iter_idx()
+                .filter(|p| {
+                    self.tile[*p].is_passable()
+                        && (p.0 == 0
+                            || p.1 == 0
+                            || p.0 == orig_size.0 - 1
+                            || p.1 == orig_size.1 - 1)
+                })
+                .collect()
+        } else {
+            self.connections.clone()
+        };
+        let connections: Vec<Vec2d> = connections
+            .into_iter()
+            .map(|p| transform.apply(p, orig_size))
+            .filter(|p| p.0 == 0 || p.1 == 0 || p.0 == size.0 - 1 || p.1 == size.1 - 1)
+            .collect();
+        if connections.is_empty() {
+            return None;
+        }
+
+        let top_left = choose_free_space(gm, size)?;
+        for p in self.tile.iter_idx() {
+            gm.tile[top_left + transform.apply(p, orig_size)] = self.tile[p];
+        }
+        for c in &connections {
+            dig_corridor(gm, top_left, size, top_left + *c);
+        }
+        Some(top_left)
+    }
+}
+
+/// Choose the position where the rectangle of given size and its surroundings are all walls
+fn choose_free_space(gm: &GeneratedMap, size: Vec2d) -> Option<Vec2d> {
+    if size.0 + 2 > gm.size.0 || size.1 + 2 > gm.size.1 {
+        return None;
+    }
+    let is_free = |top_left: Vec2d| {
+        RectIter::new(top_left - (1, 1), top_left + size)
+            .all(|p| gm.tile[p] == TileKind::Wall && gm.exit != Some(p))
+    };
+
+    let candidates: Vec<Vec2d> = RectIter::new(Vec2d(1, 1), gm.size - size - (1, 1))
+        .filter(|p| is_free(*p))
+        .collect();
+
+    if candidates.is_empty() {
+        None
+    } else {
+        Some(candidates[gen_range(0..candidates.len())])
+    }
+}
+
+/// Dig the shortest corridor from the connection point to the nearest passable tile outside the vault
+fn dig_corridor(gm: &mut GeneratedMap, top_left: Vec2d, size: Vec2d, start: Vec2d) {
+    let in_vault = |p: Vec2d| {
+        top_left.0 <= p.0
+            && p.0 < top_left.0 + size.0
+            && top_left.1 <= p.1
+            && p.1 < top_left.1 + size.1
+    };
+    // The outermost tiles are not dug
+    let is_inner = |p: Vec2d| 0 < p.0 && p.0 < gm.size.0 - 1 && 0 < p.1 && p.1 < gm.size.1 - 1;
+
+    let mut prev: Array2d<Option<Vec2d>> = Array2d::new(gm.size.0 as u32, gm.size.1 as u32, None);
+    let mut queue = VecDeque::new();
+    queue.push_back(start);
+    let mut goal = None;
+
+    while let Some(p) = queue.pop_front() {
+        for dir in &FOUR_DIRS {
+            let next = p + *dir;
+            if !is_inner(next) || in_vault(next) || next == start || prev[next].is_some() {
+                continue;
+            }
+            prev[next] = Some(p);
+            if gm.tile[next].is_passable() {
+                goal = Some(next);
+                break;
+            }
+            queue.push_back(next);
+        }
+        if goal.is_some() {
+            break;
+        }
+    }
+
+    let mut p = if let Some(goal) = goal { goal } else { return };
+    while let Some(q) = prev[p] {
+        if q == start {
+            break;
+        }
+        gm.tile[q] = TileKind::Floor;
+        p = q;
+    }
+}
@@ -0,0 +1,349 @@
+//! Region (world map) generation by heightmap and moisture noise
+
+use geom::*;
+use rng::{gen_range, SliceRandom};
+use serde_derive::{Deserialize, Serialize};
+
+/// Terrain of a region map tile
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum Terrain {
+    Sea,
+    /// Land tiles adjacent to the sea
+    Coast,
+    Plain,
+    Forest,
+    Desert,
+    Mountain,
+}
+
+impl Terrain {
+    pub fn is_land(self) -> bool {
+        self != Terrain::Sea
+    }
+
+    /// Towns and roads can be built on this terrain
+    pub fn is_habitable(self) -> bool {
+        matches!(self, Terrain::Coast | Terrain::Plain | Terrain::Forest)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorldGenParam {
+    pub w: u32,
+    pub h: u32,
+    /// Grid size of the coarsest noise octave
+    pub noise_scale: u32,
+    pub octaves: u32,
+    /// Lower heights near the map edges to make the land surrounded by the sea
+    pub island: bool,
+    /// Tiles lower than this are sea
+    pub sea_level: f32,
+    /// Tiles higher than this are mountains
+    pub mountain_level: f32,
+    /// Land tiles drier than this are desert
+    pub desert_moisture: f32,
+    /// Land tiles wetter than this are forest
+    pub forest_moisture: f32,
+    pub n_rivers: u32,
+    pub n_towns: u32,
+    /// The minimum distance between towns
+    pub min_town_distance: u32,
+}
+
+impl Default for WorldGenParam {
+    fn default() -> Self {
+        WorldGenParam {
+            w: 64,
+            h: 48,
+            noise_scale: 16,
+            octaves: 4,
+            island: true,
+            sea_level: 0.35,
+            mountain_level: 0.75,
+            desert_moisture: 0.3,
+            forest_moisture: 0.6,
+            n_rivers: 4,
+            n_towns: 4,
+            min_town_distance: 10,
+        }
+    }
+}
+
+pub struct GeneratedWorld {
+    pub size: Vec2d,
+    /// Normalized to 0.0 - 1.0
+    pub height: Array2d<f32>,
+    /// Normalized to 0.0 - 1.0
+    pub moisture: Array2d<f32>,
+    pub terrain: Array2d<Terrain>,
+    pub river: Array2d<bool>,
+    pub road: Array2d<bool>,
+    pub towns: Vec<Vec2d>,
+}
+
+impl WorldGenParam {
+    /// Generate a world. The result depends only on the state of the random number generator.
+    pub fn generate(&self) -> GeneratedWorld {
+        let size = Vec2d(self.w as i32, self.h as i32);
+        let mut height = noise(self.w, self.h, self.noise_scale, self.octaves);
+        let moisture = noise(self.w, self.h, self.noise_scale, self.octaves);
+
+        if self.island {
+            let c = Vec2d(size.0 / 2, size.1 / 2);
+            for p in height.iter_idx() {
+                let dx = (p.0 - c.0) as f32 / c.0.max(1) as f32;
+                let dy = (p.1 - c.1) as f32 / c.1.max(1) as f32;
+                let d = (dx * dx + dy * dy).sqrt();
+                height[p] *= (1.0 - d * d).max(0.0);
+            }
+            normalize(&mut height);
+        }
+
+        let mut world = GeneratedWorld {
+            size,
+            terrain: Array2d::new(self.w, self.h, Terrain::Sea),
+            river: Array2d::new(self.w, self.h, false),
+            road: Array2d::new(self.w, self.h, false),
+            towns: Vec::new(),
+            height,
+            moisture,
+        };
+        self.assign_terrain(&mut world);
+        self.gen_rivers(&mut world);
+        self.gen_towns(&mut world);
+        world.gen_roads();
+        world
+    }
+
+    fn assign_terrain(&self, world: &mut GeneratedWorld) {
+        for p in world.terrain.iter_idx() {
+            let h = world.height[p];
+            let m = world.moisture[p];
+            world.terrain[p] = if h < self.sea_level {
+                Terrain::Sea
+            } else if h > self.mountain_level {
+                Terrain::Mountain
+            } else if m < self.desert_moisture {
+                Terrain::Desert
+            } else if m > self.forest_moisture {
+                Terrain::Forest
+            } else {
+                Terrain::Plain
+            };
+        }
+
+        let is_coast = |p: Vec2d, terrain: &Array2d<Terrain>| {
+            terrain[p] != Terrain::Sea
+                && terrain[p] != Terrain::Mountain
+                && Direction::EIGHT_DIRS
+                    .iter()
+                    .any(|dir| terrain.get(p + dir.as_vec()) == Some(&Terrain::Sea))
+        };
+        let coasts: Vec<Vec2d> = world
+            .terrain
+            .iter_idx()
+            .filter(|p| is_coast(*p, &world.terrain))
+            .collect();
+        for p in coasts {
+            world.terrain[p] = Terrain::Coast;
+        }
+    }
+
+    /// Rivers flow from high lands to the lowest neighbour until they reach the sea
+    fn gen_rivers(&self, world: &mut GeneratedWorld) {
+        let mut sources: Vec<Vec2d> = world
+            .terrain
+            .iter_idx()
+            .filter(|p| {
+                world.terrain[*p].is_land()
+                    && world.height[*p] > (self.sea_level + self.mountain_level) / 2.0
+            })
+            .collect();
+        sources.shuffle(&mut rng::get_rng());
+
+        for source in sources.into_iter().take(self.n_rivers as usize) {
+            let mut p = source;
+            let mut path = vec![p];
+            loop {
+                if world.terrain[p] == Terrain::Sea || world.river[p] {
+                    break;
+                }
+                let next = Direction::EIGHT_DIRS
+                    .iter()
+                    .filter(|dir| dir.hdir.is_none() || dir.vdir.is_none())
+                    .map(|dir| p + dir.as_vec())
+                    .filter(|next| world.terrain.in_range(*next) && !path.contains(next))
+                    .min_by(|a, b| world.height[*a].partial_cmp(&world.height[*b]).unwrap());
+                match next {
+                    Some(next) => {
+                        // Rivers dig through local minimums
+                        if world.height[next] > world.height[p] {
+                            world.height[next] = world.height[p];
+                        }
+                        p = next;
+                        path.push(p);
+                    }
+                    None => break,
+                }
+            }
+            for p in path {
+                if world.terrain[p].is_land() {
+                    world.river[p] = true;
+                }
+            }
+        }
+    }
+
+    fn gen_towns(&self, world: &mut GeneratedWorld) {
+        let mut candidates: Vec<Vec2d> = world
+            .terrain
+            .iter_idx()
+            .filter(|p| world.terrain[*p].is_habitable() && !world.river[*p])
+            .filter(|p| p.0 > 0 && p.1 > 0 && p.0 < world.size.0 - 1 && p.1 < world.size.1 - 1)
+            .collect();
+        candidates.shuffle(&mut rng::get_rng());
+        // Towns near rivers and coasts are preferred
+        candidates.sort_by_key(|p| {
+            !Direction::EIGHT_DIRS.iter().any(|dir| {
+                let p = *p + dir.as_vec();
+                world.river.get(p) == Some(&true) || world.terrain.get(p) == Some(&Terrain::Coast)
+            })
+        });
+
+        for p in candidates {
+            if world.towns.len() >= self.n_towns as usize {
+                break;
+            }
+            if world
+                .towns
+                .iter()
+                .all(|town| town.mdistance(p) >= self.min_town_distance as i32)
+            {
+                world.towns.push(p);
+            }
+        }
+    }
+}
+
+impl GeneratedWorld {
+    /// Connect towns by roads along the minimum spanning tree
+    fn gen_roads(&mut self) {
+        if self.towns.is_empty() {
+            return;
+        }
+        let mut connected = vec![self.towns[0]];
+        let mut rest: Vec<Vec2d> = self.towns[1..].to_vec();
+
+        while !rest.is_empty() {
+            let (i, start, end) = rest
+                .iter()
+                .enumerate()
+                .flat_map(|(i, a)| connected.iter().map(move |b| (i, *b, *a)))
+                .min_by_key(|(_, a, b)| a.mdistance(*b))
+                .unwrap();
+            rest.remove(i);
+            connected.push(end);
+
+            let terrain = &self.terrain;
+            let pathfinding = PathFinding::new(
+                self.size.0 as u32,
+                self.size.1 as u32,
+                (self.size.0 * self.size.1) as u32,
+                |p| matches!(terrain.get(p), Some(t) if t.is_land()),
+            );
+            let road = &self.road;
+            let route = pathfinding.route_with_cost(start, end, |p| {
+                if road[p] {
+                    1
+                } else {
+                    match terrain[p] {
+                        Terrain::Mountain => 20,
+                        Terrain::Forest | Terrain::Desert => 4,
+                        _ => 2,
+                    }
+                }
+            });
+            if let Some(route) = route {
+                for p in route {
+                    self.road[p] = true;
+                }
+            }
+        }
+        for &town in &self.towns {
+            self.road[town] = false;
+        }
+    }
+
+    /// Choose a random tile for a site with weights by terrain.
+    /// Tiles on towns, rivers and roads, and near the occupied tiles are not chosen.
+    pub fn choose_site_pos<F: Fn(Terrain) -> f32>(
+        &self,
+        occupied: &[Vec2d],
+        weight: F,
+    ) -> Option<Vec2d> {
+        let candidates: Vec<Vec2d> = self
+            .terrain
+            .iter_idx()
+            .filter(|p| !self.river[*p] && !self.road[*p])
+            .filter(|p| {
+                self.towns
+                    .iter()
+                    .chain(occupied)
+                    .all(|o| o.mdistance(*p) > 2)
+            })
+            .filter(|p| weight(self.terrain[*p]) > 0.0)
+            .collect();
+
+        candidates
+            .choose_weighted(&mut rng::get_rng(), |p| weight(self.terrain[*p]))
+            .ok()
+            .copied()
+    }
+}
+
+/// Generate value noise normalized to 0.0 - 1.0
+fn noise(w: u32, h: u32, scale: u32, octaves: u32) -> Array2d<f32> {
+    let mut a = Array2d::new(w, h, 0.0);
+    let mut amplitude = 1.0;
+    let mut scale = scale.max(1);
+
+    for _ in 0..octaves.max(1) {
+        let gw = w / scale + 2;
+        let gh = h / scale + 2;
+        let grid = Array2d::from_fn(gw, gh, |_| gen_range(0.0..1.0f32));
+        for p in a.iter_idx() {
+            let x = p.0 as f32 / scale as f32;
+            let y = p.1 as f32 / scale as f32;
+            let (x0, y0) = (x.floor() as i32, y.floor() as i32);
+            let (tx, ty) = (smoothstep(x - x0 as f32), smoothstep(y - y0 as f32));
+            let top = lerp(grid[(x0, y0)], grid[(x0 + 1, y0)], tx);
+            let bottom = lerp(grid[(x0, y0 + 1)], grid[(x0 + 1, y0 + 1)], tx);
+            a[p] += lerp(top, bottom, ty) * amplitude;
+        }
+        amplitude *= 0.5;
+        scale = (scale / 2).max(1);
+    }
+
+    normalize(&mut a);
+    a
+}
+
+fn normalize(a: &mut Array2d<f32>) {
+    let min = a.iter().copied().fold(f32::MAX, f32::min);
+    let max = a.iter().copied().fold(f32::MIN, f32::max);
+    if max - min <= f32::EPSILON {
+        return;
+    }
+    for p in a.iter_idx() {
+        a[p] = (a[p] - min) / (max - min);
+    }
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+fn smoothstep(t: f32) -> f32 {
+    t * t * (3.0 - 2.0 * t)
+}
@@ -60,6 +60,13 @@ pub fn reseed(fixed: bool) {
     })
 }
 
+/// Reseed with given seed
+pub fn reseed_with(seed: u64) {
+    XORSHIFT_RNG.with(|xorshift_rng| {
+        xorshift_rng.replace(XorShiftRng::seed_from_u64(seed));
+    })
+}
+
 pub fn next_u32() -> u32 {
     let mut rng = GameRng;
     rng.next_u32()
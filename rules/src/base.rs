@@ -0,0 +1,51 @@
+use super::Rule;
+
+/// Rules for player's bases
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Base {
+    /// Weight capacity of the shared storage (gram)
+    pub storage_capacity: u32,
+    /// Facility type of beds
+    pub bed_facility: String,
+    /// The maximum quality bonus from facilities aggregated in a base
+    pub facility_quality_max: i32,
+    /// Daily probability of visitor or raid events
+    pub event_prob: f32,
+    /// (raid probability) = (base wealth) / raid_wealth_scale, clamped by raid_prob_max
+    pub raid_wealth_scale: f32,
+    pub raid_prob_max: f32,
+    /// Base wealth per additional raider
+    pub wealth_per_raider: i64,
+    pub max_raiders: u32,
+    /// Character template ids of visitors
+    pub visitors: Vec<String>,
+    /// Character template ids of raiders
+    pub raiders: Vec<String>,
+}
+
+impl Default for Base {
+    fn default() -> Self {
+        Base {
+            storage_capacity: 1_000_000,
+            bed_facility: "bed".into(),
+            facility_quality_max: 5,
+            event_prob: 0.1,
+            raid_wealth_scale: 100_000.0,
+            raid_prob_max: 0.5,
+            wealth_per_raider: 20_000,
+            max_raiders: 5,
+            visitors: Vec::new(),
+            raiders: Vec::new(),
+        }
+    }
+}
+
+impl Rule for Base {
+    const NAME: &'static str = "base";
+
+    fn append(&mut self, other: Self) {
+        self.visitors.extend(other.visitors);
+        self.raiders.extend(other.raiders);
+    }
+}
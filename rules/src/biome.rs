@@ -1,4 +1,5 @@
 use super::Rule;
+use common::gamedata::FactionId;
 use common::gobj::ObjIdxAsId;
 use common::objholder::*;
 use serde_with::serde_as;
@@ -35,7 +36,139 @@ pub struct BiomeDetail {
     pub plants: Vec<(ItemIdx, f32)>,
     #[serde_as(as = "Vec<(ObjIdxAsId, _)>")]
     pub items: Vec<(ItemIdx, f32)>,
+    /// The probability of an encounter when entering the wilderness
+    #[serde(default)]
+    pub encounter_probability: f32,
+    #[serde(default)]
+    pub encounters: Vec<Encounter>,
 }
 
+/// Features and encounters added on top of the base biome
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SubBiomeDetail {
+    pub features: Vec<SubBiomeFeature>,
+    /// Added to the encounters of the base biome
+    pub encounters: Vec<Encounter>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+pub enum SubBiomeFeature {
+    /// Water flowing across the map, connected to the same sub-biome on adjacent region map tiles
+    River {
+        #[serde_as(as = "ObjIdxAsId")]
+        tile: TileIdx,
+        width: u32,
+        /// The number of passable gaps
+        fords: u32,
+    },
+    Lake {
+        #[serde_as(as = "ObjIdxAsId")]
+        tile: TileIdx,
+        radius: [u32; 2],
+    },
+    /// Rocks with ore walls
+    Outcrop {
+        #[serde_as(as = "ObjIdxAsId")]
+        wall: WallIdx,
+        #[serde_as(as = "Vec<(ObjIdxAsId, _)>")]
+        ore_walls: Vec<(WallIdx, f32)>,
+        n: [u32; 2],
+        radius: [u32; 2],
+    },
+    /// Fragments of ruined buildings
+    Ruins {
+        #[serde_as(as = "ObjIdxAsId")]
+        wall: WallIdx,
+        n: [u32; 2],
+        size: [u32; 2],
+        /// The probability that each wall of the building remains
+        remaining: f32,
+        #[serde_as(as = "Vec<(ObjIdxAsId, _)>")]
+        items: Vec<(ItemIdx, f32)>,
+    },
+    /// Open areas without walls and plants
+    Clearing { n: [u32; 2], radius: [u32; 2] },
+    /// Road connected to the same sub-biome on adjacent region map tiles
+    Road {
+        #[serde_as(as = "ObjIdxAsId")]
+        tile: TileIdx,
+        width: u32,
+    },
+}
+
+/// Random encounter when entering the wilderness
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Encounter {
+    pub kind: EncounterKind,
+    pub weight: f32,
+    /// Hours of day [start, end) when this encounter occurs. Wraps around midnight if start > end.
+    #[serde(default)]
+    pub hours: Option<[u16; 2]>,
+}
+
+impl Encounter {
+    pub fn is_available(&self, hour: u16) -> bool {
+        match self.hours {
+            Some([start, end]) if start <= end => start <= hour && hour < end,
+            Some([start, end]) => start <= hour || hour < end,
+            None => true,
+        }
+    }
+}
+
+#[serde_as]
 #[derive(Debug, Serialize, Deserialize)]
-pub struct SubBiomeDetail {}
+pub enum EncounterKind {
+    /// A group of npcs
+    Npcs {
+        #[serde_as(as = "Vec<(ObjIdxAsId, _)>")]
+        chara: Vec<(CharaTemplateIdx, f32)>,
+        n: [u32; 2],
+        #[serde(default)]
+        faction: Option<FactionId>,
+    },
+    /// Items left on the map
+    Items {
+        #[serde_as(as = "Vec<(ObjIdxAsId, _)>")]
+        items: Vec<(ItemIdx, u32)>,
+    },
+    /// Start a script after entering
+    Script { id: String },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn encounter(hours: Option<[u16; 2]>) -> Encounter {
+        Encounter {
+            kind: EncounterKind::Script { id: "x".into() },
+            weight: 1.0,
+            hours,
+        }
+    }
+
+    #[test]
+    fn encounter_hours() {
+        let e = encounter(None);
+        assert!((0..24).all(|hour| e.is_available(hour)));
+
+        let e = encounter(Some([6, 18]));
+        assert!(e.is_available(6));
+        assert!(e.is_available(17));
+        assert!(!e.is_available(5));
+        assert!(!e.is_available(18));
+
+        // Wraps around midnight
+        let e = encounter(Some([22, 4]));
+        assert!(e.is_available(22));
+        assert!(e.is_available(23));
+        assert!(e.is_available(0));
+        assert!(e.is_available(3));
+        assert!(!e.is_available(4));
+        assert!(!e.is_available(12));
+        assert!(!e.is_available(21));
+    }
+}
@@ -12,6 +12,21 @@ pub struct Chara {
     pub hp_regeneration_probability: f32,
     /// Multiplying factor of HP regeneration
     pub hp_regeneration_factor: f32,
+    /// (max mp) = (INT) * (MAGIC_DEVICE + 8) / 8 * (mp_factor)
+    #[serde(default = "mp_factor_default")]
+    pub mp_factor: f32,
+    /// The probability of MP regeneration per turn
+    #[serde(default = "mp_regeneration_probability_default")]
+    pub mp_regeneration_probability: f32,
+    /// Multiplying factor of MP regeneration
+    #[serde(default = "mp_regeneration_factor_default")]
+    pub mp_regeneration_factor: f32,
+    /// Failure probability of active skills per a skill level lacking for its difficulty
+    #[serde(default = "active_skill_failure_factor_default")]
+    pub active_skill_failure_factor: f32,
+    /// Maximum failure probability of active skills
+    #[serde(default = "active_skill_failure_max_default")]
+    pub active_skill_failure_max: f32,
     /// Default sp when a new character is created
     pub sp_default: f32,
     /// Maximum sp
@@ -50,3 +65,23 @@ impl Rule for Chara {
         *self = other;
     }
 }
+
+fn mp_factor_default() -> f32 {
+    1.0
+}
+
+fn mp_regeneration_probability_default() -> f32 {
+    0.2
+}
+
+fn mp_regeneration_factor_default() -> f32 {
+    0.1
+}
+
+fn active_skill_failure_factor_default() -> f32 {
+    0.05
+}
+
+fn active_skill_failure_max_default() -> f32 {
+    0.9
+}
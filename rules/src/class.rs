@@ -33,6 +33,9 @@ pub struct Class {
     pub attr: CharaAttrDiff,
     /// Skill bonus
     pub skill_bonus: HashMap<SkillKind, BonusLevel>,
+    /// Element protection by class
+    #[serde(default)]
+    pub element_protection: ElementArray<ElementProtection>,
     /// Item generation for equipment slot. The last number is level bonus.
     #[serde(default)]
     pub equips: Vec<(EquipSlotKind, ItemSelector, u32)>,
@@ -1,4 +1,6 @@
 use super::Rule;
+use common::gamedata::WeaponKind;
+use std::collections::HashMap;
 
 /// Rules for calculation related to combat.
 #[derive(Serialize, Deserialize)]
@@ -13,6 +15,12 @@ pub struct Combat {
     pub detection_range: i32,
     /// Factor for detection probability.
     pub detection_factor: f32,
+    /// Parameters for attack, defence and evasion power calculation.
+    #[serde(default)]
+    pub formula: CombatFormula,
+    /// Parameters for critical hits.
+    #[serde(default)]
+    pub critical: Critical,
 }
 
 impl Rule for Combat {
@@ -22,3 +30,152 @@ impl Rule for Combat {
         *self = other;
     }
 }
+
+/// Parameters for power calculation.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct CombatFormula {
+    /// (attack power) = (attr)^2 * ((skill) + skill_base)^(attack_skill_exponent)
+    pub attack_skill_exponent: f32,
+    /// (defence power) = ((equip) + defence_equip_base) * (VIT) * ((skill) + defence_skill_base)
+    pub defence_equip_base: f32,
+    pub defence_skill_base: f32,
+    /// (evasion power) = evasion_equip + (skill) + (DEX) * evasion_dex_factor
+    pub evasion_equip: f32,
+    pub evasion_dex_factor: f32,
+    /// (hit probability) = 1 / (1 + exp(-((hit power) - (evasion power)) * hit_slope))
+    pub hit_slope: f32,
+}
+
+impl Default for CombatFormula {
+    fn default() -> Self {
+        CombatFormula {
+            attack_skill_exponent: 1.5,
+            defence_equip_base: 16.0,
+            defence_skill_base: 8.0,
+            evasion_equip: 1.0,
+            evasion_dex_factor: 0.125,
+            hit_slope: 0.125,
+        }
+    }
+}
+
+/// Parameters for critical hits.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Critical {
+    /// Base probability of critical hits
+    pub base: f32,
+    /// Additional probability per DEX
+    pub dex_factor: f32,
+    /// Additional probability per weapon skill level
+    pub skill_factor: f32,
+    /// Maximum probability of critical hits
+    pub max: f32,
+    /// Damage multiplier of critical hits
+    pub multiplier: f32,
+    /// Adjustment for each weapon kind
+    pub weapon: HashMap<WeaponKind, WeaponCritical>,
+}
+
+impl Default for Critical {
+    fn default() -> Self {
+        Critical {
+            base: 0.02,
+            dex_factor: 0.001,
+            skill_factor: 0.002,
+            max: 0.5,
+            multiplier: 1.5,
+            weapon: HashMap::default(),
+        }
+    }
+}
+
+/// Critical hit adjustment for a weapon kind.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WeaponCritical {
+    /// Additional probability
+    pub chance: f32,
+    /// Multiplied to the base damage multiplier
+    pub multiplier: f32,
+}
+
+impl Default for WeaponCritical {
+    fn default() -> Self {
+        WeaponCritical {
+            chance: 0.0,
+            multiplier: 1.0,
+        }
+    }
+}
+
+impl Combat {
+    /// Attack power by the attribute and the weapon skill level
+    pub fn attack_power(&self, attr: u16, skill_level: u32) -> f32 {
+        let attr = attr as f32;
+        let skill_level = skill_level as f32;
+        attr * attr * (skill_level + self.skill_base).powf(self.formula.attack_skill_exponent)
+    }
+
+    /// Hit power by the attribute and the weapon skill level
+    pub fn hit_power(&self, attr: u16, skill_level: u32) -> f32 {
+        attr as f32 * (skill_level as f32 + self.skill_base)
+    }
+
+    pub fn defence_power(&self, equip_def: u32, vit: u16, skill_level: u32) -> f32 {
+        let f = &self.formula;
+        (equip_def as f32 + f.defence_equip_base)
+            * vit.max(1) as f32
+            * (skill_level as f32 + f.defence_skill_base)
+    }
+
+    /// Damage factor by element protection in percent.
+    /// Protection of 100 or more means immunity, and weakness at most doubles the damage.
+    pub fn element_protection_factor(&self, protection: i16) -> f32 {
+        let protection = protection.clamp(-100, 100) as f32;
+        1.0 - protection / 100.0
+    }
+
+    /// Damage of an attack before critical hit judgement
+    pub fn damage(&self, attack_power: f32, defence_power: f32, protection: i16) -> f32 {
+        attack_power / defence_power * self.element_protection_factor(protection)
+    }
+
+    pub fn evasion_power(&self, skill_level: u32, dex: u16) -> f32 {
+        let f = &self.formula;
+        f.evasion_equip + skill_level as f32 + dex as f32 * f.evasion_dex_factor
+    }
+
+    pub fn hit_probability(&self, hit_power: f32, evasion_power: f32) -> f32 {
+        let d = hit_power - evasion_power;
+        1.0 / (1.0 + (-d * self.formula.hit_slope).exp())
+    }
+
+    pub fn critical_probability(
+        &self,
+        dex: u16,
+        skill_level: u32,
+        weapon: Option<WeaponKind>,
+    ) -> f32 {
+        let c = &self.critical;
+        let weapon_chance = weapon
+            .and_then(|weapon| c.weapon.get(&weapon))
+            .map(|w| w.chance)
+            .unwrap_or(0.0);
+        let p = c.base
+            + dex as f32 * c.dex_factor
+            + skill_level as f32 * c.skill_factor
+            + weapon_chance;
+        p.clamp(0.0, c.max)
+    }
+
+    pub fn critical_multiplier(&self, weapon: Option<WeaponKind>) -> f32 {
+        let c = &self.critical;
+        let weapon_multiplier = weapon
+            .and_then(|weapon| c.weapon.get(&weapon))
+            .map(|w| w.multiplier)
+            .unwrap_or(1.0);
+        c.multiplier * weapon_multiplier
+    }
+}
@@ -0,0 +1,46 @@
+use crate::Rule;
+
+/// Rules for doors
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Door {
+    /// Door hp against wall damage
+    pub hp: u16,
+    /// The probability that a generated door is open
+    pub open_probability: f32,
+    /// The probability that a generated door is locked
+    pub locked_probability: f32,
+    /// Base lock level of generated doors
+    pub lock_level_base: u16,
+    /// Additional lock level per floor
+    pub lock_level_per_floor: u16,
+    /// Factor for the probability to pick locks
+    pub lockpicking_factor: f32,
+    /// Additional pathfinding cost to pass closed doors for npcs
+    pub path_cost: u32,
+    /// Base exp to Lockpicking skill
+    pub exp_lockpicking: u32,
+}
+
+impl Default for Door {
+    fn default() -> Self {
+        Door {
+            hp: 50,
+            open_probability: 0.3,
+            locked_probability: 0.1,
+            lock_level_base: 5,
+            lock_level_per_floor: 1,
+            lockpicking_factor: 1.0,
+            path_cost: 2,
+            exp_lockpicking: 50,
+        }
+    }
+}
+
+impl Rule for Door {
+    const NAME: &'static str = "door";
+
+    fn append(&mut self, other: Self) {
+        *self = other;
+    }
+}
@@ -32,6 +32,133 @@ pub struct DungeonGenParams {
     /// If underground, use downstairs tiles to the deeper floor, and the exit is upstairs tile.
     /// If not, upstairs tile is used to go to the deeper floor like towers.
     pub underground: bool,
+    /// Prefab rooms placed into generated floors
+    #[serde(default)]
+    pub vaults: Vec<VaultParams>,
+    /// Floor themes and their weight. If empty, floors have no theme.
+    #[serde(default)]
+    pub themes: Vec<(FloorTheme, f32)>,
+    /// Changes of floors by the elapsed time since the last visit
+    #[serde(default)]
+    pub aging: DungeonAging,
+}
+
+/// Changes of revisited dungeon floors. Rates are per day.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DungeonAging {
+    /// The number of npcs respawned per day
+    pub respawn_per_day: f32,
+    /// Npcs are not respawned over this number on a floor
+    pub max_npcs: u32,
+    /// The probability of each item on floors to decay per day
+    pub item_decay: f32,
+    /// The probability of each item on floors to be picked up by npcs per day
+    pub item_scavenge: f32,
+    /// Floors without npcs are reclaimed by a new faction after these days. Zero disables reclaiming.
+    pub reclaim_days: u32,
+    /// Factions reclaiming the dungeon and their weight
+    pub reclaim_factions: Vec<(FactionId, f32)>,
+}
+
+/// Theme of a floor which changes spawn tables and decos
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FloorTheme {
+    pub id: String,
+    /// The number of npcs generated on the floor
+    pub n_npc: u32,
+    /// Replaces npc_race_probability of the dungeon if not empty
+    pub npc_race_probability: HashMap<String, f32>,
+    /// Replaces item_gen_probability of the dungeon
+    pub item_gen_probability: Option<f64>,
+    /// Replaces item_gen_weight of the dungeon if not empty
+    pub item_gen_weight: Vec<(ItemSelector, f32)>,
+    /// Deco ids and probability to be placed on each floor tile
+    pub decos: Vec<(String, f32)>,
+    /// Special rooms and their weight
+    pub special_rooms: Vec<(SpecialRoom, f32)>,
+    /// The range of the number of special rooms
+    pub n_special_rooms: [u32; 2],
+}
+
+impl Default for FloorTheme {
+    fn default() -> Self {
+        FloorTheme {
+            id: String::new(),
+            n_npc: 10,
+            npc_race_probability: HashMap::default(),
+            item_gen_probability: None,
+            item_gen_weight: Vec::new(),
+            decos: Vec::new(),
+            special_rooms: Vec::new(),
+            n_special_rooms: [0, 1],
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum SpecialRoomKind {
+    TreasureVault,
+    MonsterNest,
+    Shrine,
+    Merchant,
+    /// Placed on the narrowest room
+    TrappedCorridor,
+}
+
+/// Special room placed in one of the rooms from room based map generators
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpecialRoom {
+    pub kind: SpecialRoomKind,
+    /// Npc races generated in the room. If empty, the races of the floor are used.
+    #[serde(default)]
+    pub npc_race_probability: HashMap<String, f32>,
+    /// The range of the number of npcs in the room
+    #[serde(default)]
+    pub n_npc: [u32; 2],
+    /// Item kinds generated in the room. If empty, the item kinds of the floor are used.
+    #[serde(default)]
+    pub item_gen_weight: Vec<(ItemSelector, f32)>,
+    /// The range of the number of items in the room
+    #[serde(default)]
+    pub n_item: [u32; 2],
+    /// Deco ids and probability to be placed on each tile in the room
+    #[serde(default)]
+    pub decos: Vec<(String, f32)>,
+    /// Character template id of the merchant
+    #[serde(default)]
+    pub merchant: String,
+    /// Shop kind of the merchant
+    #[serde(default)]
+    pub shop_kind: String,
+    /// Talk script id of the merchant
+    #[serde(default)]
+    pub talk_script: String,
+}
+
+/// Rule for a prefab room made as a map template
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VaultParams {
+    /// Map template id. Its entrances are used as connection points.
+    pub map_template: String,
+    /// The probability of placing this vault on each floor
+    pub prob: f32,
+    /// Vaults are not placed on floors shallower than this
+    #[serde(default)]
+    pub min_floor: u32,
+    /// Allow random rotation
+    #[serde(default)]
+    pub rotate: bool,
+    /// Allow random mirroring
+    #[serde(default)]
+    pub mirror: bool,
+    /// Item ids always generated in the vault
+    #[serde(default)]
+    pub treasure: Vec<String>,
+    /// Character template id always generated in the vault
+    #[serde(default)]
+    pub boss: Option<String>,
 }
 
 impl Rule for DungeonGen {
@@ -8,6 +8,8 @@ pub struct Effect {
     pub mining_power_factor: f32,
     pub mining_power_base: f32,
     pub recover_hp_factor: f32,
+    #[serde(default = "recover_mp_factor_default")]
+    pub recover_mp_factor: f32,
     pub throw_weight_to_power_factor: f32,
 }
 
@@ -18,3 +20,7 @@ impl Rule for Effect {
         *self = other;
     }
 }
+
+fn recover_mp_factor_default() -> f32 {
+    0.5
+}
@@ -27,6 +27,9 @@ pub struct Exp {
     pub mining: u32,
     /// Base exp for creation
     pub creation_base_exp: u32,
+    /// Base exp to Negotiation skill when trading at shops
+    #[serde(default = "negotiation_default")]
+    pub negotiation: u32,
 }
 
 impl Rule for Exp {
@@ -36,3 +39,7 @@ impl Rule for Exp {
         *self = other;
     }
 }
+
+fn negotiation_default() -> u32 {
+    10
+}
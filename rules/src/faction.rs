@@ -11,6 +11,18 @@ pub struct Faction {
     pub relvar_attacked: i16,
     pub relvar_killed: i16,
     pub factions: HashMap<FactionId, FactionInfo>,
+    /// Relation values between factions for each stance
+    #[serde(default)]
+    pub stance_relation: StanceParams<FactionRelation>,
+    /// Ratio of relation changes propagated to the factions that have a stance to the changed faction
+    #[serde(default)]
+    pub propagation: StanceParams<f32>,
+    /// Reputation ranks sorted by the threshold in ascending order
+    #[serde(default = "ranks_default")]
+    pub ranks: Vec<ReputationRank>,
+    /// Shop owners refuse to trade with the player if the relation is lower than this
+    #[serde(default = "relation_shop_refuse_default")]
+    pub relation_shop_refuse: FactionRelation,
 }
 
 impl Faction {
@@ -20,11 +32,48 @@ impl Faction {
             .unwrap_or(&self.factions[&FactionId::default()])
     }
 
+    /// Stance between two factions. Stances are looked up in both directions.
+    pub fn stance(&self, f1: FactionId, f2: FactionId) -> Option<FactionStance> {
+        self.factions
+            .get(&f1)
+            .and_then(|f| f.stances.get(&f2))
+            .or_else(|| self.factions.get(&f2).and_then(|f| f.stances.get(&f1)))
+            .copied()
+    }
+
     pub fn relation(&self, f1: FactionId, f2: FactionId) -> FactionRelation {
+        if let Some(stance) = self.stance(f1, f2) {
+            return *self.stance_relation.get(stance);
+        }
         let f1 = self.get(f1);
         let f2 = self.get(f2);
         std::cmp::min(f1.default_relation, f2.default_relation)
     }
+
+    /// Factions that have a stance to the given faction
+    pub fn stances(&self, faction: FactionId) -> Vec<(FactionId, FactionStance)> {
+        let mut stances: Vec<(FactionId, FactionStance)> = Vec::new();
+        for &other in self.factions.keys() {
+            if other == faction {
+                continue;
+            }
+            if let Some(stance) = self.stance(faction, other) {
+                stances.push((other, stance));
+            }
+        }
+        stances.sort();
+        stances
+    }
+
+    /// Reputation rank for the given relation
+    pub fn rank(&self, relation: FactionRelation) -> &ReputationRank {
+        self.ranks
+            .iter()
+            .rev()
+            .find(|rank| relation >= rank.threshold)
+            .or_else(|| self.ranks.first())
+            .expect("reputation ranks are empty")
+    }
 }
 
 /// Rules for character generation
@@ -33,6 +82,62 @@ pub struct FactionInfo {
     pub default_relation: FactionRelation,
     #[serde(default)]
     pub constant: bool,
+    /// Stances to other factions
+    #[serde(default)]
+    pub stances: HashMap<FactionId, FactionStance>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FactionStance {
+    Ally,
+    Rival,
+    War,
+}
+
+/// Parameters for each faction stance
+#[derive(Serialize, Deserialize)]
+pub struct StanceParams<T> {
+    pub ally: T,
+    pub rival: T,
+    pub war: T,
+}
+
+impl<T> StanceParams<T> {
+    pub fn get(&self, stance: FactionStance) -> &T {
+        match stance {
+            FactionStance::Ally => &self.ally,
+            FactionStance::Rival => &self.rival,
+            FactionStance::War => &self.war,
+        }
+    }
+}
+
+impl Default for StanceParams<FactionRelation> {
+    fn default() -> Self {
+        StanceParams {
+            ally: FactionRelation::new(5000),
+            rival: FactionRelation::new(-1000),
+            war: FactionRelation::new(-5000),
+        }
+    }
+}
+
+impl Default for StanceParams<f32> {
+    fn default() -> Self {
+        StanceParams {
+            ally: 0.5,
+            rival: -0.25,
+            war: -0.5,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReputationRank {
+    /// Text id is "reputation_rank-{id}"
+    pub id: String,
+    pub threshold: FactionRelation,
 }
 
 impl Rule for Faction {
@@ -42,3 +147,25 @@ impl Rule for Faction {
         *self = other;
     }
 }
+
+fn ranks_default() -> Vec<ReputationRank> {
+    [
+        ("hated", -10000),
+        ("hostile", -3000),
+        ("unfriendly", -1000),
+        ("neutral", -300),
+        ("friendly", 1000),
+        ("honored", 3000),
+        ("exalted", 6000),
+    ]
+    .iter()
+    .map(|&(id, threshold)| ReputationRank {
+        id: id.to_owned(),
+        threshold: FactionRelation::new(threshold),
+    })
+    .collect()
+}
+
+fn relation_shop_refuse_default() -> FactionRelation {
+    FactionRelation::new(-3000)
+}
@@ -14,6 +14,7 @@ extern crate rusted_ruins_geom as geom;
 extern crate rusted_ruins_map_generator as map_generator;
 
 pub mod active_skill;
+pub mod base;
 pub mod biome;
 pub mod chara;
 pub mod chara_trait;
@@ -21,11 +22,13 @@ pub mod charagen;
 pub mod class;
 pub mod combat;
 pub mod creation;
+pub mod door;
 pub mod dungeon_gen;
 pub mod effect;
 pub mod exp;
 pub mod faction;
 pub mod item;
+pub mod livestock;
 pub mod map_gen;
 pub mod material;
 pub mod newgame;
@@ -33,9 +36,15 @@ pub mod npc;
 pub mod npc_ai;
 pub mod params;
 pub mod quest;
+pub mod quest_template;
 pub mod race;
 pub mod recipe;
+pub mod region_gen;
+pub mod rest;
+pub mod status_effect;
+pub mod theft;
 pub mod town;
+pub mod trap;
 pub mod world;
 
 use anyhow::{anyhow, Context, Result};
@@ -49,6 +58,19 @@ trait Rule: DeserializeOwned {
     const NAME: &'static str;
 
     fn load<P: AsRef<Path>>(rule_dirs: &[P]) -> Result<Self> {
+        Self::load_optional(rule_dirs)?
+            .ok_or_else(|| anyhow!("rule file not found for \"{}\" rule", Self::NAME))
+    }
+
+    /// Load rule, or use the default value if there are no rule files.
+    fn load_or_default<P: AsRef<Path>>(rule_dirs: &[P]) -> Result<Self>
+    where
+        Self: Default,
+    {
+        Ok(Self::load_optional(rule_dirs)?.unwrap_or_default())
+    }
+
+    fn load_optional<P: AsRef<Path>>(rule_dirs: &[P]) -> Result<Option<Self>> {
         info!("loading rule \"{}\"", Self::NAME);
 
         let mut rule: Option<Self> = None;
@@ -83,7 +105,7 @@ trait Rule: DeserializeOwned {
             }
         }
 
-        rule.ok_or_else(|| anyhow!("rule file not found for \"{}\" rule", Self::NAME))
+        Ok(rule)
     }
 
     fn from_file(path: &Path) -> Result<Self> {
@@ -98,6 +120,7 @@ trait Rule: DeserializeOwned {
 /// Contain game rules
 pub struct Rules {
     pub active_skills: active_skill::ActiveSkills,
+    pub base: base::Base,
     pub biomes: biome::Biomes,
     pub chara: chara::Chara,
     pub chara_gen: charagen::CharaGen,
@@ -105,21 +128,29 @@ pub struct Rules {
     pub classes: class::Classes,
     pub combat: combat::Combat,
     pub creation: creation::Creation,
+    pub door: door::Door,
     pub dungeon_gen: dungeon_gen::DungeonGen,
     pub exp: exp::Exp,
     pub effect: effect::Effect,
     pub faction: faction::Faction,
     pub map_gen: map_gen::MapGen,
     pub item: item::Item,
+    pub livestock: livestock::Livestock,
     pub materials: material::Materials,
     pub newgame: newgame::NewGame,
     pub npc: npc::Npc,
     pub npc_ai: npc_ai::NpcAIs,
     pub params: params::Params,
     pub quest: quest::Quest,
+    pub quest_templates: quest_template::QuestTemplates,
     pub races: race::Races,
     pub recipes: recipe::Recipes,
+    pub region_gen: region_gen::RegionGen,
+    pub rest: rest::Rest,
+    pub status_effects: status_effect::StatusEffects,
+    pub theft: theft::Theft,
     pub town: town::Town,
+    pub trap: trap::Trap,
     pub world: world::World,
 }
 
@@ -133,12 +164,14 @@ impl Rules {
 
         Ok(Rules {
             active_skills: active_skill::ActiveSkills::load(&dirs)?,
+            base: base::Base::load_or_default(&dirs)?,
             biomes: biome::Biomes::load(&dirs)?,
             chara: chara::Chara::load(&dirs)?,
             chara_gen: charagen::CharaGen::load(&dirs)?,
             chara_traits: chara_trait::CharaTraits::load(&dirs)?,
             classes: class::Classes::load(&dirs)?,
             creation: creation::Creation::load(&dirs)?,
+            door: door::Door::load_or_default(&dirs)?,
             combat: combat::Combat::load(&dirs)?,
             dungeon_gen: dungeon_gen::DungeonGen::load(&dirs)?,
             effect: effect::Effect::load(&dirs)?,
@@ -146,15 +179,22 @@ impl Rules {
             faction: faction::Faction::load(&dirs)?,
             map_gen: map_gen::MapGen::load(&dirs)?,
             item: item::Item::load(&dirs)?,
+            livestock: livestock::Livestock::load_or_default(&dirs)?,
             materials: material::Materials::load(&dirs)?,
             newgame: newgame::NewGame::load(&dirs)?,
             npc: npc::Npc::load(&dirs)?,
             npc_ai: npc_ai::NpcAIs::load(&dirs)?,
             params: params::Params::load(&dirs)?,
             quest: quest::Quest::load(&dirs)?,
+            quest_templates: quest_template::QuestTemplates::load_or_default(&dirs)?,
             races: race::Races::load(&dirs)?,
             recipes: recipe::Recipes::load(&dirs)?,
+            region_gen: region_gen::RegionGen::load_or_default(&dirs)?,
+            rest: rest::Rest::load_or_default(&dirs)?,
+            status_effects: status_effect::StatusEffects::load_or_default(&dirs)?,
+            theft: theft::Theft::load_or_default(&dirs)?,
             town: town::Town::load(&dirs)?,
+            trap: trap::Trap::load_or_default(&dirs)?,
             world: world::World::load(&dirs)?,
         })
     }
@@ -0,0 +1,80 @@
+use super::Rule;
+use common::gamedata::Duration;
+use std::collections::HashMap;
+
+/// Rules for tameable creatures and livestock
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Livestock {
+    /// Tameable creatures. Keys are character template ids.
+    pub animals: HashMap<String, Animal>,
+    /// (taming probability) = (Animals skill level) * tame_factor / (creature level)
+    pub tame_factor: f32,
+    /// The maximum probability of taming
+    pub tame_prob_max: f32,
+    /// Tiles treated as pens at player bases
+    pub pen_tiles: Vec<String>,
+    /// Required pen tiles per animal for breeding
+    pub pen_tiles_per_animal: u32,
+    /// Duration until fed animals get hungry
+    pub food_duration: Duration,
+    /// Base exp to Animals skill for taming
+    pub exp_tame: u32,
+    /// Base exp to Animals skill for producing items
+    pub exp_product: u32,
+}
+
+impl Default for Livestock {
+    fn default() -> Self {
+        Livestock {
+            animals: HashMap::default(),
+            tame_factor: 0.1,
+            tame_prob_max: 0.9,
+            pen_tiles: Vec::new(),
+            pen_tiles_per_animal: 4,
+            food_duration: Duration::from_days(1),
+            exp_tame: 100,
+            exp_product: 10,
+        }
+    }
+}
+
+impl Rule for Livestock {
+    const NAME: &'static str = "livestock";
+
+    fn append(&mut self, other: Self) {
+        for (k, v) in other.animals.into_iter() {
+            self.animals.insert(k, v);
+        }
+        self.pen_tiles.extend(other.pen_tiles);
+    }
+}
+
+/// Rules for one tameable creature
+#[derive(Serialize, Deserialize)]
+pub struct Animal {
+    /// Item consumed by taming
+    pub tame_item: String,
+    /// Item ids this animal eats. Any food items if empty.
+    #[serde(default)]
+    pub foods: Vec<String>,
+    /// Items produced periodically and their numbers
+    #[serde(default)]
+    pub products: Vec<(String, u32)>,
+    #[serde(default = "product_duration_default")]
+    pub product_duration: Duration,
+    /// Interval of breeding chances
+    #[serde(default = "breed_duration_default")]
+    pub breed_duration: Duration,
+    /// Probability of breeding when there is a partner in pens
+    #[serde(default)]
+    pub breed_prob: f32,
+}
+
+fn product_duration_default() -> Duration {
+    Duration::from_days(1)
+}
+
+fn breed_duration_default() -> Duration {
+    Duration::from_days(7)
+}
@@ -1,10 +1,13 @@
 use crate::Rule;
 use common::hashmap::HashMap;
-use map_generator::MapGenParam;
+use map_generator::{MapGenParam, MapRequirements};
 
 #[derive(Serialize, Deserialize)]
 pub struct MapGen {
     pub map_gen_params: HashMap<String, MapGenParam>,
+    /// Requirements for generated maps. Maps are generated again if not satisfied.
+    #[serde(default)]
+    pub requirements: HashMap<String, MapRequirements>,
 }
 
 impl Rule for MapGen {
@@ -14,5 +17,8 @@ impl Rule for MapGen {
         for (k, v) in other.map_gen_params.into_iter() {
             self.map_gen_params.insert(k, v);
         }
+        for (k, v) in other.requirements.into_iter() {
+            self.requirements.insert(k, v);
+        }
     }
 }
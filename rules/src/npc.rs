@@ -8,6 +8,9 @@ pub struct Npc {
     /// The maximum size of player's party
     pub party_size_max: u32,
     pub party_pathfinding_step: u32,
+    /// Parameters for hired party members
+    #[serde(default)]
+    pub hire: Hire,
 }
 
 impl Rule for Npc {
@@ -17,3 +20,38 @@ impl Rule for Npc {
         *self = other;
     }
 }
+
+/// Parameters for hired party members.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Hire {
+    /// (daily wage) = wage_base + wage_per_level * (level)
+    pub wage_base: i64,
+    pub wage_per_level: i64,
+    /// Loyalty when hired
+    pub loyalty_initial: u32,
+    pub loyalty_max: u32,
+    /// Loyalty increase when the wage is paid
+    pub loyalty_paid: u32,
+    /// Loyalty decrease when the wage is not paid
+    pub loyalty_unpaid: u32,
+}
+
+impl Default for Hire {
+    fn default() -> Self {
+        Hire {
+            wage_base: 20,
+            wage_per_level: 10,
+            loyalty_initial: 50,
+            loyalty_max: 100,
+            loyalty_paid: 5,
+            loyalty_unpaid: 20,
+        }
+    }
+}
+
+impl Hire {
+    pub fn wage(&self, lv: u32) -> i64 {
+        self.wage_base + self.wage_per_level * lv as i64
+    }
+}
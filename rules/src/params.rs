@@ -12,6 +12,9 @@ pub struct Params {
     /// Skill bonus (base * value.0 + value.1).
     pub skill_bonus: HashMap<BonusLevel, (f32, i32)>,
     pub magic_device_base_power: f32,
+    /// Maps not visited for these days are aged when the player enters them
+    #[serde(default = "map_aging_days_default")]
+    pub map_aging_days: u32,
 }
 
 impl Rule for Params {
@@ -21,3 +24,7 @@ impl Rule for Params {
         *self = other;
     }
 }
+
+fn map_aging_days_default() -> u32 {
+    7
+}
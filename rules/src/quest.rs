@@ -6,6 +6,12 @@ use std::collections::HashMap;
 pub struct Quest {
     /// The probability of choose npc for monster slaying quest
     pub slay_race_probability: HashMap<String, f32>,
+    /// The number of quests offered in a town
+    #[serde(default = "n_town_quests_default")]
+    pub n_town_quests: u32,
+    /// Reward money is multiplied by (1 + (player level) * reward_level_factor)
+    #[serde(default = "reward_level_factor_default")]
+    pub reward_level_factor: f32,
 }
 
 impl Rule for Quest {
@@ -15,3 +21,11 @@ impl Rule for Quest {
         *self = other;
     }
 }
+
+fn n_town_quests_default() -> u32 {
+    3
+}
+
+fn reward_level_factor_default() -> f32 {
+    0.1
+}
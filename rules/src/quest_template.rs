@@ -0,0 +1,98 @@
+use super::Rule;
+use common::gamedata::{FactionId, FactionRelation};
+use std::collections::HashMap;
+
+/// Quest templates defined by rule files. Keys are used as the id of quests.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct QuestTemplates(HashMap<String, QuestTemplate>);
+
+impl QuestTemplates {
+    pub fn get(&self, id: &str) -> Option<&QuestTemplate> {
+        self.0.get(id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &QuestTemplate)> {
+        self.0.iter()
+    }
+}
+
+impl Default for QuestTemplates {
+    fn default() -> Self {
+        let slay_monsters = QuestTemplate {
+            kind: QuestKindTemplate::SlayMonsters { goal: 10 },
+            reward: 1000,
+            reward_items: Vec::new(),
+            deadline_days: None,
+            weight: 1.0,
+            min_level: 0,
+            towns: Vec::new(),
+            faction: None,
+            min_relation: None,
+            reward_relation: 0,
+        };
+        QuestTemplates(
+            vec![("slay_monsters".to_owned(), slay_monsters)]
+                .into_iter()
+                .collect(),
+        )
+    }
+}
+
+impl Rule for QuestTemplates {
+    const NAME: &'static str = "quest_templates";
+
+    fn append(&mut self, other: Self) {
+        for (k, v) in other.0.into_iter() {
+            self.0.insert(k, v);
+        }
+    }
+}
+
+/// Rules to generate a quest
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuestTemplate {
+    pub kind: QuestKindTemplate,
+    /// Base reward money
+    pub reward: i64,
+    /// Item ids given as the reward
+    #[serde(default)]
+    pub reward_items: Vec<String>,
+    /// Days until the deadline. No deadline if None.
+    #[serde(default)]
+    pub deadline_days: Option<u32>,
+    /// Weight to choose this template
+    #[serde(default = "weight_default")]
+    pub weight: f32,
+    /// The minimum player level to offer this quest
+    #[serde(default)]
+    pub min_level: u32,
+    /// Town ids that offer this quest. Offered in all towns if empty.
+    #[serde(default)]
+    pub towns: Vec<String>,
+    /// Offered only if the player's relation to this faction is min_relation or higher
+    #[serde(default)]
+    pub faction: Option<FactionId>,
+    /// relation_friend is used if None
+    #[serde(default)]
+    pub min_relation: Option<FactionRelation>,
+    /// Relation change to the faction when this quest is completed
+    #[serde(default)]
+    pub reward_relation: i16,
+}
+
+/// Quest kind specific parameters. Item and character ids are chosen randomly from the lists.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum QuestKindTemplate {
+    SlayMonsters { goal: u32 },
+    Fetch { items: Vec<String>, n: u32 },
+    Deliver { items: Vec<String>, n: u32 },
+    Escort { charas: Vec<String> },
+    Explore { floor: u32 },
+    Retrieve { items: Vec<String>, floor: u32 },
+    Craft { items: Vec<String>, n: u32 },
+}
+
+fn weight_default() -> f32 {
+    1.0
+}
@@ -0,0 +1,128 @@
+use crate::Rule;
+use common::gamedata::{DungeonKind, FactionId};
+use common::gobj::ObjIdxAsId;
+use common::objholder::*;
+use geom::Vec2d;
+use map_generator::world::{Terrain, WorldGenParam};
+use serde_with::serde_as;
+use std::collections::HashMap;
+
+/// Rules for procedural region generation
+#[serde_as]
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RegionGen {
+    /// Procedurally generated regions. Regions not listed here are created from map templates.
+    pub regions: HashMap<String, ProceduralRegion>,
+    /// Tile and wall for each terrain
+    pub terrain: HashMap<Terrain, TerrainTile>,
+    /// Tile overlaid on rivers
+    #[serde_as(as = "Option<ObjIdxAsId>")]
+    pub river_tile: Option<TileIdx>,
+    /// Tile overlaid on roads
+    #[serde_as(as = "Option<ObjIdxAsId>")]
+    pub road_tile: Option<TileIdx>,
+    /// Terrains and their weight to place dungeons of each kind.
+    /// Dungeons not listed here are placed on any empty tile.
+    pub dungeon_affinity: HashMap<DungeonKind, Vec<(Terrain, f32)>>,
+    /// Regions generated for a new game in addition to the start region
+    pub other_regions: Vec<String>,
+    /// Routes between regions
+    pub links: Vec<RegionLink>,
+    /// Parameters for each region
+    pub params: HashMap<String, RegionParams>,
+}
+
+impl Rule for RegionGen {
+    const NAME: &'static str = "region_gen";
+
+    fn append(&mut self, other: Self) {
+        self.regions.extend(other.regions);
+        self.terrain.extend(other.terrain);
+        if other.river_tile.is_some() {
+            self.river_tile = other.river_tile;
+        }
+        if other.road_tile.is_some() {
+            self.road_tile = other.road_tile;
+        }
+        self.dungeon_affinity.extend(other.dungeon_affinity);
+        self.other_regions.extend(other.other_regions);
+        self.links.extend(other.links);
+        self.params.extend(other.params);
+    }
+}
+
+impl RegionGen {
+    /// Get the terrain from the base tile of a region map
+    pub fn terrain_by_tile(&self, tile: TileIdx) -> Option<Terrain> {
+        self.terrain
+            .iter()
+            .find(|(_, terrain_tile)| terrain_tile.tile == tile)
+            .map(|(terrain, _)| *terrain)
+    }
+
+    /// Get the link between given regions
+    pub fn link(&self, a: &str, b: &str) -> Option<&RegionLink> {
+        self.links
+            .iter()
+            .find(|link| link.regions == [a, b] || link.regions == [b, a])
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ProceduralRegion {
+    /// `n_towns` is replaced by the number of towns
+    pub world: WorldGenParam,
+    /// Regions are generated from a random seed if not specified
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Site gen ids of towns. The player starts from the first town.
+    pub towns: Vec<String>,
+    /// Site gen ids of other sites, and terrains they are placed on
+    #[serde(default)]
+    pub others: Vec<(String, Vec<Terrain>)>,
+    #[serde(default)]
+    pub music: String,
+}
+
+#[serde_as]
+#[derive(Serialize, Deserialize)]
+pub struct TerrainTile {
+    #[serde_as(as = "ObjIdxAsId")]
+    pub tile: TileIdx,
+    #[serde_as(as = "Option<ObjIdxAsId>")]
+    #[serde(default)]
+    pub wall: Option<WallIdx>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum RegionLinkKind {
+    /// Ports placed on coasts. Sailing takes money and time.
+    Ship,
+    Portal,
+}
+
+/// A route between two regions
+#[derive(Serialize, Deserialize)]
+pub struct RegionLink {
+    pub kind: RegionLinkKind,
+    pub regions: [String; 2],
+    /// Positions on each region map. Chosen randomly if not specified.
+    #[serde(default)]
+    pub pos: [Option<Vec2d>; 2],
+    #[serde(default)]
+    pub fare: u32,
+    /// Travel time in hours
+    #[serde(default)]
+    pub hours: u32,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RegionParams {
+    /// Dungeon kinds generated in the region and their weight.
+    /// If empty, all dungeon kinds are generated.
+    pub dungeon_kinds: Vec<(DungeonKind, f32)>,
+    /// Replaces the default faction of dungeons in the region
+    pub dungeon_factions: HashMap<DungeonKind, FactionId>,
+}
@@ -0,0 +1,43 @@
+use super::Rule;
+
+/// Rules for resting, waiting and sleeping
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rest {
+    /// The maximum turns for resting until healed or sleeping
+    pub max_turns: u16,
+    /// Game time passes faster by this factor while sleeping
+    pub time_factor_sleep: f32,
+    /// Hour to wake up in the morning
+    pub wake_up_hour: u32,
+    /// Sp consumption factor while sleeping
+    pub sp_consumption_factor_sleep: f32,
+    /// HP and MP regeneration factor while resting
+    pub regeneration_factor_rest: f32,
+    /// HP and MP regeneration factor while sleeping
+    pub regeneration_factor_sleep: f32,
+    /// Additional regeneration factor per bed quality
+    pub regeneration_factor_per_bed_quality: f32,
+}
+
+impl Default for Rest {
+    fn default() -> Self {
+        Rest {
+            max_turns: 5000,
+            time_factor_sleep: 5.0,
+            wake_up_hour: 7,
+            sp_consumption_factor_sleep: 0.5,
+            regeneration_factor_rest: 1.5,
+            regeneration_factor_sleep: 2.0,
+            regeneration_factor_per_bed_quality: 0.5,
+        }
+    }
+}
+
+impl Rule for Rest {
+    const NAME: &'static str = "rest";
+
+    fn append(&mut self, other: Self) {
+        *self = other;
+    }
+}
@@ -0,0 +1,88 @@
+use super::Rule;
+use common::gamedata::CharaModifier;
+use std::collections::HashMap;
+
+/// Status effects defined by rule files. Keys are used as the id of status effects.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct StatusEffects(HashMap<String, StatusEffect>);
+
+impl StatusEffects {
+    pub fn get(&self, id: &str) -> Option<&StatusEffect> {
+        self.0.get(id)
+    }
+}
+
+impl Rule for StatusEffects {
+    const NAME: &'static str = "status_effects";
+
+    fn append(&mut self, other: Self) {
+        for (k, v) in other.0.into_iter() {
+            self.0.insert(k, v);
+        }
+    }
+}
+
+/// Rules for one status effect
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatusEffect {
+    /// UI image id shown with the status name
+    #[serde(default)]
+    pub icon: String,
+    /// Base duration in turns. If zero, the status continues until cured.
+    #[serde(default)]
+    pub duration: u16,
+    /// Additional duration per effect power
+    #[serde(default)]
+    pub duration_per_power: f32,
+    #[serde(default)]
+    pub stacking: StatusStacking,
+    /// Modifiers applied to the character per stack
+    #[serde(default)]
+    pub modifiers: Vec<CharaModifier>,
+    /// Processed at the beginning of the character's turn
+    #[serde(default)]
+    pub per_turn: Vec<StatusTurnEffect>,
+    #[serde(default)]
+    pub cure: Vec<StatusCure>,
+    /// The character cannot act
+    #[serde(default)]
+    pub prevent_action: bool,
+    /// Probability of moving to a random direction
+    #[serde(default)]
+    pub random_move: f32,
+}
+
+/// How to process a status effect given to a character that already has it
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum StatusStacking {
+    /// New one is ignored
+    Ignore,
+    /// Duration is reset to the longer one
+    #[default]
+    Refresh,
+    /// Duration is added
+    Extend,
+    /// Increase the number of stacks up to max, and refresh duration
+    Stack { max: u16 },
+}
+
+/// Per turn effect of a status. Values are multiplied by the number of stacks.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum StatusTurnEffect {
+    /// Damage by the ratio to max HP
+    Damage(f32),
+    /// Heal by the ratio to max HP
+    Heal(f32),
+    /// Sp change
+    Sp(f32),
+}
+
+/// Condition to remove a status before its expiration
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum StatusCure {
+    /// Cured when the character is damaged
+    Damaged,
+    /// Cured by this probability every turn
+    Chance(f32),
+}
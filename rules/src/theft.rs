@@ -0,0 +1,34 @@
+use crate::Rule;
+
+/// Rules for stealing items owned by other factions
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theft {
+    /// Factor for the probability that an observer notices a theft
+    pub detection_factor: f32,
+    /// Relation change to the owner faction when a theft is witnessed
+    pub relvar_witnessed: i16,
+    /// Fences pay this ratio of the normal selling price for stolen items
+    pub fence_price_ratio: f32,
+    /// Base exp to Conceal skill for an unnoticed theft
+    pub exp: u32,
+}
+
+impl Default for Theft {
+    fn default() -> Self {
+        Theft {
+            detection_factor: 1.0,
+            relvar_witnessed: -500,
+            fence_price_ratio: 0.5,
+            exp: 50,
+        }
+    }
+}
+
+impl Rule for Theft {
+    const NAME: &'static str = "theft";
+
+    fn append(&mut self, other: Self) {
+        *self = other;
+    }
+}
@@ -11,6 +11,99 @@ pub struct Town {
     pub max_shop_items: u32,
     /// Shop kinds and its item selectors.
     pub shop_kinds: HashMap<String, ItemSelector>,
+    /// Reward money factor of quests offered in each town
+    #[serde(default)]
+    pub quest_reward_factor: HashMap<String, f32>,
+    /// Interval days of shop restocking
+    #[serde(default = "restock_days_default")]
+    pub restock_days: u32,
+    /// Interval days of quest board refreshing
+    #[serde(default = "quest_update_days_default")]
+    pub quest_update_days: u32,
+    /// Economy growth points given every day
+    #[serde(default = "economy_growth_per_day_default")]
+    pub economy_growth_per_day: u32,
+    /// Economy growth points per money the player spends at shops
+    #[serde(default = "economy_trade_factor_default")]
+    pub economy_trade_factor: f32,
+    /// Growth points needed for the next level are (economy_exp_per_level) * (next level)
+    #[serde(default = "economy_exp_per_level_default")]
+    pub economy_exp_per_level: u32,
+    #[serde(default = "economy_max_level_default")]
+    pub economy_max_level: u32,
+    /// Additional shop level per economy level
+    #[serde(default = "economy_shop_level_default")]
+    pub economy_shop_level: u32,
+    /// Additional base quality of shop items per economy level
+    #[serde(default = "economy_item_quality_default")]
+    pub economy_item_quality: f32,
+    /// Parameters for shop prices
+    #[serde(default)]
+    pub price: ShopPrice,
+}
+
+/// Parameters for shop price calculation.
+/// Each adjustment is a ratio. Positive values are favorable to the player.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShopPrice {
+    /// Adjustment per player's CHA
+    pub cha_factor: f32,
+    /// Adjustment per player's Negotiation skill level
+    pub skill_factor: f32,
+    /// Adjustment per faction relation point between the player and the shop owner
+    pub relation_factor: f32,
+    /// Adjustment per supply point of the item group
+    pub supply_factor: f32,
+    /// Change of supply points per traded item
+    pub supply_per_trade: i32,
+    /// Maximum absolute value of supply points
+    pub supply_max: i32,
+    /// Supply points are multiplied by this value on restock
+    pub supply_restock_decay: f32,
+    /// Maximum absolute value of the total adjustment
+    pub max_adjustment: f32,
+    /// Shop funds on restock are funds_base + funds_per_level * (shop level)
+    pub funds_base: i64,
+    pub funds_per_level: i64,
+}
+
+impl Default for ShopPrice {
+    fn default() -> Self {
+        ShopPrice {
+            cha_factor: 0.004,
+            skill_factor: 0.01,
+            relation_factor: 0.00002,
+            supply_factor: 0.03,
+            supply_per_trade: 1,
+            supply_max: 10,
+            supply_restock_decay: 0.5,
+            max_adjustment: 0.4,
+            funds_base: 1000,
+            funds_per_level: 500,
+        }
+    }
+}
+
+impl ShopPrice {
+    /// Clamp the total adjustment and apply it to the base price
+    pub fn apply(&self, base: i64, adjustment: f32, buying: bool) -> i64 {
+        let adjustment = adjustment.clamp(-self.max_adjustment, self.max_adjustment);
+        let factor = if buying {
+            1.0 - adjustment
+        } else {
+            1.0 + adjustment
+        };
+        (base as f32 * factor) as i64
+    }
+
+    /// Selling price is kept lower than the buying price of the same item,
+    /// so that buying an item and selling it back never makes money.
+    pub fn apply_selling(&self, base: i64, adjustment: f32, buying_price: i64) -> i64 {
+        self.apply(base, adjustment, false)
+            .min(buying_price - 1)
+            .max(0)
+    }
 }
 
 impl Rule for Town {
@@ -20,3 +113,67 @@ impl Rule for Town {
         *self = other;
     }
 }
+
+fn restock_days_default() -> u32 {
+    7
+}
+
+fn quest_update_days_default() -> u32 {
+    1
+}
+
+fn economy_growth_per_day_default() -> u32 {
+    1
+}
+
+fn economy_trade_factor_default() -> f32 {
+    0.01
+}
+
+fn economy_exp_per_level_default() -> u32 {
+    100
+}
+
+fn economy_max_level_default() -> u32 {
+    10
+}
+
+fn economy_shop_level_default() -> u32 {
+    2
+}
+
+fn economy_item_quality_default() -> f32 {
+    0.5
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn selling_price_is_lower_than_buying_price() {
+        let price = ShopPrice::default();
+        let adjustments: Vec<f32> = (-20..=20).map(|i| i as f32 * 0.05).collect();
+
+        for base in [2, 10, 99, 100, 1234, 100000] {
+            for &buying_adjustment in &adjustments {
+                let buying = price.apply(base, buying_adjustment, true);
+                for &selling_adjustment in &adjustments {
+                    // Selling base price is not higher than the buying base price
+                    for selling_base in [base / 2, base] {
+                        let selling = price.apply_selling(selling_base, selling_adjustment, buying);
+                        assert!(
+                            selling < buying,
+                            "base {}, adjustment {}/{}: sell {} >= buy {}",
+                            base,
+                            buying_adjustment,
+                            selling_adjustment,
+                            selling,
+                            buying
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
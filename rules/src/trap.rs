@@ -0,0 +1,77 @@
+use crate::Rule;
+use common::gamedata::TrapKind;
+
+/// Rules for traps and hidden features
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Trap {
+    /// Trap kinds and their weight used for generation
+    pub kinds: Vec<(TrapKind, f32)>,
+    /// The probability of trap generation on each floor tile of dungeons
+    pub gen_probability: f32,
+    /// The probability of trap generation on each tile of trapped corridors
+    pub gen_probability_trapped_corridor: f32,
+    /// The range of the number of secret passages on each dungeon floor
+    pub n_secret_passages: [u32; 2],
+    /// The probability that a revealed trap is triggered
+    pub trigger_prob_revealed: f32,
+    /// Base difficulty to find or disarm traps
+    pub difficulty_base: f32,
+    /// Additional difficulty per floor level
+    pub difficulty_per_floor: f32,
+    /// Range of the search action
+    pub search_range: i32,
+    /// Factor for the probability to find hidden features nearby when moving
+    pub passive_detection_factor: f32,
+    /// Factor for the probability to disarm traps
+    pub disarm_factor: f32,
+    /// The probability that a trap is triggered when disarming fails
+    pub disarm_fail_trigger_prob: f32,
+    /// Base damage of damage traps
+    pub damage_base: f32,
+    /// Additional damage per floor level
+    pub damage_per_floor: f32,
+    /// Damage by falling into pits
+    pub pit_damage: f32,
+    /// Base exp to Detection skill when finding hidden features
+    pub exp_detect: u32,
+    /// Base exp to Lockpicking skill when disarming traps
+    pub exp_disarm: u32,
+}
+
+impl Default for Trap {
+    fn default() -> Self {
+        Trap {
+            kinds: vec![
+                (TrapKind::Damage, 1.0),
+                (TrapKind::Poison, 0.6),
+                (TrapKind::Teleport, 0.4),
+                (TrapKind::Alarm, 0.4),
+                (TrapKind::Pit, 0.2),
+            ],
+            gen_probability: 0.002,
+            gen_probability_trapped_corridor: 0.2,
+            n_secret_passages: [0, 2],
+            trigger_prob_revealed: 0.2,
+            difficulty_base: 5.0,
+            difficulty_per_floor: 1.0,
+            search_range: 2,
+            passive_detection_factor: 0.2,
+            disarm_factor: 1.0,
+            disarm_fail_trigger_prob: 0.3,
+            damage_base: 5.0,
+            damage_per_floor: 2.0,
+            pit_damage: 3.0,
+            exp_detect: 30,
+            exp_disarm: 50,
+        }
+    }
+}
+
+impl Rule for Trap {
+    const NAME: &'static str = "trap";
+
+    fn append(&mut self, other: Self) {
+        *self = other;
+    }
+}
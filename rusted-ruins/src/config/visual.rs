@@ -85,6 +85,7 @@ pub struct UiColorConfig {
     pub button_normal_bg_covered: CfgColor,
     pub button_normal_border_dark: CfgColor,
     pub button_normal_border_light: CfgColor,
+    pub disabled_font: CfgColor,
     pub gauge_bg: CfgColor,
     pub gauge_border_dark: CfgColor,
     pub gauge_border_light: CfgColor,
@@ -298,6 +299,7 @@ pub struct StatusWindowConfig {
     pub lv_label_rect: CfgRect,
     pub hp_label_rect: CfgRect,
     pub sp_label_rect: CfgRect,
+    pub mp_label_rect: CfgRect,
     pub str_label_rect: CfgRect,
     pub vit_label_rect: CfgRect,
     pub dex_label_rect: CfgRect,
@@ -307,6 +309,8 @@ pub struct StatusWindowConfig {
     pub spd_label_rect: CfgRect,
     pub carry_label_rect: CfgRect,
     pub travel_speed_label_rect: CfgRect,
+    pub element_protection_label_rect: CfgRect,
+    pub element_protection_label_h: i32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -20,6 +20,7 @@ const CHARA_DRAW_OFFSET: i32 = 16;
 #[derive(Clone, Debug)]
 pub struct TargetModeDrawInfo {
     pub range: Shape,
+    pub effect: Effect,
 }
 
 pub struct MainWinDrawer {
@@ -91,7 +92,7 @@ impl MainWinDrawer {
 
         // Draw target mode UI
         if let Some(target_mode) = target_mode {
-            self.draw_target_mode(context, game, target_mode);
+            self.draw_target_mode(context, game, target_mode, hover_tile);
         }
 
         if let Some(t) = hover_tile {
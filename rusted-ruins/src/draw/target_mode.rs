@@ -1,6 +1,6 @@
 use super::mainwin::{MainWinDrawer, TargetModeDrawInfo};
 use crate::context::*;
-use crate::game::Game;
+use crate::game::{Game, InfoGetter};
 use common::gobj;
 use common::objholder::UiImgIdx;
 use geom::*;
@@ -15,6 +15,7 @@ impl MainWinDrawer {
         context: &mut Context<'_, '_, '_, '_>,
         game: &Game<'_>,
         target_mode: &TargetModeDrawInfo,
+        hover_tile: Option<Vec2d>,
     ) {
         let map = game.gd.get_current_map();
 
@@ -24,6 +25,29 @@ impl MainWinDrawer {
             }
             self.draw_target_mode_to_tile(context, p, game, target_mode);
         }
+
+        // Highlight the area of the effect if the cursor is on a selectable tile
+        if let Some(hover_tile) = hover_tile {
+            if !target_mode.range.is_inside(hover_tile)
+                || !map.is_inside(hover_tile)
+                || !game.view_map.get_tile_visible(hover_tile)
+            {
+                return;
+            }
+            let area = crate::game::effect::effect_area(
+                map,
+                &target_mode.effect,
+                hover_tile,
+                Some(game.gd.player_pos()),
+            );
+            for p in area {
+                if !game.view_map.get_tile_visible(p) {
+                    continue;
+                }
+                let tile_rect = self.tile_rect(p, 0, 0);
+                context.render_tex(*TILE_RANGE_HIGHLIBHT, tile_rect);
+            }
+        }
     }
 
     fn draw_target_mode_to_tile(
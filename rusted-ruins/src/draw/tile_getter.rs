@@ -36,10 +36,10 @@ impl<'a> BackgroundDrawInfo<'a> {
         di.tile = tile;
 
         if map.is_inside(pos) {
-            if let Some(special_tile_id) = map.observed_tile[pos].special.obj_id() {
-                let special_tile_obj: &'static SpecialTileObject = gobj::get_by_id(special_tile_id);
+            if let Some((special_tile_obj, special_tile_idx)) =
+                observed_special(&map.observed_tile[pos])
+            {
                 if special_tile_obj.always_background {
-                    let special_tile_idx: SpecialTileIdx = gobj::id_to_idx(special_tile_id);
                     di.special = Some(special_tile_idx);
                 }
             }
@@ -66,10 +66,10 @@ impl<'a> ForegroundDrawInfo<'a> {
         let mut di = ForegroundDrawInfo::default();
 
         if map.is_inside(pos) {
-            if let Some(special_tile_id) = map.observed_tile[pos].special.obj_id() {
-                let special_tile_obj: &'static SpecialTileObject = gobj::get_by_id(special_tile_id);
+            if let Some((special_tile_obj, special_tile_idx)) =
+                observed_special(&map.observed_tile[pos])
+            {
                 if !special_tile_obj.always_background {
-                    let special_tile_idx: SpecialTileIdx = gobj::id_to_idx(special_tile_id);
                     di.special = Some(special_tile_idx);
                 }
             }
@@ -106,6 +106,20 @@ impl<'a> ForegroundDrawInfo<'a> {
     }
 }
 
+/// Special tile object of the observed tile. Doors are drawn as special tiles.
+/// Returns None if the object is not loaded.
+fn observed_special(
+    tinfo: &ObservedTileInfo,
+) -> Option<(&'static SpecialTileObject, SpecialTileIdx)> {
+    let id = tinfo
+        .special
+        .obj_id()
+        .or_else(|| tinfo.door.map(|state| state.obj_id()))?;
+    // Missing objects are skipped silently because this is called for every drawn tile
+    let idx = SpecialTileIdx::search_idx(id, gobj::get_objholder())?;
+    Some((gobj::get_obj(idx), idx))
+}
+
 /// Adjust piece pattern when getting piece pattern from the nearest tile.
 fn adjust_pattern_from_nearest(pp: &mut PiecePattern, _pos: Vec2d, _nearest_pos: Vec2d) {
     *pp = PiecePattern::SURROUNDED;
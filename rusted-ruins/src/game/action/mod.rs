@@ -20,12 +20,37 @@ pub fn try_move(game: &mut Game<'_>, cid: CharaId, dir: Direction) -> bool {
         return true;
     }
 
+    // Confused characters move to a random direction
+    let random_move = game
+        .gd
+        .chara
+        .get(cid)
+        .status
+        .iter()
+        .filter_map(|s| s.status_effect())
+        .map(|status_effect| status_effect.random_move)
+        .fold(0.0, f32::max);
+    let dir = if random_move > 0.0 && rng::gen_bool(random_move.min(1.0)) {
+        use rng::SliceRandom;
+        *Direction::EIGHT_DIRS.choose(&mut rng::get_rng()).unwrap()
+    } else {
+        dir
+    };
+
     if cid == CharaId::Player {
         super::time::set_player_moved();
     }
 
     let dest_tile = game.gd.get_current_map().chara_pos(cid).unwrap() + dir.as_vec();
 
+    // Closed doors are opened instead of moving
+    let map = game.gd.get_current_map();
+    if map.is_inside(dest_tile)
+        && matches!(map.tile[dest_tile].door, Some(door) if door.state.is_closed())
+    {
+        return super::door::try_open(game, cid, dest_tile);
+    }
+
     if !game
         .gd
         .get_current_map()
@@ -46,6 +71,9 @@ pub fn try_move(game: &mut Game<'_>, cid: CharaId, dir: Direction) -> bool {
                 if cid == CharaId::Player {
                     game.anim_queue.push_player_move(dir);
                 }
+                if game.gd.chara_pos(cid) == Some(dest_tile) {
+                    after_move(game, cid, dest_tile);
+                }
             }
             Relationship::Hostile => {
                 melee_attack(game, cid, other_chara);
@@ -56,11 +84,20 @@ pub fn try_move(game: &mut Game<'_>, cid: CharaId, dir: Direction) -> bool {
         if cid == CharaId::Player {
             game.anim_queue.push_player_move(dir);
         }
+        after_move(game, cid, dest_tile);
     }
 
     true
 }
 
+/// Trigger traps, and the player may notice hidden features nearby
+fn after_move(game: &mut Game<'_>, cid: CharaId, pos: Vec2d) {
+    super::trap::step_on(game, cid, pos);
+    if cid == CharaId::Player {
+        super::trap::search(game, 1, RULES.trap.passive_detection_factor);
+    }
+}
+
 /// Melee attack
 pub fn melee_attack(game: &mut Game<'_>, cid: CharaId, target: CharaId) {
     use crate::game::chara::power::*;
@@ -65,6 +65,7 @@ fn use_deed(game: &mut Game<'_>) -> bool {
     let mut site = Site::new(1, None);
     site.content = SiteContent::Player {
         kind: PlayerBaseKind::Normal,
+        base: Box::default(),
     };
     let rid = mapid.rid();
     let sid = gd.add_site(site, SiteKind::Player, rid, Some(pos)).unwrap();
@@ -1,20 +1,25 @@
 use super::effect::do_effect;
 use super::extrait::*;
+use super::target::Target;
 use super::{Game, InfoGetter};
 use crate::text::ToText;
 use common::gamedata::*;
 use ordered_float::NotNan;
+use rng::gen_bool;
 use rules::RULES;
 
-/// Return true if success.
-pub fn use_active_skill(
+/// Return true if the character used its turn.
+pub fn use_active_skill<T: Into<Target>>(
     game: &mut Game<'_>,
     active_skill_id: &ActiveSkillId,
     cid: CharaId,
-    target: CharaId,
+    target: T,
 ) -> bool {
-    if !game.gd.target_visible(cid, target) {
-        return false;
+    let target = target.into();
+    if let Target::Chara(target) = target {
+        if !game.gd.target_visible(cid, target) {
+            return false;
+        }
     }
 
     let active_skill = if let Some(active_skill) = RULES.active_skills.get(active_skill_id) {
@@ -25,13 +30,26 @@ pub fn use_active_skill(
     };
 
     let chara = game.gd.chara.get(cid);
-    if !chara.active_skill_available(active_skill) {
+    if !chara.active_skill_available(active_skill_id, active_skill) {
         return false;
     }
 
     let power =
         crate::game::active_skill::calc_power(&game.gd, active_skill, cid) * active_skill.power;
     let hit_power = active_skill.hit_power;
+    let failure_probability = chara.active_skill_failure_probability(active_skill);
+
+    // Consume costs. Enough SP and MP are checked by active_skill_available()
+    let chara = game.gd.chara.get_mut(cid);
+    if active_skill.cost_sp > 0 {
+        chara.sub_sp(active_skill.cost_sp as f32, cid);
+    }
+    chara.mp -= active_skill.cost_mp as i32;
+    if active_skill.cooldown > 0 {
+        chara
+            .active_skill_cooldowns
+            .insert(active_skill_id.clone(), active_skill.cooldown);
+    }
 
     let chara = game.gd.chara.get(cid);
     trace!(
@@ -51,6 +69,11 @@ pub fn use_active_skill(
         }
     }
 
+    if failure_probability > 0.0 && gen_bool(failure_probability) {
+        game_log!("active-skill-failed"; chara=chara, active_skill=active_skill_id);
+        return true;
+    }
+
     do_effect(
         game,
         &active_skill.effect,
@@ -62,6 +85,53 @@ pub fn use_active_skill(
     true
 }
 
+/// Result of reading a spellbook
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LearnActiveSkillResult {
+    Success,
+    AlreadyLearned,
+    NotSpellbook,
+}
+
+/// Learn an active skill from a readable item whose title is "active_skill:<id>".
+pub fn learn_active_skill(gd: &mut GameData, il: ItemLocation) -> LearnActiveSkillResult {
+    const ACTIVE_SKILL_PREFIX: &str = "active_skill:";
+
+    let item = gd.get_item(il).0;
+    let title = if let Some(title) = item.title() {
+        title
+    } else {
+        return LearnActiveSkillResult::NotSpellbook;
+    };
+
+    if !title.starts_with(ACTIVE_SKILL_PREFIX) {
+        return LearnActiveSkillResult::NotSpellbook;
+    }
+    let active_skill_id = ActiveSkillId(title.trim_start_matches(ACTIVE_SKILL_PREFIX).to_owned());
+    if RULES.active_skills.get(&active_skill_id).is_none() {
+        warn!("unknown active_skill \"{}\"", active_skill_id);
+        return LearnActiveSkillResult::NotSpellbook;
+    }
+
+    let chara = gd.chara.get_mut(CharaId::Player);
+    if chara
+        .active_skills
+        .iter()
+        .any(|(_, learned)| *learned == active_skill_id)
+    {
+        game_log!("active-skill-already-learned"; chara=chara, active_skill=active_skill_id);
+        return LearnActiveSkillResult::AlreadyLearned;
+    }
+
+    chara
+        .active_skills
+        .push((ActiveSkillOrigin::Learned, active_skill_id.clone()));
+    let chara = gd.chara.get(CharaId::Player);
+    game_log!("active-skill-learned"; chara=chara, active_skill=active_skill_id);
+    gd.remove_item(il, 1);
+    LearnActiveSkillResult::Success
+}
+
 pub fn calc_power(gd: &GameData, active_skill: &'static ActiveSkill, cid: CharaId) -> f32 {
     match active_skill.power_calc {
         PowerCalcMethod::Num(n) => n,
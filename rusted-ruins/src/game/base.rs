@@ -0,0 +1,172 @@
+//! Functions for player's bases
+
+use crate::game::extrait::*;
+use crate::game::InfoGetter;
+use common::gamedata::*;
+use common::gobj;
+use common::objholder::CharaTemplateIdx;
+use geom::*;
+use rules::RULES;
+
+/// Returns the site id if the current map is a player's base
+pub fn current_base(gd: &GameData) -> Option<SiteId> {
+    let mid = gd.get_current_mapid();
+    if is_player_base(gd, mid) {
+        Some(mid.sid())
+    } else {
+        None
+    }
+}
+
+pub fn is_player_base(gd: &GameData, mid: MapId) -> bool {
+    !mid.is_region_map() && gd.region.get_site(mid.sid()).player_base().is_some()
+}
+
+/// Get the item list location to put and take items for given container.
+/// Storage containers in a base share the base storage.
+pub fn container_item_list(gd: &GameData, il: ItemLocation) -> ItemListLocation {
+    let item_obj = gd.get_item(il).0.obj();
+    let shared = find_attr!(item_obj, ItemObjAttr::Container { functions, .. } => functions)
+        .map(|functions| functions.contains(&ContainerFunction::SharedStorage))
+        .unwrap_or(false);
+
+    match current_base(gd) {
+        Some(sid) if shared => ItemListLocation::BaseStorage { sid },
+        _ => ItemListLocation::in_container(il),
+    }
+}
+
+fn facility_quality(item: &Item, facility_type: &str) -> Option<i8> {
+    find_attr!(item.obj(), ItemObjAttr::Facility { ty, quality } => (ty, quality))
+        .filter(|(ty, _)| *ty == facility_type)
+        .map(|(_, quality)| *quality)
+}
+
+/// Quality bonus for products from facilities.
+/// In a base, facilities of the type placed on the map are aggregated.
+pub fn facility_quality_bonus(gd: &GameData, facility_type: &str) -> i32 {
+    let quality = if current_base(gd).is_some() {
+        let map = gd.get_current_map();
+        map.tile
+            .iter()
+            .flat_map(|tile| tile.item_list.iter())
+            .filter_map(|(item, _)| facility_quality(item, facility_type))
+            .map(|quality| quality as i32)
+            .sum()
+    } else {
+        crate::game::map::search::search_facility(gd, facility_type)
+            .and_then(|item| facility_quality(item, facility_type))
+            .map(|quality| quality as i32)
+            .unwrap_or(0)
+    };
+    quality.min(RULES.base.facility_quality_max)
+}
+
+/// Returns the quality of the best bed on or next to the player's tile
+pub fn search_bed(gd: &GameData) -> Option<i8> {
+    let map = gd.get_current_map();
+    let player_pos = gd.player_pos();
+    RectIter::new(player_pos + (-1, -1), player_pos + (1, 1))
+        .filter(|pos| map.is_inside(*pos))
+        .flat_map(|pos| map.tile[pos].item_list.iter())
+        .filter_map(|(item, _)| facility_quality(item, &RULES.base.bed_facility))
+        .max()
+}
+
+/// Total price of items in the base storage
+fn base_wealth(gd: &GameData, sid: SiteId) -> i64 {
+    gd.get_item_list(ItemListLocation::BaseStorage { sid })
+        .iter()
+        .map(|(item, n)| item.price() * *n as i64)
+        .sum()
+}
+
+/// Daily visitor and raid events for player's bases
+pub fn update_bases_daily(gd: &mut GameData) {
+    let mut sids = Vec::new();
+    gd.region.visit_all_sites(|sid, site| {
+        if site.player_base().is_some() {
+            sids.push(sid);
+        }
+    });
+
+    for sid in sids {
+        if !rng::gen_bool(RULES.base.event_prob) {
+            continue;
+        }
+        let wealth = base_wealth(gd, sid);
+        let raid_prob =
+            (wealth as f32 / RULES.base.raid_wealth_scale).min(RULES.base.raid_prob_max);
+        if rng::gen_bool(raid_prob) {
+            let n = (1 + wealth / RULES.base.wealth_per_raider.max(1))
+                .min(RULES.base.max_raiders as i64) as u32;
+            raid(gd, sid, n);
+        } else {
+            visit(gd, sid);
+        }
+    }
+}
+
+fn choose_template(ids: &[String]) -> Option<CharaTemplateIdx> {
+    if ids.is_empty() {
+        return None;
+    }
+    let id = &ids[rng::gen_range(0..ids.len())];
+    let idx = gobj::id_to_idx_checked(id);
+    if idx.is_none() {
+        warn!("unknown character template \"{}\" for base events", id);
+    }
+    idx
+}
+
+fn visit(gd: &mut GameData, sid: SiteId) {
+    if current_base(gd) != Some(sid) {
+        return;
+    }
+    if let Some(idx) = choose_template(&RULES.base.visitors) {
+        let lv = gd.chara.get(CharaId::Player).lv;
+        let chara = crate::game::chara::gen::create_chara(idx, lv, None, None);
+        game_log!("base-visitor"; chara=chara);
+        spawn_near_entrance(gd, chara);
+    }
+}
+
+/// Raiders appear if the player is in the base. Otherwise, they steal stored items.
+fn raid(gd: &mut GameData, sid: SiteId, n: u32) {
+    if current_base(gd) == Some(sid) {
+        game_log!("base-raid");
+        let lv = gd.chara.get(CharaId::Player).lv;
+        for _ in 0..n {
+            if let Some(idx) = choose_template(&RULES.base.raiders) {
+                let chara = crate::game::chara::gen::create_chara(idx, lv, None, None);
+                spawn_near_entrance(gd, chara);
+            }
+        }
+        return;
+    }
+
+    let ill = ItemListLocation::BaseStorage { sid };
+    for _ in 0..n {
+        let len = gd.get_item_list(ill).len();
+        if len == 0 {
+            break;
+        }
+        let i = rng::gen_range(0..len) as u32;
+        let item = gd.remove_item_and_get((ill, i), ItemMoveNum::All);
+        let site = gd.region.get_site(sid);
+        game_log!("base-raid-stolen"; site=site, item=item);
+    }
+}
+
+fn spawn_near_entrance(gd: &mut GameData, chara: Chara) {
+    let map = gd.get_current_map();
+    let entrance = map.entrance.first().copied().unwrap_or(Vec2d(0, 0));
+    let pos = if let Some(pos) = map.empty_tile_around(entrance) {
+        pos
+    } else {
+        return;
+    };
+    let mid = gd.get_current_mapid();
+    let cid = gd.add_chara_to_map(chara, mid);
+    gd.get_current_map_mut().locate_chara(cid, pos);
+}
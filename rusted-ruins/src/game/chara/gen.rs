@@ -50,17 +50,14 @@ pub fn create_chara<T: Into<Option<FactionId>>>(
 
     chara.update();
     chara.hp = chara.attr.max_hp;
+    chara.mp = chara.attr.max_mp;
     chara.reset_wait_time();
     chara
 }
 
-/// Create npc character from the race
-pub fn create_npc_chara(dungeon: DungeonKind, floor_level: u32) -> Option<Chara> {
-    let dungeon_gen_rule = &RULES
-        .dungeon_gen
-        .get(&dungeon)
-        .expect("No rule for npc generation");
-    let idx = choose_npc_chara_template(&dungeon_gen_rule.npc_race_probability, floor_level)?;
+/// Create npc character from given race probability
+pub fn create_npc_chara_by_race(nrp: &HashMap<String, f32>, floor_level: u32) -> Option<Chara> {
+    let idx = choose_npc_chara_template(nrp, floor_level)?;
     let ct = gobj::get_obj(idx);
     let mut chara = create_chara(idx, ct.gen_level, ct.faction, None);
     set_skill(&mut chara);
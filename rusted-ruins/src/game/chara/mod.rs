@@ -140,7 +140,9 @@ impl Chara {
     fn resurrect(&mut self) {
         self.hp = self.attr.max_hp;
         self.status.clear();
+        self.active_skill_cooldowns.clear();
         self.update();
+        self.mp = self.attr.max_mp;
     }
 
     /// Return (current item weight, capacity)
@@ -172,9 +174,42 @@ impl Chara {
         (lv, adj)
     }
 
+    /// Recover MP of this character
+    fn recover_mp(&mut self, value: i32) {
+        self.mp = std::cmp::min(self.mp + value, self.attr.max_mp);
+    }
+
     /// active skill available or not.
-    fn active_skill_available(&self, active_skill: &ActiveSkill) -> bool {
+    fn active_skill_available(&self, id: &ActiveSkillId, active_skill: &ActiveSkill) -> bool {
         self.sp > active_skill.cost_sp as f32
+            && self.mp >= active_skill.cost_mp as i32
+            && self.active_skill_cooldown(id) == 0
+    }
+
+    /// Remaining turns until the active skill becomes available.
+    fn active_skill_cooldown(&self, id: &ActiveSkillId) -> u16 {
+        self.active_skill_cooldowns.get(id).copied().unwrap_or(0)
+    }
+
+    /// Decrease the remaining turns of active skill cooldowns.
+    fn advance_active_skill_cooldowns(&mut self) {
+        for turn_left in self.active_skill_cooldowns.values_mut() {
+            *turn_left = turn_left.saturating_sub(1);
+        }
+        self.active_skill_cooldowns
+            .retain(|_, turn_left| *turn_left > 0);
+    }
+
+    /// The probability of failure when using the active skill.
+    /// It increases when the skill level is lower than the skill difficulty.
+    fn active_skill_failure_probability(&self, active_skill: &ActiveSkill) -> f32 {
+        let skill_level = self.skill_level(SkillKind::MagicDevice);
+        if skill_level >= active_skill.difficulty {
+            return 0.0;
+        }
+        let lacking = (active_skill.difficulty - skill_level) as f32;
+        (lacking * RULES.chara.active_skill_failure_factor)
+            .min(RULES.chara.active_skill_failure_max)
     }
 
     /// Update character level by the current skill level
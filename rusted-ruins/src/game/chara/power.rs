@@ -14,17 +14,14 @@ pub fn calc_power(
     _element: Element,
     skill_kind: SkillKind,
 ) -> (f32, f32) {
-    let skill_lv = chara.skill_level(skill_kind) as f32;
+    let skill_lv = chara.skill_level(skill_kind);
     let (attr, hit_attr) = match power_kind {
         CharaPowerKind::MeleeAttack => (chara.attr.str, chara.attr.dex),
         CharaPowerKind::RangedAttack => (chara.attr.dex, chara.attr.dex),
     };
-    let (attr, hit_attr) = (attr as f32, hit_attr as f32);
 
-    let skill_base = RULES.combat.skill_base;
-
-    let power = attr * attr * (skill_lv + skill_base).powf(1.5);
-    let hit_power = hit_attr * (skill_lv + skill_base);
+    let power = RULES.combat.attack_power(attr, skill_lv);
+    let hit_power = RULES.combat.hit_power(hit_attr, skill_lv);
 
     (power, hit_power)
 }
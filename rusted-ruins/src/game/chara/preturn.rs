@@ -5,12 +5,16 @@ use crate::text::ToText;
 use common::gamedata::*;
 use geom::MDistRangeIter;
 use rng::{get_rng, roll_dice, Rng};
+use rules::status_effect::{StatusCure, StatusTurnEffect};
 use rules::RULES;
 
 /// This function will be called before the character's turn
 ///
 pub fn preturn(game: &mut Game<'_>, cid: CharaId) -> bool {
     awake_other_npc(game, cid);
+    if cid == CharaId::Player {
+        crate::game::rest::update_rest(game);
+    }
 
     let chara = game.gd.chara.get_mut(cid);
     chara.update();
@@ -35,6 +39,8 @@ pub fn preturn(game: &mut Game<'_>, cid: CharaId) -> bool {
         s.expire(&mut game.gd, cid);
     }
 
+    process_status_effects(game, cid);
+
     let chara = game.gd.chara.get_mut(cid);
     let mut is_poisoned = false;
     let mut progress_anim = None;
@@ -44,6 +50,11 @@ pub fn preturn(game: &mut Game<'_>, cid: CharaId) -> bool {
             CharaStatus::Poisoned => {
                 is_poisoned = true;
             }
+            // Resting is processed without progress animation
+            CharaStatus::Work {
+                work: Work::Rest { .. },
+                ..
+            } => (),
             CharaStatus::Work {
                 turn_left,
                 needed_turn,
@@ -82,12 +93,15 @@ pub fn preturn(game: &mut Game<'_>, cid: CharaId) -> bool {
     };
 
     let chara = game.gd.chara.get_mut(cid);
+    let sp_consumption_factor =
+        sp_consumption_factor * crate::game::rest::sp_consumption_factor(chara);
+    let regeneration_factor = crate::game::rest::regeneration_factor(chara);
 
     if chara.hp < chara.attr.max_hp && chara.sp > RULES.chara.sp_starving {
         // HP regeneration
         let lv = chara.skill_level(SkillKind::Endurance) as f32;
         if get_rng().gen_bool(RULES.chara.hp_regeneration_probability.into()) {
-            let a = (lv * RULES.chara.hp_regeneration_factor) as i32;
+            let a = (lv * RULES.chara.hp_regeneration_factor * regeneration_factor) as i32;
             let v = roll_dice(1, a);
             chara.heal(v);
         }
@@ -103,6 +117,19 @@ pub fn preturn(game: &mut Game<'_>, cid: CharaId) -> bool {
         }
     }
 
+    let chara = game.gd.chara.get_mut(cid);
+    chara.advance_active_skill_cooldowns();
+
+    if chara.mp < chara.attr.max_mp {
+        // MP regeneration
+        let lv = chara.skill_level(SkillKind::MagicDevice) as f32 + 1.0;
+        if get_rng().gen_bool(RULES.chara.mp_regeneration_probability.into()) {
+            let a = ((lv * RULES.chara.mp_regeneration_factor * regeneration_factor) as i32).max(1);
+            let v = roll_dice(1, a);
+            chara.recover_mp(v);
+        }
+    }
+
     can_act(game.gd.chara.get_mut(cid))
 }
 
@@ -121,12 +148,73 @@ fn can_act(chara: &Chara) -> bool {
             CharaStatus::Work { .. } => {
                 return false;
             }
+            CharaStatus::Defined { .. }
+                if s.status_effect().map(|s| s.prevent_action).unwrap_or(false) =>
+            {
+                game_log!("status-prevent-action"; chara=chara, status=s);
+                return false;
+            }
             _ => (),
         }
     }
     true
 }
 
+/// Process cure by chance and per turn effects of status effects defined in rules.
+fn process_status_effects(game: &mut Game<'_>, cid: CharaId) {
+    let chara = game.gd.chara.get_mut(cid);
+    let mut turn_effects = Vec::new();
+
+    for s in std::mem::take(&mut chara.status).into_iter() {
+        let status_effect = if let Some(status_effect) = s.status_effect() {
+            status_effect
+        } else {
+            chara.status.push(s);
+            continue;
+        };
+
+        let cured = status_effect.cure.iter().any(|cure| match *cure {
+            StatusCure::Chance(p) => get_rng().gen_bool(p.clamp(0.0, 1.0).into()),
+            _ => false,
+        });
+        if cured {
+            game_log_i!("status-expired"; chara=chara, status=s);
+            continue;
+        }
+
+        if let CharaStatus::Defined { stack, .. } = s {
+            for turn_effect in &status_effect.per_turn {
+                turn_effects.push((*turn_effect, stack, s.clone()));
+            }
+        }
+        chara.status.push(s);
+    }
+
+    for (turn_effect, stack, s) in turn_effects.into_iter() {
+        let chara = game.gd.chara.get_mut(cid);
+        let max_hp = chara.attr.max_hp as f32;
+        match turn_effect {
+            StatusTurnEffect::Damage(ratio) => {
+                let damage = ((max_hp * ratio * stack as f32) as i32).max(1);
+                game_log_i!("status-damage"; chara=chara, status=s, damage=damage);
+                if do_damage(game, cid, damage, CharaDamageKind::Status, None) <= 0 {
+                    return;
+                }
+            }
+            StatusTurnEffect::Heal(ratio) => {
+                chara.heal((max_hp * ratio * stack as f32) as i32);
+            }
+            StatusTurnEffect::Sp(value) => {
+                if let Some(damage) = chara.add_sp(value * stack as f32, cid) {
+                    if do_damage(game, cid, damage, CharaDamageKind::Starve, None) <= 0 {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Awake enemy NPC to combat state.
 fn awake_other_npc(game: &mut Game<'_>, cid: CharaId) {
     let center = if let Some(center) = game.gd.chara_pos(cid) {
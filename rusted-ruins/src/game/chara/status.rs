@@ -1,11 +1,14 @@
 //! Functions for character status operation
 
 use common::gamedata::*;
+use rules::status_effect::{StatusCure, StatusEffect, StatusStacking};
+use rules::RULES;
 
 pub trait CharaStatusOperation {
     fn add_status(&mut self, new_status: CharaStatus);
     fn remove_sp_status(&mut self);
     fn remove_encumbrance_status(&mut self);
+    fn remove_status_by_cure(&mut self, cure: StatusCure) -> Vec<CharaStatus>;
 }
 
 impl CharaStatusOperation for Chara {
@@ -56,6 +59,47 @@ impl CharaStatusOperation for Chara {
                 }
                 return;
             }
+            CharaStatus::Defined {
+                ref id,
+                turn_left: turn_left_new,
+                stack: stack_new,
+            } => {
+                let stacking = RULES
+                    .status_effects
+                    .get(id)
+                    .map(|status_effect| status_effect.stacking)
+                    .unwrap_or_default();
+
+                for s in self.status.iter_mut() {
+                    if let CharaStatus::Defined {
+                        id: ref id_old,
+                        ref mut turn_left,
+                        ref mut stack,
+                    } = *s
+                    {
+                        if id_old != id {
+                            continue;
+                        }
+                        match stacking {
+                            StatusStacking::Ignore => (),
+                            StatusStacking::Refresh => {
+                                *turn_left = longer_turn(*turn_left, turn_left_new);
+                            }
+                            StatusStacking::Extend => {
+                                *turn_left = match (*turn_left, turn_left_new) {
+                                    (Some(a), Some(b)) => Some(a.saturating_add(b)),
+                                    _ => None,
+                                };
+                            }
+                            StatusStacking::Stack { max } => {
+                                *stack = stack.saturating_add(stack_new).min(max.max(1));
+                                *turn_left = longer_turn(*turn_left, turn_left_new);
+                            }
+                        }
+                        return;
+                    }
+                }
+            }
             _ => (),
         }
         self.status.push(new_status);
@@ -70,6 +114,31 @@ impl CharaStatusOperation for Chara {
     fn remove_encumbrance_status(&mut self) {
         self.status.retain(|s| !s.about_encumbrance());
     }
+
+    /// Remove status effects that can be cured by given condition, and returns removed status.
+    fn remove_status_by_cure(&mut self, cure: StatusCure) -> Vec<CharaStatus> {
+        let mut removed = Vec::new();
+        for s in std::mem::take(&mut self.status).into_iter() {
+            let cured = s
+                .status_effect()
+                .map(|status_effect| status_effect.cure.contains(&cure))
+                .unwrap_or(false);
+            if cured {
+                removed.push(s);
+            } else {
+                self.status.push(s);
+            }
+        }
+        removed
+    }
+}
+
+/// None means infinite duration.
+fn longer_turn(a: Option<u16>, b: Option<u16>) -> Option<u16> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(std::cmp::max(a, b)),
+        _ => None,
+    }
 }
 
 pub trait CharaStatusExt {
@@ -78,6 +147,7 @@ pub trait CharaStatusExt {
     fn advance_turn(&mut self, n: u16);
     fn is_expired(&self) -> bool;
     fn expire(self, gd: &mut GameData, cid: CharaId);
+    fn status_effect(&self) -> Option<&'static StatusEffect>;
 }
 
 impl CharaStatusExt for CharaStatus {
@@ -119,6 +189,10 @@ impl CharaStatusExt for CharaStatus {
     }
 
     fn expire(self, gd: &mut GameData, cid: CharaId) {
+        if let CharaStatus::Defined { .. } = self {
+            game_log_i!("status-expired"; chara=gd.chara.get(cid), status=self);
+        }
+
         if let CharaStatus::Work { work, .. } = self {
             match work {
                 Work::Creation {
@@ -139,7 +213,18 @@ impl CharaStatusExt for CharaStatus {
                 Work::Harvest { item_idx, il } => {
                     crate::game::action::harvest::finish_harvest(gd, cid, item_idx, il);
                 }
+                Work::Rest { kind } => {
+                    crate::game::rest::finish_rest(gd, cid, kind);
+                }
             }
         }
     }
+
+    fn status_effect(&self) -> Option<&'static StatusEffect> {
+        if let CharaStatus::Defined { id, .. } = self {
+            RULES.status_effects.get(id)
+        } else {
+            None
+        }
+    }
 }
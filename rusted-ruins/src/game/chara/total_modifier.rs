@@ -1,7 +1,9 @@
 use common::basic::BonusLevel;
 use common::gamedata::{
-    CharaAttrDiff, CharaModifier, CharaStatus, CharaTotalModifier, CharaTrait, SkillKind,
+    CharaAttrDiff, CharaModifier, CharaStatus, CharaTotalModifier, CharaTrait, Element,
+    ElementArray, ElementProtection, Item, ItemObjAttr, SkillKind, ELEMENTS,
 };
+use common::gobj;
 use rules::class::Class;
 use rules::RULES;
 use std::collections::HashMap;
@@ -25,18 +27,49 @@ pub fn add_modifier(tm: &mut CharaTotalModifier, p: &CharaModifier) {
         CharaModifier::Wil(value) => tm.wil += value,
         CharaModifier::Cha(value) => tm.cha += value,
         CharaModifier::Spd(value) => tm.spd += value,
+        CharaModifier::ViewRange(value) => tm.view_range += value,
+        CharaModifier::ElementProtection(element, value) => {
+            if *element != Element::None {
+                tm.element_protection[*element] += *value as i16;
+            }
+        }
     }
 }
 
-pub fn add_status(_tm: &mut CharaTotalModifier, _status: &CharaStatus) {
-    // match status {
-    //     _ => (),
-    // }
+pub fn add_status(tm: &mut CharaTotalModifier, status: &CharaStatus) {
+    if let CharaStatus::Defined { id, stack, .. } = status {
+        if let Some(status_effect) = RULES.status_effects.get(id) {
+            for _ in 0..*stack {
+                for modifier in &status_effect.modifiers {
+                    add_modifier(tm, modifier);
+                }
+            }
+        }
+    }
 }
 
 pub fn add_class(tm: &mut CharaTotalModifier, class: &Class) {
     add_attr_diff(tm, &class.attr);
-    add_skill_bonus_map(tm, &class.skill_bonus)
+    add_skill_bonus_map(tm, &class.skill_bonus);
+    add_element_protection(tm, &class.element_protection);
+}
+
+pub fn add_element_protection(
+    tm: &mut CharaTotalModifier,
+    element_protection: &ElementArray<ElementProtection>,
+) {
+    for e in &ELEMENTS {
+        tm.element_protection[*e] += element_protection[*e].value() as i16;
+    }
+}
+
+pub fn add_equip_item(tm: &mut CharaTotalModifier, item: &Item) {
+    let obj = gobj::get_obj(item.idx);
+    if let Some(modifiers) = find_attr!(obj, ItemObjAttr::CharaModifiers(modifiers)) {
+        for modifier in modifiers {
+            add_modifier(tm, modifier);
+        }
+    }
 }
 
 fn add_attr_diff(tm: &mut CharaTotalModifier, d: &CharaAttrDiff) {
@@ -12,6 +12,15 @@ pub fn update_attributes(chara: &mut Chara) {
     // Update total effect
     chara.tm = Box::default();
     total_modifier::add_class(&mut chara.tm, RULES.classes.get(chara.class));
+    total_modifier::add_element_protection(&mut chara.tm, &ct.element_protection);
+
+    if let Some(race) = RULES.races.get(&ct.race) {
+        total_modifier::add_element_protection(&mut chara.tm, &race.element_protection);
+    }
+
+    for (_, _, item) in chara.equip.item_iter() {
+        total_modifier::add_equip_item(&mut chara.tm, item);
+    }
 
     for status in &chara.status {
         total_modifier::add_status(&mut chara.tm, status);
@@ -29,9 +38,11 @@ pub fn update_attributes(chara: &mut Chara) {
     chara.attr.int = (ct.base_attr.int + chara.tm.int).max(1) as u16;
     chara.attr.wil = (ct.base_attr.wil + chara.tm.wil).max(1) as u16;
     chara.attr.cha = (ct.base_attr.cha + chara.tm.cha).max(1) as u16;
+    chara.attr.max_mp = calc_max_mp(chara);
+    chara.mp = chara.mp.min(chara.attr.max_mp);
 
     // View range
-    chara.attr.view_range = RULES.chara.default_view_range;
+    chara.attr.view_range = (RULES.chara.default_view_range + chara.tm.view_range as i32).max(1);
 }
 
 fn calc_max_hp(chara: &mut Chara, ct: &CharaTemplateObject) -> i32 {
@@ -40,6 +51,11 @@ fn calc_max_hp(chara: &mut Chara, ct: &CharaTemplateObject) -> i32 {
     ((factor * base_hp / 8) + chara.tm.max_hp).max(1)
 }
 
+fn calc_max_mp(chara: &Chara) -> i32 {
+    let factor = chara.skill_level(SkillKind::MagicDevice) as f32 + 8.0;
+    (chara.attr.int as f32 * factor / 8.0 * RULES.chara.mp_factor) as i32
+}
+
 pub fn update_encumbrance_status(chara: &mut Chara) {
     let cap = calc_carrying_capacity(chara);
     let total_weight = chara.item_list.sum_weight() as f32;
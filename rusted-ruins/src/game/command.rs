@@ -30,6 +30,7 @@ pub enum Command {
     OpenExitWin,
     OpenGameInfoWin,
     OpenHelpWin,
+    OpenQuestWin,
     OpenStatusWin,
     OpenItemMenu,
     PickUpItem,
@@ -68,11 +68,19 @@ pub fn finish_creation(
 ) {
     let idx: ItemIdx = gobj::id_to_idx(&recipe.product);
     let item_obj = gobj::get_obj(idx);
+    let quality_bonus = recipe
+        .facility
+        .as_ref()
+        .map(|facility_type| crate::game::base::facility_quality_bonus(gd, facility_type))
+        .unwrap_or(0);
     let mut item = Item {
         idx,
         flags: item_obj.default_flags,
         kind: item_obj.kind,
-        quality: ItemQuality::default(),
+        quality: ItemQuality {
+            base: quality_bonus,
+            ..ItemQuality::default()
+        },
         attrs: vec![],
         time: None,
     };
@@ -92,6 +100,7 @@ pub fn finish_creation(
     };
     let il = gd.get_item_list_mut(ill);
     il.append(item, 1);
+    crate::game::quest::count_crafted_item(gd, idx);
 
     let product = obj_txt(&recipe.product);
     let player = gd.chara.get_mut(CharaId::Player);
@@ -1,7 +1,9 @@
 use crate::config::changeable::game_log_cfg;
 use crate::game::extrait::*;
+use crate::game::faction::change_relation;
 use crate::game::{Game, InfoGetter};
 use common::gamedata::*;
+use rules::status_effect::StatusCure;
 use rules::RULES;
 
 #[derive(Clone, Copy)]
@@ -11,6 +13,8 @@ pub enum CharaDamageKind {
     Explosion,
     Poison,
     Starve,
+    Status,
+    Trap,
 }
 
 /// Give damage to a character.
@@ -42,6 +46,23 @@ pub fn do_damage(
     let chara_hp = chara.hp;
 
     if chara_hp > 0 {
+        if cid == CharaId::Player {
+            crate::game::rest::interrupt_rest(&mut game.gd, "rest-interrupted-damage");
+        }
+        let chara = game.gd.chara.get_mut(cid);
+
+        // Cure status effects that are cured by damage
+        if matches!(
+            damage_kind,
+            CharaDamageKind::MeleeAttack
+                | CharaDamageKind::RangedAttack
+                | CharaDamageKind::Explosion
+        ) {
+            for s in chara.remove_status_by_cure(StatusCure::Damaged).into_iter() {
+                game_log_i!("status-expired"; chara=chara, status=s);
+            }
+        }
+
         // Faction process
         if let (Some(origin), Some(origin_faction)) = (origin, origin_faction) {
             if !chara.ai.state.is_combat() {
@@ -50,9 +71,7 @@ pub fn do_damage(
                 if origin_faction.is_player() && !faction.is_player() {
                     game_log!("npc-get-hostile"; chara=chara);
                     let target_faction = chara.faction;
-                    game.gd
-                        .faction
-                        .change(target_faction, RULES.faction.relvar_attacked);
+                    change_relation(&mut game.gd, target_faction, RULES.faction.relvar_attacked);
                 }
             }
         }
@@ -74,6 +93,12 @@ pub fn do_damage(
             CharaDamageKind::Starve => {
                 game_log_i!("killed-by-starve-damage"; chara=chara);
             }
+            CharaDamageKind::Status => {
+                game_log_i!("killed-by-status-damage"; chara=chara);
+            }
+            CharaDamageKind::Trap => {
+                game_log_i!("killed-by-trap"; chara=chara);
+            }
         }
 
         if origin == Some(CharaId::Player) && Some(cid) != origin {
@@ -82,9 +107,7 @@ pub fn do_damage(
             let target_faction = chara.faction;
             crate::game::quest::count_slayed_monster(&mut game.gd, idx);
 
-            game.gd
-                .faction
-                .change(target_faction, RULES.faction.relvar_killed);
+            change_relation(&mut game.gd, target_faction, RULES.faction.relvar_killed);
         }
     }
     chara_hp
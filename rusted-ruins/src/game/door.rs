@@ -0,0 +1,156 @@
+//! Opening, closing, unlocking and breaking doors
+
+use super::extrait::*;
+use super::Game;
+use common::gamedata::*;
+use common::gobj;
+use common::objholder::SpecialTileIdx;
+use geom::*;
+use rules::RULES;
+
+/// Doors are generated only when their special tile objects are loaded
+pub fn door_objects_available() -> bool {
+    [DoorState::Open, DoorState::Closed]
+        .iter()
+        .all(|state| gobj::id_to_idx_checked::<SpecialTileIdx>(state.obj_id()).is_some())
+}
+
+/// Create a door for a generated map
+pub fn gen_door(floor_level: u32) -> Door {
+    let r = &RULES.door;
+    let state = if rng::gen_bool(r.locked_probability) {
+        DoorState::Locked
+    } else if rng::gen_bool(r.open_probability) {
+        DoorState::Open
+    } else {
+        DoorState::Closed
+    };
+    Door {
+        state,
+        lock_level: r.lock_level_base + r.lock_level_per_floor * floor_level as u16,
+        hp: r.hp,
+    }
+}
+
+/// Try to open the closed door. The player unlocks locked doors by keys in the inventory.
+/// Returns true if the character's turn is consumed.
+pub fn try_open(game: &mut Game<'_>, cid: CharaId, pos: Vec2d) -> bool {
+    let door = match game.gd.get_current_map().tile[pos].door {
+        Some(door) if door.state.is_closed() => door,
+        _ => {
+            return false;
+        }
+    };
+
+    if door.state == DoorState::Locked {
+        if cid != CharaId::Player {
+            return false;
+        }
+        if !unlock_by_key(game, door.lock_level) {
+            game_log!("door-locked");
+            return false;
+        }
+    }
+
+    set_state(game, pos, DoorState::Open);
+    if cid == CharaId::Player {
+        game_log!("door-open"; chara=game.gd.chara.get(cid));
+    }
+    true
+}
+
+/// Consume a key whose level is enough for the lock
+fn unlock_by_key(game: &mut Game<'_>, lock_level: u16) -> bool {
+    let il = game.gd.get_item_list(ItemListLocation::PLAYER);
+    let key = il
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (item, _))| {
+            find_attr!(item.obj(), ItemObjAttr::Key(level) => level)
+                .filter(|level| **level >= lock_level)
+                .map(|level| (i, *level))
+        })
+        .min_by_key(|(_, level)| *level);
+
+    let i = if let Some((i, _)) = key {
+        i
+    } else {
+        return false;
+    };
+    let item_location = (ItemListLocation::PLAYER, i as u32);
+    let item = game.gd.get_item(item_location).0.clone();
+    game.gd.remove_item(item_location, 1);
+    game_log!("door-unlock-key"; chara=game.gd.chara.get(CharaId::Player), item=item);
+    true
+}
+
+/// Close the open door. Returns true if the player's turn is consumed.
+pub fn close(game: &mut Game<'_>, pos: Vec2d) -> bool {
+    let tile = &game.gd.get_current_map().tile[pos];
+    if tile.door.map(|door| door.state) != Some(DoorState::Open) {
+        return false;
+    }
+    if tile.chara.is_some() || !tile.item_list.is_empty() {
+        game_log!("door-cannot-close");
+        return false;
+    }
+
+    set_state(game, pos, DoorState::Closed);
+    game_log!("door-close"; chara=game.gd.chara.get(CharaId::Player));
+    true
+}
+
+/// Try to pick the lock by Lockpicking skill. Returns true if the player's turn is consumed.
+pub fn pick_lock(game: &mut Game<'_>, pos: Vec2d) -> bool {
+    let door = match game.gd.get_current_map().tile[pos].door {
+        Some(door) if door.state == DoorState::Locked => door,
+        _ => {
+            return false;
+        }
+    };
+
+    let floor_level = game.gd.get_current_mapid().floor();
+    let player = game.gd.chara.get_mut(CharaId::Player);
+    let skill = player.skill_level(SkillKind::Lockpicking).max(1) as f32;
+    let p = RULES.door.lockpicking_factor * skill / (skill + door.lock_level as f32);
+
+    if p >= 1.0 || rng::gen_bool(p.max(0.0)) {
+        game_log!("door-lock-picked"; chara=player);
+        player.add_skill_exp(
+            SkillKind::Lockpicking,
+            RULES.door.exp_lockpicking,
+            floor_level,
+        );
+        set_state(game, pos, DoorState::Closed);
+    } else {
+        game_log!("door-lock-pick-failed"; chara=player);
+    }
+    true
+}
+
+/// Damage the door on the tile. Returns false if there is no door.
+pub fn damage(game: &mut Game<'_>, pos: Vec2d, power: f32) -> bool {
+    let map = game.gd.get_current_map_mut();
+    let door = if let Some(door) = map.tile[pos].door.as_mut() {
+        door
+    } else {
+        return false;
+    };
+
+    let damage = power as u16;
+    if door.hp <= damage {
+        map.tile[pos].door = None;
+        game_log!("door-broken");
+        super::view::update_view_map(game);
+    } else {
+        door.hp -= damage;
+    }
+    true
+}
+
+fn set_state(game: &mut Game<'_>, pos: Vec2d, state: DoorState) {
+    if let Some(door) = game.gd.get_current_map_mut().tile[pos].door.as_mut() {
+        door.state = state;
+    }
+    super::view::update_view_map(game);
+}
@@ -3,35 +3,65 @@
 use crate::game::extrait::*;
 use crate::game::map::builder::MapBuilder;
 use crate::game::saveload::gen_box_id;
+use crate::map_generator::RoomRect;
 use common::gamedata::*;
 use common::gobj;
 use common::objholder::*;
 use geom::*;
 use rng::{self, GameRng, SliceRandom};
-use rules::dungeon_gen::DungeonGenParams;
+use rules::dungeon_gen::{DungeonGenParams, FloorTheme};
 use rules::RULES;
 
 /// Add a new dungeon
-pub fn add_dungeon_site(gd: &mut GameData, dungeon_kind: DungeonKind, pos: Vec2d) -> SiteId {
+pub fn add_dungeon_site(
+    gd: &mut GameData,
+    rid: RegionId,
+    dungeon_kind: DungeonKind,
+    pos: Vec2d,
+) -> SiteId {
     let floor_range = &RULES.dungeon_gen[&dungeon_kind].floor_range;
     let n_floor = rng::gen_range(floor_range[0]..floor_range[1]);
     let mut site = Site::new(n_floor, None);
-    site.content = SiteContent::AutoGenDungeon { dungeon_kind };
-    gd.add_site(
-        site,
-        SiteKind::AutoGenDungeon,
-        RegionId::default(),
-        Some(pos),
-    )
-    .unwrap()
+    site.content = SiteContent::AutoGenDungeon {
+        dungeon_kind,
+        shops: Default::default(),
+        faction: None,
+    };
+    gd.add_site(site, SiteKind::AutoGenDungeon, rid, Some(pos))
+        .unwrap()
+}
+
+/// Faction of the dungeon. The default faction can be replaced for each region.
+pub fn dungeon_faction(gd: &GameData, sid: SiteId) -> Option<FactionId> {
+    let (dungeon_kind, faction) = match gd.region.get_site(sid).content {
+        SiteContent::AutoGenDungeon {
+            dungeon_kind,
+            faction,
+            ..
+        } => (dungeon_kind, faction),
+        _ => {
+            return None;
+        }
+    };
+    let faction = faction
+        .or_else(|| {
+            RULES
+                .region_gen
+                .params
+                .get(&gd.region.get(sid.rid).name)
+                .and_then(|params| params.dungeon_factions.get(&dungeon_kind))
+                .copied()
+        })
+        .unwrap_or(RULES.dungeon_gen[&dungeon_kind].default_faction_id);
+    Some(faction)
 }
 
 /// Extend dungion site by one floor
 pub fn extend_site_floor(gd: &mut GameData, sid: SiteId) {
     let floor = gd.region.get_site(sid).floor_num();
     let is_deepest_floor = floor >= gd.region.get_site(sid).max_floor() - 1;
-    let map = match gd.region.get_site(sid).content {
-        SiteContent::AutoGenDungeon { dungeon_kind } => {
+    let (built_map, rule) = match gd.region.get_site(sid).content {
+        SiteContent::AutoGenDungeon { dungeon_kind, .. } => {
             let rule = &RULES.dungeon_gen[&dungeon_kind];
             let gen_params = &RULES.dungeon_gen[&dungeon_kind];
             let map_gen_id = &gen_params
@@ -41,29 +71,122 @@ pub fn extend_site_floor(gd: &mut GameData, sid: SiteId) {
                 .0;
             let tile_idx = gobj::id_to_idx(&rule.terrain[0][0]);
             let wall_idx = gobj::id_to_idx(&rule.terrain[0][1]);
-            let mut map = MapBuilder::from_map_gen_id(map_gen_id)
+            let mut built_map = MapBuilder::from_map_gen_id(map_gen_id)
                 .floor(floor)
                 .tile(tile_idx)
                 .wall(wall_idx)
                 .deepest_floor(is_deepest_floor)
                 .music(&gen_params.music)
-                .build();
-            set_sub_walls(&mut map, rule);
-            map
+                .vaults(&gen_params.vaults)
+                .build_detail();
+            set_sub_walls(&mut built_map.map, rule);
+            (built_map, Some(rule))
         }
-        _ => MapBuilder::new(40, 40).floor(floor).build(),
+        _ => (MapBuilder::new(40, 40).floor(floor).build_detail(), None),
     };
 
     let map_random_id = gen_box_id(gd);
-    let mid = gd.add_map(map, sid, map_random_id);
-    super::map::gen_npcs(gd, mid, 10, mid.floor());
-    super::map::gen_items(gd, mid);
+    let mid = gd.add_map(built_map.map, sid, map_random_id);
+    // New floors are not aged on entering
+    gd.region.get_map_mut(mid).last_visit = crate::game::time::current_time();
+    super::map::vault::gen_vault_contents(gd, mid, &built_map.vaults);
+
+    let faction = dungeon_faction(gd, sid);
+    let theme = rule.and_then(|rule| {
+        rule.themes
+            .choose_weighted(&mut GameRng, |item| item.1)
+            .ok()
+            .map(|item| &item.0)
+    });
+    if let (Some(rule), Some(theme), Some(faction)) = (rule, theme, faction) {
+        gen_themed_floor(gd, mid, rule, theme, &built_map.rooms, faction);
+    } else {
+        super::map::gen_npcs(gd, mid, 10, mid.floor());
+        super::map::gen_items(gd, mid);
+    }
+
+    if let Some(faction) = faction {
+        super::trap::gen_traps(gd.region.get_map_mut(mid), faction);
+    }
 
     if is_deepest_floor {
         add_for_deepest_floor(gd, mid);
     }
 }
 
+/// Generate special rooms, npcs, items and decos by the floor theme
+fn gen_themed_floor(
+    gd: &mut GameData,
+    mid: MapId,
+    rule: &DungeonGenParams,
+    theme: &FloorTheme,
+    rooms: &[RoomRect],
+    faction: FactionId,
+) {
+    trace!("Floor theme \"{}\" is selected for {:?}", theme.id, mid);
+    let nrp = if theme.npc_race_probability.is_empty() {
+        &rule.npc_race_probability
+    } else {
+        &theme.npc_race_probability
+    };
+    let item_gen_weight = if theme.item_gen_weight.is_empty() {
+        &rule.item_gen_weight
+    } else {
+        &theme.item_gen_weight
+    };
+    let item_gen_probability = theme
+        .item_gen_probability
+        .unwrap_or(rule.item_gen_probability);
+
+    super::special_room::gen_special_rooms(gd, mid, theme, rooms, nrp, item_gen_weight, faction);
+
+    let floor_level = mid.floor();
+    super::map::gen_npcs_by_race(
+        gd,
+        mid,
+        theme.n_npc,
+        floor_level,
+        nrp,
+        super::map::choose_empty_tile,
+    );
+    super::map::gen_items_by_weight(gd, mid, item_gen_probability, item_gen_weight);
+
+    let map = gd.region.get_map_mut(mid);
+    let positions: Vec<Vec2d> = map.tile.iter_idx().collect();
+    set_decos(map, positions, &theme.decos);
+}
+
+/// Place decos on empty floor tiles by given probability
+pub fn set_decos<I: IntoIterator<Item = Vec2d>>(
+    map: &mut Map,
+    positions: I,
+    decos: &[(String, f32)],
+) {
+    let decos: Vec<(DecoIdx, f32)> = decos
+        .iter()
+        .filter_map(|(id, prob)| {
+            let idx = gobj::id_to_idx_checked(id);
+            if idx.is_none() {
+                warn!("unknown deco id {}", id);
+            }
+            idx.map(|idx| (idx, *prob))
+        })
+        .collect();
+    if decos.is_empty() {
+        return;
+    }
+
+    for p in positions {
+        let tile = &mut map.tile[p];
+        if !tile.wall.is_empty() || tile.deco.is_some() || !tile.special.is_none() {
+            continue;
+        }
+        if let Some((idx, _)) = decos.iter().find(|(_, prob)| rng::gen_bool(*prob)) {
+            tile.deco = Some(*idx);
+        }
+    }
+}
+
 pub fn set_sub_walls(map: &mut Map, rule: &DungeonGenParams) {
     for (sub_wall_id, weight) in &rule.sub_walls {
         let wall_idx: WallIdx = if let Some(wall_idx) = gobj::id_to_idx_checked(sub_wall_id) {
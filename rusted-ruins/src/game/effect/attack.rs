@@ -7,7 +7,7 @@ use common::gamedata::*;
 use common::gobj;
 use geom::ShapeKind;
 use rng::Rng;
-// use rules::RULES;
+use rules::RULES;
 
 #[derive(Clone, Copy)]
 struct AttackParams {
@@ -121,7 +121,15 @@ fn attack_target(
         target.attr.vit,
         defence_skill_level,
     );
-    let damage = (attack_params.attack_power / defence_power).floor() as i32;
+    let protection = target.tm.element_protection[attack_params.element];
+    let mut damage = RULES
+        .combat
+        .damage(attack_params.attack_power, defence_power, protection);
+
+    if let Some(multiplier) = critical_judge(&game.gd, cid, target_id, attack_params.kind) {
+        damage *= multiplier;
+    }
+    let damage = damage.floor() as i32;
 
     // Give damage
     let hp = do_damage(game, target_id, damage, attack_params.kind, Some(cid));
@@ -153,21 +161,9 @@ fn calc_equip_defence(gd: &GameData, cid: CharaId) -> ElementArray<u32> {
 
 /// Calculate defence power
 fn calc_defence_power(equip_def: u32, chara_param: u16, skill_level: u32) -> f32 {
-    assert!(chara_param > 0);
-    let equip_def = equip_def as f32;
-    let chara_param = chara_param as f32;
-    let skill_level = skill_level as f32;
-
-    (equip_def + 16.0) * chara_param * (skill_level + 8.0)
-}
-
-/// Calculate evasion power
-fn calc_evasion_power(equip: u32, skill_level: u32, chara_param: u16) -> f32 {
-    let equip = equip as f32;
-    let skill_level = skill_level as f32;
-    let chara_param = chara_param as f32 * 0.25;
-
-    equip + skill_level + chara_param * 0.5
+    RULES
+        .combat
+        .defence_power(equip_def, chara_param, skill_level)
 }
 
 fn hit_judge(
@@ -177,24 +173,20 @@ fn hit_judge(
     kind: CharaDamageKind,
 ) -> bool {
     let evasion_power = {
-        let equip = match kind {
-            CharaDamageKind::MeleeAttack => 1,
-            CharaDamageKind::RangedAttack => 1,
+        match kind {
+            CharaDamageKind::MeleeAttack | CharaDamageKind::RangedAttack => (),
             _ => {
                 return true;
             } // Some kind damage always hits
         };
 
         let target = gd.chara.get(target_id);
-        calc_evasion_power(
-            equip,
-            target.skill_level(SkillKind::Evasion),
-            target.attr.dex,
-        )
+        RULES
+            .combat
+            .evasion_power(target.skill_level(SkillKind::Evasion), target.attr.dex)
     };
 
-    let d = accuracy_power - evasion_power;
-    let p = 1.0 / (1.0 + (-d * 0.125).exp());
+    let p = RULES.combat.hit_probability(accuracy_power, evasion_power);
     let is_hit = rng::get_rng().gen_bool(p.into());
 
     if !is_hit && game_log_cfg().combat_log.attack() {
@@ -204,6 +196,48 @@ fn hit_judge(
     is_hit
 }
 
+/// Judge critical hit. Returns damage multiplier if critical.
+fn critical_judge(
+    gd: &GameData,
+    cid: CharaId,
+    target_id: CharaId,
+    kind: CharaDamageKind,
+) -> Option<f32> {
+    let slot = match kind {
+        CharaDamageKind::MeleeAttack => EquipSlotKind::MeleeWeapon,
+        CharaDamageKind::RangedAttack => EquipSlotKind::RangedWeapon,
+        _ => {
+            return None;
+        }
+    };
+
+    let attacker = gd.chara.get(cid);
+    let weapon_kind = attacker
+        .equip
+        .item(slot, 0)
+        .and_then(|weapon| match weapon.obj().kind {
+            ItemKind::Weapon(weapon_kind) => Some(weapon_kind),
+            _ => None,
+        });
+    let skill_kind = weapon_kind
+        .map(SkillKind::from)
+        .unwrap_or(SkillKind::BareHands);
+
+    let p = RULES.combat.critical_probability(
+        attacker.attr.dex,
+        attacker.skill_level(skill_kind),
+        weapon_kind,
+    );
+    if !rng::get_rng().gen_bool(p.into()) {
+        return None;
+    }
+
+    if game_log_cfg().combat_log.attack() {
+        game_log_i!("attack-critical"; chara=gd.chara.get(target_id));
+    }
+    Some(RULES.combat.critical_multiplier(weapon_kind))
+}
+
 pub fn weapon_to_effect(item: &Item) -> Effect {
     let item_obj = gobj::get_obj(item.idx);
 
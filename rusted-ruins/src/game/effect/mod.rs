@@ -14,6 +14,7 @@ use common::gamedata::*;
 use common::gobj;
 use common::objholder::TileIdx;
 use geom::*;
+use rules::RULES;
 
 pub fn do_effect<T: Into<Target>>(
     game: &mut Game<'_>,
@@ -23,7 +24,7 @@ pub fn do_effect<T: Into<Target>>(
     power: f32,
     hit_power: f32,
 ) {
-    let target = target.into();
+    let target = target_pos(game, target.into());
     // Target tiles
     let tiles = get_tiles(game, effect, target, cause);
     // Target characters
@@ -36,6 +37,11 @@ pub fn do_effect<T: Into<Target>>(
                     self::recover::recover_hp(game, *cid, power);
                 }
             }
+            EffectKind::RecoverMp => {
+                for cid in &cids {
+                    self::recover::recover_mp(game, *cid, power);
+                }
+            }
             EffectKind::Melee { element } => {
                 for cid in &cids {
                     self::attack::melee_attack(
@@ -74,7 +80,7 @@ pub fn do_effect<T: Into<Target>>(
             }
             EffectKind::Status { status } => {
                 for cid in &cids {
-                    cause_status(game, *cid, power, *status);
+                    cause_status(game, *cid, power, status);
                 }
             }
             EffectKind::WallDamage => {
@@ -128,13 +134,18 @@ pub fn do_effect<T: Into<Target>>(
             }
         }
         EffectAnimKind::Shot => {
-            if !effect.anim_img_shot.is_empty() {
+            if !effect.anim_img_shot.is_empty() && !tiles.is_empty() {
                 let start = game
                     .gd
                     .chara_pos(cause.unwrap())
                     .expect("chara position search error");
+                // Beams fly to the end of the line, other shots fly to the target tile
+                let end = match effect.shape {
+                    ShapeKind::Line => *tiles.last().unwrap(),
+                    _ => target.unwrap_or(tiles[0]),
+                };
                 let idx = gobj::id_to_idx(&effect.anim_img_shot);
-                game.anim_queue.push(Animation::shot(idx, start, tiles[0]));
+                game.anim_queue.push(Animation::shot(idx, start, end));
             }
         }
     }
@@ -161,48 +172,27 @@ fn get_cids(game: &Game<'_>, _effect: &Effect, tiles: &[Vec2d]) -> Vec<CharaId>
 fn get_tiles(
     game: &Game<'_>,
     effect: &Effect,
-    target: Target,
+    target: Option<Vec2d>,
     cause: Option<CharaId>,
 ) -> Vec<Vec2d> {
     let cause = cause.map(|cause| game.gd.chara_pos(cause)).flatten();
-    let target = match target {
-        Target::None => {
-            return vec![];
-        }
-        Target::Tile(pos) => pos,
-        Target::Chara(cid) => {
-            if let Some(pos) = game.gd.chara_pos(cid) {
-                pos
-            } else {
-                return vec![];
-            }
-        }
-    };
-    let map = game.gd.get_current_map();
-    if let Some(shape) = to_shape(effect, target, cause) {
-        shape
-            .iter()
-            .into_iter()
-            .filter(|pos| map.is_inside(*pos))
-            .collect()
+    if let Some(target) = target {
+        effect_area(game.gd.get_current_map(), effect, target, cause)
     } else {
         vec![]
     }
 }
 
-fn to_shape(effect: &Effect, target: Vec2d, _cause: Option<Vec2d>) -> Option<Shape> {
-    match effect.shape {
-        ShapeKind::OneTile => Some(Shape::OneTile { pos: target }),
-        ShapeKind::Line => unimplemented!(),
-        ShapeKind::Circle => Some(Shape::Circle {
-            center: target,
-            radius: effect.size,
-        }),
+fn target_pos(game: &Game<'_>, target: Target) -> Option<Vec2d> {
+    match target {
+        Target::None => None,
+        Target::Tile(pos) => Some(pos),
+        Target::Chara(cid) => game.gd.chara_pos(cid),
     }
 }
 
 // Cause status effect to given chara.
-fn cause_status(game: &mut Game<'_>, cid: CharaId, power: f32, status: StatusEffect) {
+fn cause_status(game: &mut Game<'_>, cid: CharaId, power: f32, status: &StatusEffect) {
     let chara = game.gd.chara.get_mut(cid);
 
     match status {
@@ -220,5 +210,28 @@ fn cause_status(game: &mut Game<'_>, cid: CharaId, power: f32, status: StatusEff
             chara.add_status(CharaStatus::Scanned);
             game_log_i!("scanned"; chara=chara);
         }
+        StatusEffect::Defined { id } => {
+            let status_effect = if let Some(status_effect) = RULES.status_effects.get(id) {
+                status_effect
+            } else {
+                warn!("unknown status effect \"{}\"", id);
+                return;
+            };
+            let turn_left =
+                if status_effect.duration == 0 && status_effect.duration_per_power == 0.0 {
+                    None
+                } else {
+                    let turn_left =
+                        status_effect.duration as f32 + status_effect.duration_per_power * power;
+                    Some((turn_left as u16).max(1))
+                };
+            let status = CharaStatus::Defined {
+                id: id.clone(),
+                turn_left,
+                stack: 1,
+            };
+            game_log_i!("status-inflicted"; chara=chara, status=status);
+            chara.add_status(status);
+        }
     }
 }
@@ -8,3 +8,101 @@ pub fn effect_to_range(effect: &Effect, center: Vec2d) -> Shape {
         radius: effect.range,
     }
 }
+
+/// Get the shape of the area affected by the given effect.
+/// Line and Sector need the position of the cause.
+pub fn effect_to_shape(
+    effect: &Effect,
+    target: Vec2d,
+    cause: Option<Vec2d>,
+    map_size: (u32, u32),
+) -> Option<Shape> {
+    match effect.shape {
+        ShapeKind::OneTile => Some(Shape::OneTile { pos: target }),
+        ShapeKind::Line => {
+            let start = cause?;
+            if start == target {
+                return None;
+            }
+            Some(Shape::Line {
+                start,
+                end: extend_line(start, target, effect.size),
+            })
+        }
+        ShapeKind::Circle => Some(Shape::Circle {
+            center: target,
+            radius: effect.size,
+        }),
+        ShapeKind::Sector { angle } => Some(Shape::Sector {
+            center: cause?,
+            target,
+            radius: effect.size,
+            angle,
+        }),
+        ShapeKind::Ring => Some(Shape::Ring {
+            center: target,
+            radius: effect.size,
+        }),
+        ShapeKind::All => Some(Shape::All {
+            w: map_size.0,
+            h: map_size.1,
+        }),
+    }
+}
+
+/// Get tile positions affected by the given effect on the map.
+/// Lines are stopped by walls, and sectors do not spread behind walls.
+pub fn effect_area(map: &Map, effect: &Effect, target: Vec2d, cause: Option<Vec2d>) -> Vec<Vec2d> {
+    let shape = if let Some(shape) = effect_to_shape(effect, target, cause, map.size()) {
+        shape
+    } else {
+        return vec![];
+    };
+
+    match shape {
+        Shape::Line { start, .. } => {
+            let mut tiles = Vec::new();
+            for pos in shape.iter().into_iter().filter(|pos| *pos != start) {
+                if !map.is_inside(pos) {
+                    break;
+                }
+                tiles.push(pos);
+                if !map.tile[pos].wall.is_empty() {
+                    break;
+                }
+            }
+            tiles
+        }
+        Shape::Sector { center, .. } => shape
+            .iter()
+            .into_iter()
+            .filter(|pos| *pos != center && map.is_inside(*pos) && is_reachable(map, center, *pos))
+            .collect(),
+        _ => shape
+            .iter()
+            .into_iter()
+            .filter(|pos| map.is_inside(*pos))
+            .collect(),
+    }
+}
+
+/// Extend the line from start to target until its length becomes len.
+/// If len is zero, the line ends at target.
+fn extend_line(start: Vec2d, target: Vec2d, len: u32) -> Vec2d {
+    if len == 0 {
+        return target;
+    }
+    let d = target - start;
+    let l = std::cmp::max(d.0.abs(), d.1.abs()) as f32;
+    let len = len as f32;
+    let dx = (d.0 as f32 * len / l).round() as i32;
+    let dy = (d.1 as f32 * len / l).round() as i32;
+    start + Vec2d(dx, dy)
+}
+
+/// There are no walls between from and to, excluding the both ends.
+fn is_reachable(map: &Map, from: Vec2d, to: Vec2d) -> bool {
+    LineIter::new(from, to)
+        .filter(|pos| *pos != from && *pos != to)
+        .all(|pos| map.is_inside(pos) && map.tile[pos].wall.is_empty())
+}
@@ -1,3 +1,4 @@
+use crate::text::misc_txt;
 use common::gamedata::*;
 use rules::RULES;
 
@@ -24,3 +25,60 @@ impl Faction {
         }
     }
 }
+
+/// Change the player's relation to the faction.
+/// The change is propagated to the factions that have a stance to it.
+pub fn change_relation(gd: &mut GameData, faction: FactionId, value: i16) {
+    change_relation_without_propagation(gd, faction, value);
+
+    for (other, stance) in RULES.faction.stances(faction) {
+        let value = (value as f32 * RULES.faction.propagation.get(stance)) as i16;
+        if value != 0 {
+            change_relation_without_propagation(gd, other, value);
+        }
+    }
+}
+
+fn change_relation_without_propagation(gd: &mut GameData, faction: FactionId, value: i16) {
+    let old = gd.faction.get(faction);
+    gd.faction.change(faction, value);
+    let new = gd.faction.get(faction);
+
+    let old_rank = &RULES.faction.rank(old).id;
+    let new_rank = &RULES.faction.rank(new).id;
+    if old_rank != new_rank {
+        let rank = rank_text(new);
+        game_log!("faction-rank-changed"; faction=faction, rank=rank);
+    }
+
+    if old >= RULES.faction.relation_neutral && new < RULES.faction.relation_neutral {
+        turn_hostile(gd, faction);
+    }
+}
+
+/// Characters of the faction on the current map start to attack the player
+fn turn_hostile(gd: &mut GameData, faction: FactionId) {
+    game_log!("faction-turn-hostile"; faction=faction);
+
+    for cid in gd.get_charas_on_map() {
+        let chara = gd.chara.get_mut(cid);
+        if chara.faction == faction && !chara.ai.state.is_combat() {
+            chara.ai.state = AiState::Combat {
+                target: CharaId::Player,
+            };
+        }
+    }
+}
+
+/// Text of the reputation rank for the given relation
+pub fn rank_text(relation: FactionRelation) -> String {
+    misc_txt(&format!(
+        "reputation_rank-{}",
+        RULES.faction.rank(relation).id
+    ))
+}
+
+/// Returns true if the character refuses to trade with the player
+pub fn refuses_trade(gd: &GameData, cid: CharaId) -> bool {
+    gd.faction.get(gd.chara.get(cid).faction) < RULES.faction.relation_shop_refuse
+}
@@ -60,7 +60,10 @@ impl GameData {
         let tile = &map.tile[self.player_pos()];
         matches!(
             tile.special,
-            SpecialTileKind::Stairs { .. } | SpecialTileKind::SiteSymbol { .. }
+            SpecialTileKind::Stairs { .. }
+                | SpecialTileKind::SiteSymbol { .. }
+                | SpecialTileKind::Port { .. }
+                | SpecialTileKind::Portal { .. }
         )
     }
 
@@ -209,7 +212,7 @@ impl GameData {
         let map = self.get_current_map();
 
         for p in LineIter::new(p0, p1) {
-            if !map.tile[p].wall.is_empty() {
+            if crate::game::view::blocks_view(&map.tile[p]) {
                 return false;
             }
         }
@@ -124,10 +124,10 @@ impl ItemFilter {
         self
     }
 
-    // pub fn flags(mut self, flags: ItemFlags) -> ItemFilter {
-    //     self.flags = flags;
-    //     self
-    // }
+    pub fn flags(mut self, flags: ItemFlags) -> ItemFilter {
+        self.flags = flags;
+        self
+    }
 
     pub fn kind_rough(mut self, kind_rough: ItemKindRough) -> ItemFilter {
         self.kind_rough = Some(kind_rough);
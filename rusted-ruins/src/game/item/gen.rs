@@ -7,10 +7,9 @@ use common::objholder::ItemIdx;
 use rng::SliceRandom;
 use rules::RULES;
 
-/// Generate new item on dungeon floor
-pub fn gen_dungeon_item(floor_level: u32, dungeon_kind: DungeonKind) -> Option<Item> {
-    let gen_rule = RULES.dungeon_gen.get(&dungeon_kind)?;
-    let (_, (item_selector, _)) = rng::choose(&gen_rule.item_gen_weight, |(_, weight)| *weight)?;
+/// Generate new item by the weight for each ItemSelector
+pub fn gen_item_by_weight(floor_level: u32, weight: &[(ItemSelector, f32)]) -> Option<Item> {
+    let (_, (item_selector, _)) = rng::choose(weight, |(_, weight)| *weight)?;
     let item_selector = item_selector.clone().level(floor_level);
     let item_idx = choose_item_by_item_selector(&item_selector)?;
     Some(gen_item_from_idx(item_idx, floor_level))
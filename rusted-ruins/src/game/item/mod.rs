@@ -143,6 +143,15 @@ impl Item {
         None
     }
 
+    /// The faction this item was stolen from
+    fn stolen(&self) -> Option<FactionId> {
+        find_attr!(self, ItemAttr::Stolen(faction) => faction).copied()
+    }
+
+    fn is_quest_item(&self) -> bool {
+        self.attrs.contains(&ItemAttr::QuestItem)
+    }
+
     fn title(&self) -> Option<&str> {
         for attr in &self.attrs {
             if let ItemAttr::Title(title) = attr {
@@ -168,27 +177,8 @@ impl Item {
 
     /// Update Time attribute. Returns true if remaining reaches zero.
     fn update_time(&mut self, update_remaining: bool) -> bool {
-        crate::game::time::current_time();
-        if let Some(&mut ItemTime {
-            ref mut last_updated,
-            ref mut remaining,
-        }) = self.time.as_mut()
-        {
-            let current_time = crate::game::time::current_time();
-            let since_last_updated = current_time.duration_from(*last_updated);
-            *last_updated = current_time;
-
-            if !update_remaining {
-                return false;
-            }
-
-            let new_remaining = remaining
-                .as_secs()
-                .saturating_sub(since_last_updated.as_secs());
-
-            *remaining = Duration::from_seconds(new_remaining);
-
-            new_remaining == 0
+        if let Some(time) = self.time.as_mut() {
+            time.update(update_remaining)
         } else {
             false
         }
@@ -207,20 +197,48 @@ impl Item {
 
     /// Reset Time attribute.
     fn reset_time(&mut self, new_remaining: Duration) {
-        let current_time = crate::game::time::current_time();
-        if let Some(&mut ItemTime {
-            ref mut last_updated,
-            ref mut remaining,
-        }) = self.time.as_mut()
-        {
-            *last_updated = current_time;
-            *remaining = new_remaining;
+        if let Some(time) = self.time.as_mut() {
+            time.reset(new_remaining);
         } else {
-            self.time = Some(ItemTime {
-                last_updated: current_time,
-                remaining: new_remaining,
-            });
+            self.time = Some(ItemTime::from_now(new_remaining));
+        }
+    }
+}
+
+#[extend::ext(pub)]
+impl ItemTime {
+    /// Create a new time stamp starting from the current time
+    fn from_now(remaining: Duration) -> ItemTime {
+        ItemTime {
+            remaining,
+            last_updated: crate::game::time::current_time(),
+        }
+    }
+
+    /// Update the time stamp. Returns true if remaining reaches zero.
+    /// If update_remaining is false, the remaining time is not decreased.
+    fn update(&mut self, update_remaining: bool) -> bool {
+        let current_time = crate::game::time::current_time();
+        let since_last_updated = current_time.duration_from(self.last_updated);
+        self.last_updated = current_time;
+
+        if !update_remaining {
+            return false;
         }
+
+        let new_remaining = self
+            .remaining
+            .as_secs()
+            .saturating_sub(since_last_updated.as_secs());
+
+        self.remaining = Duration::from_seconds(new_remaining);
+
+        new_remaining == 0
+    }
+
+    fn reset(&mut self, new_remaining: Duration) {
+        self.last_updated = crate::game::time::current_time();
+        self.remaining = new_remaining;
     }
 }
 
@@ -384,6 +402,7 @@ impl GameData {
 
                 find_attr!(container_item.obj(), ItemObjAttr::Container { capacity, .. } => capacity).copied()
             }
+            ItemListLocation::BaseStorage { .. } => Some(RULES.base.storage_capacity),
             _ => None,
         }
     }
@@ -418,8 +437,13 @@ pub fn change_equipment(
 }
 
 pub fn remove_equipment(gd: &mut GameData, cid: CharaId, slot: (EquipSlotKind, u8)) {
+    // Equipment removed from party members is passed to the player
+    let ill = if gd.player.party.contains(&cid) {
+        ItemListLocation::PLAYER
+    } else {
+        ItemListLocation::Chara { cid }
+    };
     if let Some(removed_equipment) = gd.get_equip_list_mut(cid).remove(slot.0, slot.1 as usize) {
-        gd.get_item_list_mut(ItemListLocation::Chara { cid })
-            .append(removed_equipment, 1);
+        gd.get_item_list_mut(ill).append(removed_equipment, 1);
     }
 }
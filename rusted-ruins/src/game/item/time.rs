@@ -22,6 +22,8 @@ pub fn update_item_time(gd: &mut GameData) {
 
         update_item_list_time(gd, ill);
     }
+
+    crate::game::livestock::update_livestock(gd);
 }
 
 #[derive(Debug, Default)]
@@ -0,0 +1,347 @@
+//! Tameable creatures and livestock kept at player bases
+
+use crate::game::base::is_player_base;
+use crate::game::extrait::*;
+use crate::game::item::gen::gen_item_from_id;
+use crate::game::InfoGetter;
+use common::gamedata::*;
+use common::gobj;
+use common::objholder::{ItemIdx, TileIdx};
+use geom::*;
+use rules::livestock::Animal;
+use rules::RULES;
+
+/// Returns the rule if given character can be tamed
+fn animal_rule(chara: &Chara) -> Option<&'static Animal> {
+    RULES.livestock.animals.get(gobj::idx_to_id(chara.idx))
+}
+
+pub fn is_tameable(gd: &GameData, cid: CharaId) -> bool {
+    if cid == CharaId::Player || gd.player.party.contains(&cid) {
+        return false;
+    }
+    let chara = gd.chara.get(cid);
+    chara.livestock.is_none() && animal_rule(chara).is_some()
+}
+
+pub fn is_livestock(gd: &GameData, cid: CharaId) -> bool {
+    gd.chara.get(cid).livestock.is_some()
+}
+
+/// The first floor of a player base. Bases in the current region are preferred.
+fn player_base_map(gd: &GameData) -> Option<MapId> {
+    let rid = gd.get_current_region_id();
+    let mut base = None;
+    gd.region.visit_all_sites(|sid, site| {
+        if matches!(site.content, SiteContent::Player { .. }) && (base.is_none() || sid.rid == rid)
+        {
+            base = Some(MapId::site_first_floor(sid));
+        }
+    });
+    base
+}
+
+fn pen_tiles() -> Vec<TileIdx> {
+    RULES
+        .livestock
+        .pen_tiles
+        .iter()
+        .filter_map(|id| gobj::id_to_idx_checked(id))
+        .collect()
+}
+
+fn is_pen(map: &Map, pos: Vec2d, pen_tiles: &[TileIdx]) -> bool {
+    pen_tiles.contains(&map.tile[pos].main_tile())
+}
+
+fn new_livestock(animal: &Animal) -> Livestock {
+    Livestock {
+        product_time: ItemTime::from_now(animal.product_duration),
+        breed_time: ItemTime::from_now(animal.breed_duration),
+        food_time: ItemTime::from_now(RULES.livestock.food_duration),
+    }
+}
+
+/// Try to tame a creature with the required item and Animals skill.
+/// A tamed creature is sent to the player's base.
+/// Returns false if the player cannot try.
+pub fn tame(gd: &mut GameData, cid: CharaId) -> bool {
+    let animal = if let Some(animal) = animal_rule(gd.chara.get(cid)) {
+        animal
+    } else {
+        return false;
+    };
+    let base_mid = if let Some(mid) = player_base_map(gd) {
+        mid
+    } else {
+        game_log!("livestock-no-base");
+        return false;
+    };
+    let il = if let Some(il) = gd.player_item_location(&animal.tame_item) {
+        il
+    } else {
+        let item = crate::text::obj_txt(&animal.tame_item);
+        game_log!("livestock-tame-no-item"; item=item);
+        return false;
+    };
+    gd.remove_item(il, 1);
+    gd.chara.get_mut(CharaId::Player).update();
+
+    let lv = gd.chara.get(cid).lv.max(1);
+    let skill_level = gd
+        .chara
+        .get(CharaId::Player)
+        .skill_level(SkillKind::Animals);
+    let p = (skill_level as f32 * RULES.livestock.tame_factor / lv as f32)
+        .min(RULES.livestock.tame_prob_max);
+
+    if !rng::gen_bool(p) {
+        game_log!("livestock-tame-failure"; chara=gd.chara.get(cid));
+        return true;
+    }
+
+    gd.chara.get_mut(CharaId::Player).add_skill_exp(
+        SkillKind::Animals,
+        RULES.livestock.exp_tame,
+        lv,
+    );
+    game_log!("livestock-tame"; chara=gd.chara.get(cid));
+
+    let chara = gd.chara.get_mut(cid);
+    chara.faction = FactionId::player();
+    chara.ai.state = AiState::Normal;
+    chara.trigger_talk = None;
+    chara.livestock = Some(Box::new(new_livestock(animal)));
+
+    if gd.get_current_mapid() != base_mid {
+        send_to_base(gd, cid, base_mid);
+    }
+    true
+}
+
+fn send_to_base(gd: &mut GameData, cid: CharaId, mid: MapId) {
+    gd.region
+        .preload_map(mid, crate::game::saveload::get_map_dir(gd));
+
+    let pen_tiles = pen_tiles();
+    let map = gd.region.get_map(mid);
+    let pos = map
+        .tile
+        .iter_idx()
+        .find(|pos| is_pen(map, *pos, &pen_tiles) && map.is_empty_tile(*pos))
+        .or_else(|| {
+            let entrance = map.entrance.first().copied().unwrap_or(Vec2d(0, 0));
+            map.empty_tile_around(entrance)
+        });
+    // The animal stays on the current map if there is no room in the base
+    let pos = if let Some(pos) = pos {
+        pos
+    } else {
+        return;
+    };
+
+    let chara = if let Some(chara) = gd.remove_chara(cid) {
+        chara
+    } else {
+        return;
+    };
+    game_log!("livestock-sent-to-base"; chara=chara);
+
+    let cid = gd.add_chara_to_map(chara, mid);
+    gd.region.get_map_mut(mid).locate_chara(cid, pos);
+}
+
+/// Feed a hungry livestock with food in the player's inventory
+pub fn feed(gd: &mut GameData, cid: CharaId) -> bool {
+    let animal = if let Some(animal) = animal_rule(gd.chara.get(cid)) {
+        animal
+    } else {
+        return false;
+    };
+
+    let ill = ItemListLocation::PLAYER;
+    let i = if let Some(i) = find_food(gd.get_item_list(ill), animal) {
+        i
+    } else {
+        game_log!("livestock-no-food"; chara=gd.chara.get(cid));
+        return false;
+    };
+    let item = gd.remove_item_and_get((ill, i), 1);
+    game_log!("livestock-feed"; chara=gd.chara.get(cid), item=item);
+    gd.chara.get_mut(CharaId::Player).update();
+
+    if let Some(livestock) = gd.chara.get_mut(cid).livestock.as_mut() {
+        livestock.food_time.reset(RULES.livestock.food_duration);
+    }
+    true
+}
+
+fn is_food(item: &Item, animal: &Animal) -> bool {
+    if animal.foods.is_empty() {
+        item.obj().kind == ItemKind::Food
+    } else {
+        let id = gobj::idx_to_id(item.idx);
+        animal.foods.iter().any(|food| food == id)
+    }
+}
+
+fn find_food(item_list: &ItemList, animal: &Animal) -> Option<u32> {
+    item_list
+        .iter()
+        .position(|(item, _)| is_food(item, animal))
+        .map(|i| i as u32)
+}
+
+/// Update tamed creatures on the current map.
+/// They produce items and breed only while they are fed and in pens at player bases.
+pub fn update_livestock(gd: &mut GameData) {
+    let mid = gd.get_current_mapid();
+    if !is_player_base(gd, mid) {
+        return;
+    }
+    let pen_tiles = pen_tiles();
+    let cids: Vec<CharaId> = gd
+        .get_charas_on_map()
+        .into_iter()
+        .filter(|cid| is_livestock(gd, *cid))
+        .collect();
+
+    let mut breeders = Vec::new();
+    for &cid in &cids {
+        if update_animal(gd, cid, &pen_tiles) {
+            breeders.push(cid);
+        }
+    }
+
+    let n_pen_tiles = {
+        let map = gd.get_current_map();
+        map.tile
+            .iter_idx()
+            .filter(|pos| is_pen(map, *pos, &pen_tiles))
+            .count() as u32
+    };
+    let mut n_animals = cids.len() as u32;
+
+    for cid in breeders {
+        if n_pen_tiles < (n_animals + 1) * RULES.livestock.pen_tiles_per_animal {
+            break;
+        }
+        if breed(gd, cid, &cids, &pen_tiles) {
+            n_animals += 1;
+        }
+    }
+}
+
+/// Update timers of a livestock. Returns true if it has a breeding chance.
+fn update_animal(gd: &mut GameData, cid: CharaId, pen_tiles: &[TileIdx]) -> bool {
+    let animal = if let Some(animal) = animal_rule(gd.chara.get(cid)) {
+        animal
+    } else {
+        return false;
+    };
+    let pos = if let Some(pos) = gd.chara_pos(cid) {
+        pos
+    } else {
+        return false;
+    };
+    let in_pen = is_pen(gd.get_current_map(), pos, pen_tiles);
+
+    // Eat food on the near tiles when hungry
+    let livestock = gd.chara.get_mut(cid).livestock.as_mut().unwrap();
+    if livestock.food_time.update(true) && eat_near_food(gd, pos, animal) {
+        let livestock = gd.chara.get_mut(cid).livestock.as_mut().unwrap();
+        livestock.food_time.reset(RULES.livestock.food_duration);
+    }
+
+    let livestock = gd.chara.get_mut(cid).livestock.as_mut().unwrap();
+    let active = in_pen && !livestock.food_time.remaining.is_zero();
+    let produced = livestock.product_time.update(active);
+    let breedable = livestock.breed_time.update(active);
+    if produced {
+        livestock.product_time.reset(animal.product_duration);
+        produce(gd, cid, pos, animal);
+    }
+    if breedable {
+        let livestock = gd.chara.get_mut(cid).livestock.as_mut().unwrap();
+        livestock.breed_time.reset(animal.breed_duration);
+    }
+    breedable
+}
+
+fn eat_near_food(gd: &mut GameData, center: Vec2d, animal: &Animal) -> bool {
+    let mid = gd.get_current_mapid();
+    for pos in RectIter::new(center - (1, 1), center + (1, 1)) {
+        if !gd.get_current_map().is_inside(pos) {
+            continue;
+        }
+        let ill = ItemListLocation::OnMap { mid, pos };
+        if let Some(i) = find_food(gd.get_item_list(ill), animal) {
+            gd.remove_item((ill, i), 1);
+            return true;
+        }
+    }
+    false
+}
+
+fn produce(gd: &mut GameData, cid: CharaId, pos: Vec2d, animal: &Animal) {
+    let mid = gd.get_current_mapid();
+    let lv = gd.chara.get(cid).lv;
+    for (id, n) in &animal.products {
+        if gobj::id_to_idx_checked::<ItemIdx>(id).is_none() {
+            warn!("unknown livestock product \"{}\"", id);
+            continue;
+        }
+        let item = gen_item_from_id(id, lv);
+        gd.get_item_list_mut(ItemListLocation::OnMap { mid, pos })
+            .append(item, *n);
+    }
+    gd.chara.get_mut(CharaId::Player).add_skill_exp(
+        SkillKind::Animals,
+        RULES.livestock.exp_product,
+        lv,
+    );
+}
+
+/// Breed with another fed livestock of the same kind in pens
+fn breed(gd: &mut GameData, cid: CharaId, cids: &[CharaId], pen_tiles: &[TileIdx]) -> bool {
+    let chara = gd.chara.get(cid);
+    let idx = chara.idx;
+    let animal = animal_rule(chara).unwrap();
+
+    let has_partner = cids.iter().any(|&other| {
+        if other == cid {
+            return false;
+        }
+        let other_chara = gd.chara.get(other);
+        other_chara.idx == idx
+            && other_chara
+                .livestock
+                .as_ref()
+                .map(|livestock| !livestock.food_time.remaining.is_zero())
+                .unwrap_or(false)
+            && gd
+                .chara_pos(other)
+                .map(|pos| is_pen(gd.get_current_map(), pos, pen_tiles))
+                .unwrap_or(false)
+    });
+    if !has_partner || !rng::gen_bool(animal.breed_prob) {
+        return false;
+    }
+
+    let parent_pos = gd.chara_pos(cid).unwrap();
+    let pos = if let Some(pos) = gd.get_current_map().empty_tile_around(parent_pos) {
+        pos
+    } else {
+        return false;
+    };
+
+    let lv = gd.chara.get(cid).lv;
+    let mut chara = crate::game::chara::gen::create_chara(idx, lv, FactionId::player(), None);
+    chara.livestock = Some(Box::new(new_livestock(animal)));
+    game_log!("livestock-born"; chara=chara);
+
+    let mid = gd.get_current_mapid();
+    let cid = gd.add_chara_to_map(chara, mid);
+    gd.get_current_map_mut().locate_chara(cid, pos);
+    true
+}
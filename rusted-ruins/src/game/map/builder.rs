@@ -1,8 +1,10 @@
+use super::vault::{self, PlacedVault};
 use crate::map_generator::*;
 use common::gamedata::map::*;
 use common::gobj;
 use common::objholder::*;
 use geom::*;
+use rules::dungeon_gen::VaultParams;
 use rules::RULES;
 
 #[derive(Default)]
@@ -10,11 +12,20 @@ pub struct MapBuilder {
     floor: u32,
     is_deepest_floor: bool,
     map_gen_param: MapGenParam,
+    requirements: Option<MapRequirements>,
     tile: TileIdx,
     wall: WallIdx,
     map_boundary: Option<MapBoundary>,
     music: String,
     entrance_method: EntranceMethod,
+    vaults: Vec<VaultParams>,
+}
+
+/// Built map and the information from map generation
+pub struct BuiltMap {
+    pub map: Map,
+    pub vaults: Vec<PlacedVault>,
+    pub rooms: Vec<RoomRect>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -42,11 +53,23 @@ impl MapBuilder {
         let (w, h) = map_gen_param.size();
         let mut builder = Self::new(w, h);
         builder.map_gen_param = map_gen_param.clone();
+        builder.requirements = RULES.map_gen.requirements.get(id).cloned();
         builder
     }
 
     pub fn build(self) -> Map {
-        let generated_map = self.map_gen_param.generate();
+        self.build_detail().map
+    }
+
+    /// Build the map with the information from map generation
+    pub fn build_detail(self) -> BuiltMap {
+        let mut generated_map = if let Some(requirements) = &self.requirements {
+            self.map_gen_param.generate_with_requirements(requirements)
+        } else {
+            self.map_gen_param.generate()
+        };
+        let vaults = vault::place_vaults(&mut generated_map, &self.vaults, self.floor);
+        let rooms = generated_map.rooms.clone();
         let mut map = generated_map_to_map(
             generated_map,
             self.tile,
@@ -59,7 +82,10 @@ impl MapBuilder {
             map.boundary = map_boundary;
         }
         map.music = self.music;
-        map
+        for v in &vaults {
+            vault::stamp_to_map(&mut map, v);
+        }
+        BuiltMap { map, vaults, rooms }
     }
 
     pub fn floor(mut self, floor: u32) -> MapBuilder {
@@ -96,6 +122,11 @@ impl MapBuilder {
         self.music = music.to_owned();
         self
     }
+
+    pub fn vaults(mut self, vaults: &[VaultParams]) -> MapBuilder {
+        self.vaults = vaults.to_vec();
+        self
+    }
 }
 
 pub fn generated_map_to_map(
@@ -109,6 +140,8 @@ pub fn generated_map_to_map(
     let size = gm.size;
     let mut map = Map::new(size.0 as u32, size.1 as u32);
     let wall_obj = gobj::get_obj(wall);
+    // Door tiles become floors if doors cannot be drawn
+    let gen_doors = crate::game::door::door_objects_available();
 
     trace!("New map creating");
 
@@ -132,6 +165,8 @@ pub fn generated_map_to_map(
             };
             map.tile[p].wall = WallIdxPp::with_piece_pattern(wall, piece_pattern);
             map.tile[p].wall_hp = wall_obj.hp;
+        } else if gm.tile[p] == TileKind::Door && gen_doors {
+            map.tile[p].door = Some(crate::game::door::gen_door(floor));
         }
     }
 
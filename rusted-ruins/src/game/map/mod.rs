@@ -3,29 +3,43 @@ pub mod from_template;
 pub mod search;
 pub mod tile_info;
 mod update;
+pub mod vault;
 pub mod wall_damage;
 pub mod wilderness;
 
-use super::chara::gen::create_npc_chara;
+use super::chara::gen::create_npc_chara_by_race;
 use super::extrait::*;
-use super::item::gen::gen_dungeon_item;
+use super::item::gen::gen_item_by_weight;
 use super::Game;
 use crate::text::ToText;
 use common::basic::MAX_ITEM_FOR_DRAW;
 use common::gamedata::*;
 use common::gobj;
+use common::item_selector::ItemSelector;
 use common::obj::TileKind;
 use geom::*;
 use rules::RULES;
+use std::collections::HashMap;
 
 #[extend::ext(pub)]
 impl Map {
     /// The tile is passable for given character or not.
-    fn is_passable(&self, _chara: &Chara, pos: Vec2d) -> bool {
+    /// Characters avoid traps of their own faction.
+    fn is_passable(&self, chara: &Chara, pos: Vec2d) -> bool {
         if !self.is_inside(pos) {
             return false;
         }
 
+        if self.tile[pos].special.is_trap_of(chara.faction) {
+            return false;
+        }
+
+        if let Some(door) = self.tile[pos].door {
+            if door.state.is_closed() {
+                return false;
+            }
+        }
+
         if self.tile[pos].wall.is_empty() {
             let tile = gobj::get_obj(self.tile[pos].main_tile());
             match tile.kind {
@@ -37,6 +51,18 @@ impl Map {
         }
     }
 
+    /// The tile has a closed door which is not locked
+    fn can_open_door(&self, pos: Vec2d) -> bool {
+        self.is_inside(pos)
+            && matches!(
+                self.tile[pos].door,
+                Some(Door {
+                    state: DoorState::Closed,
+                    ..
+                })
+            )
+    }
+
     fn move_chara(&mut self, cid: CharaId, dir: Direction) -> bool {
         if let Some(p) = self.chara_pos(cid) {
             let new_p = p + dir.as_vec();
@@ -64,7 +90,8 @@ impl Map {
             observed_tile.tile = true;
             observed_tile.wall = tile.wall;
             observed_tile.deco = tile.deco;
-            observed_tile.special = tile.special;
+            observed_tile.special = tile.special.observed();
+            observed_tile.door = tile.door.map(|door| door.state);
             observed_tile.items.clear();
 
             for &(ref item, _) in tile.item_list.iter().take(MAX_ITEM_FOR_DRAW) {
@@ -108,7 +135,8 @@ impl Map {
 
 /// Function to determine the tile is empty or not
 fn is_empty_tile(tile: &TileInfo) -> bool {
-    if tile.wall.is_empty() && tile.chara.is_none() && tile.special.is_none() {
+    if tile.wall.is_empty() && tile.chara.is_none() && tile.special.is_none() && tile.door.is_none()
+    {
         let tile_idx = tile.main_tile();
         let tile_obj = gobj::get_obj(tile_idx);
         tile_obj.kind == TileKind::Ground
@@ -170,6 +198,8 @@ pub fn switch_map(game: &mut Game<'_>, destination: Destination) {
         }
     }
 
+    crate::game::quest::arrive_at_map(gd);
+
     crate::audio::play_sound("floor-change");
     crate::audio::play_music(&gd.get_current_map().music);
     update::update_map(game);
@@ -216,6 +246,7 @@ pub fn destination_to_mid(gd: &GameData, dest: Destination) -> MapId {
         Destination::MapId(mid) => mid,
         Destination::MapIdWithPos(mid, _) => mid,
         Destination::MapIdWithEntrance(mid, _) => mid,
+        Destination::Ship(rid) | Destination::Portal(rid) => MapId::RegionMap { rid },
     }
 }
 
@@ -245,6 +276,19 @@ pub fn destination_to_pos(gd: &GameData, dest: Destination) -> Vec2d {
             let map = gd.region.get_map(new_mid);
             Some(map.entrance[entrance as usize])
         }
+        Destination::Ship(rid) | Destination::Portal(rid) => {
+            // Arrive at the port or portal linked to the previous region
+            let prev_rid = prev_mid.rid();
+            let map = gd.region.get_map(new_mid);
+            let pos = map.tile.iter_idx().find(|pos| {
+                matches!(
+                    (dest, map.tile[*pos].special),
+                    (Destination::Ship(_), SpecialTileKind::Port { rid })
+                    | (Destination::Portal(_), SpecialTileKind::Portal { rid }) if rid == prev_rid
+                )
+            });
+            Some(pos.unwrap_or_else(|| crate::game::region::start_pos(gd, rid)))
+        }
     };
     if let Some(pos) = pos {
         return pos;
@@ -264,15 +308,27 @@ pub fn destination_to_pos(gd: &GameData, dest: Destination) -> Vec2d {
 
 pub fn gen_npcs(gd: &mut GameData, mid: MapId, n: u32, floor_level: u32) {
     let dungeon_kind = match gd.region.get_site(mid.sid()).content {
-        SiteContent::AutoGenDungeon { dungeon_kind } => dungeon_kind,
+        SiteContent::AutoGenDungeon { dungeon_kind, .. } => dungeon_kind,
         _ => {
             return;
         }
     };
+    let nrp = &RULES.dungeon_gen[&dungeon_kind].npc_race_probability;
+    gen_npcs_by_race(gd, mid, n, floor_level, nrp, choose_empty_tile);
+}
 
+/// Generate npcs of given races on the tiles chosen by choose_tile
+pub fn gen_npcs_by_race<F: Fn(&Map) -> Option<Vec2d>>(
+    gd: &mut GameData,
+    mid: MapId,
+    n: u32,
+    floor_level: u32,
+    nrp: &HashMap<String, f32>,
+    choose_tile: F,
+) {
     for _ in 0..n {
-        if let Some(p) = choose_empty_tile(gd.region.get_map(mid)) {
-            if let Some(chara) = create_npc_chara(dungeon_kind, floor_level) {
+        if let Some(p) = choose_tile(gd.region.get_map(mid)) {
+            if let Some(chara) = create_npc_chara_by_race(nrp, floor_level) {
                 trace!("Generate new npc {}", chara.to_text());
                 let cid = gd.add_chara_to_map(chara, mid);
                 let map = gd.region.get_map_mut(mid);
@@ -319,17 +375,27 @@ pub fn choose_empty_tile(map: &Map) -> Option<Vec2d> {
 
 /// Locate some items for a new map
 pub fn gen_items(gd: &mut GameData, mid: MapId) {
-    use rng::*;
     let dungeon_kind = {
         let site = gd.region.get_site(mid.sid());
         match site.content {
-            SiteContent::AutoGenDungeon { dungeon_kind } => dungeon_kind,
+            SiteContent::AutoGenDungeon { dungeon_kind, .. } => dungeon_kind,
             _ => {
                 return;
             } // No item generation
         }
     };
-    let item_gen_probability = RULES.dungeon_gen[&dungeon_kind].item_gen_probability;
+    let rule = &RULES.dungeon_gen[&dungeon_kind];
+    gen_items_by_weight(gd, mid, rule.item_gen_probability, &rule.item_gen_weight);
+}
+
+/// Locate items generated by given weight for each ItemSelector
+pub fn gen_items_by_weight(
+    gd: &mut GameData,
+    mid: MapId,
+    item_gen_probability: f64,
+    item_gen_weight: &[(ItemSelector, f32)],
+) {
+    use rng::*;
     let item_gen_probability = if (0.0..=1.0).contains(&item_gen_probability) {
         item_gen_probability
     } else {
@@ -348,7 +414,7 @@ pub fn gen_items(gd: &mut GameData, mid: MapId) {
         }
 
         if get_rng().gen_bool(item_gen_probability) {
-            if let Some(item) = gen_dungeon_item(mid.floor(), dungeon_kind) {
+            if let Some(item) = gen_item_by_weight(mid.floor(), item_gen_weight) {
                 map.locate_item(item, p, 1);
             }
         }
@@ -19,7 +19,10 @@ pub fn tile_info_query(gd: &GameData, pos: Vec2d) -> TileInfoQuery {
     let tinfo = &map.observed_tile[pos];
 
     let move_symbol = match tinfo.special {
-        SpecialTileKind::Stairs { .. } | SpecialTileKind::SiteSymbol { .. } => Some(tinfo.special),
+        SpecialTileKind::Stairs { .. }
+        | SpecialTileKind::SiteSymbol { .. }
+        | SpecialTileKind::Port { .. }
+        | SpecialTileKind::Portal { .. } => Some(tinfo.special),
         _ => None,
     };
 
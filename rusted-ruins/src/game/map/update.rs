@@ -1,7 +1,14 @@
+use crate::game::extrait::*;
 use crate::game::Game;
 use common::gamedata::*;
+use common::sitegen::ShopGenData;
+use rng::SliceRandom;
+use rules::dungeon_gen::DungeonAging;
 use rules::RULES;
 
+/// Npcs are respawned farther than this from the player
+const RESPAWN_DISTANCE: i32 = 8;
+
 /// Map update after map switching.
 pub fn update_map(game: &mut Game<'_>) {
     crate::game::item::time::update_item_time(&mut game.gd);
@@ -14,6 +21,231 @@ pub fn update_map(game: &mut Game<'_>) {
     if duration > Duration::from_minutes(RULES.npc.map_switch_recover_minutes.into()) {
         recover_npc(&mut game.gd);
     }
+
+    // Unvisited maps may be unloaded, so they are aged when the player enters them.
+    if duration >= Duration::from_days(RULES.params.map_aging_days.into()) {
+        age_map(&mut game.gd);
+    }
+
+    let days = duration.as_secs() as f32 / SECS_PER_DAY as f32;
+    update_dungeon_floor(&mut game.gd, days);
+}
+
+/// Changes of a revisited dungeon floor by the elapsed days
+fn update_dungeon_floor(gd: &mut GameData, days: f32) {
+    let mid = gd.get_current_mapid();
+    if mid.is_region_map() || days <= 0.0 {
+        return;
+    }
+    let sid = mid.sid();
+    let dungeon_kind = match gd.region.get_site(sid).content {
+        SiteContent::AutoGenDungeon { dungeon_kind, .. } => dungeon_kind,
+        _ => {
+            return;
+        }
+    };
+    let aging = &RULES.dungeon_gen[&dungeon_kind].aging;
+
+    update_floor_items(gd, aging, days);
+    reclaim_dungeon(gd, sid, aging, days);
+    respawn_npcs(gd, dungeon_kind, days);
+    restock_merchants(gd, sid, days);
+}
+
+/// Probability of an event with the daily probability to occur in the days
+fn prob_in_days(prob_per_day: f32, days: f32) -> f32 {
+    (1.0 - (1.0 - prob_per_day.clamp(0.0, 1.0)).powf(days)).clamp(0.0, 1.0)
+}
+
+/// Npcs on the current map except the player and party members
+fn npcs_on_map(gd: &GameData) -> Vec<CharaId> {
+    gd.get_charas_on_map()
+        .into_iter()
+        .filter(|cid| *cid != CharaId::Player && !gd.player.party.contains(cid))
+        .collect()
+}
+
+/// Npcs on the current map hostile to the player.
+/// Peaceful npcs such as wandering merchants do not defend the floor.
+fn hostile_npcs_on_map(gd: &GameData) -> Vec<CharaId> {
+    npcs_on_map(gd)
+        .into_iter()
+        .filter(|cid| gd.chara_relation(*cid, CharaId::Player) == Relationship::Hostile)
+        .collect()
+}
+
+/// What happens to an item left on the floor
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum FloorItemChange {
+    Keep,
+    Decay,
+    Scavenge,
+}
+
+/// Quest items are kept because quests cannot be completed without them
+fn floor_item_change(
+    quest_item: bool,
+    p_decay: f32,
+    p_scavenge: f32,
+    has_scavenger: bool,
+) -> FloorItemChange {
+    if quest_item {
+        FloorItemChange::Keep
+    } else if rng::gen_bool(p_decay) {
+        FloorItemChange::Decay
+    } else if has_scavenger && rng::gen_bool(p_scavenge) {
+        FloorItemChange::Scavenge
+    } else {
+        FloorItemChange::Keep
+    }
+}
+
+/// Items on the floor decay or are picked up by npcs
+fn update_floor_items(gd: &mut GameData, aging: &DungeonAging, days: f32) {
+    let p_decay = prob_in_days(aging.item_decay, days);
+    let p_scavenge = prob_in_days(aging.item_scavenge, days);
+    if p_decay <= 0.0 && p_scavenge <= 0.0 {
+        return;
+    }
+    let npcs = npcs_on_map(gd);
+    let mid = gd.get_current_mapid();
+
+    for pos in gd.get_current_map().tile.iter_idx() {
+        let ill = ItemListLocation::OnMap { mid, pos };
+        for i in (0..gd.get_item_list(ill).len() as u32).rev() {
+            let quest_item = gd.get_item((ill, i)).0.is_quest_item();
+            match floor_item_change(quest_item, p_decay, p_scavenge, !npcs.is_empty()) {
+                FloorItemChange::Keep => (),
+                FloorItemChange::Decay => {
+                    gd.remove_item((ill, i), ItemMoveNum::All);
+                }
+                FloorItemChange::Scavenge => {
+                    let cid = *npcs.choose(&mut rng::get_rng()).unwrap();
+                    let n = gd.get_item_list(ill).get_number(i);
+                    let item = gd.remove_item_and_get((ill, i), ItemMoveNum::All);
+                    gd.get_item_list_mut(ItemListLocation::Chara { cid })
+                        .append(item, n);
+                }
+            }
+        }
+    }
+}
+
+/// Floors without hostile npcs can be reclaimed after the days
+fn can_reclaim(aging: &DungeonAging, days: f32, n_hostile_npcs: usize) -> bool {
+    aging.reclaim_days != 0 && days >= aging.reclaim_days as f32 && n_hostile_npcs == 0
+}
+
+/// Cleared dungeons are reclaimed by a new faction
+fn reclaim_dungeon(gd: &mut GameData, sid: SiteId, aging: &DungeonAging, days: f32) {
+    if !can_reclaim(aging, days, hostile_npcs_on_map(gd).len()) {
+        return;
+    }
+    let new_faction = if let Ok(item) = aging
+        .reclaim_factions
+        .choose_weighted(&mut rng::get_rng(), |item| item.1)
+    {
+        item.0
+    } else {
+        return;
+    };
+
+    if let SiteContent::AutoGenDungeon {
+        ref mut faction, ..
+    } = gd.region.get_site_mut(sid).content
+    {
+        *faction = Some(new_faction);
+    }
+    info!("{:?} is reclaimed by {:?}", sid, new_faction);
+    game_log!("dungeon-reclaimed");
+}
+
+/// The number of npcs respawned in the days. The fraction is respawned by probability.
+fn n_respawn(aging: &DungeonAging, days: f32, n_npcs: u32) -> u32 {
+    let n = aging.respawn_per_day * days;
+    let n = n.floor() as u32 + rng::gen_bool(n.fract()) as u32;
+    n.min(aging.max_npcs.saturating_sub(n_npcs))
+}
+
+/// Npcs are respawned up to the max
+fn respawn_npcs(gd: &mut GameData, dungeon_kind: DungeonKind, days: f32) {
+    let rule = &RULES.dungeon_gen[&dungeon_kind];
+    let aging = &rule.aging;
+    let n = n_respawn(aging, days, npcs_on_map(gd).len() as u32);
+    if n == 0 {
+        return;
+    }
+
+    let mid = gd.get_current_mapid();
+    let player_pos = gd.player_pos();
+    let before = gd.get_charas_on_map();
+    super::gen_npcs_by_race(gd, mid, n, mid.floor(), &rule.npc_race_probability, |map| {
+        // Npcs are not respawned near the player
+        (0..10)
+            .filter_map(|_| super::choose_empty_tile(map))
+            .find(|pos| pos.mdistance(player_pos) > RESPAWN_DISTANCE)
+    });
+
+    // Respawned npcs belong to the faction which reclaimed the dungeon
+    if let SiteContent::AutoGenDungeon {
+        faction: Some(faction),
+        ..
+    } = gd.region.get_site(mid.sid()).content
+    {
+        for cid in gd.get_charas_on_map() {
+            if !before.contains(&cid) {
+                gd.chara.get_mut(cid).faction = faction;
+            }
+        }
+    }
+}
+
+/// Shops of wandering merchants on this floor are restocked as town shops
+fn restock_merchants(gd: &mut GameData, sid: SiteId, days: f32) {
+    if days < RULES.town.restock_days as f32 {
+        return;
+    }
+    let ids: Vec<u32> = gd
+        .get_charas_on_map()
+        .into_iter()
+        .filter_map(|cid| match cid {
+            CharaId::OnSite { sid: s, id } if s == sid => Some(id),
+            _ => None,
+        })
+        .collect();
+
+    if let SiteContent::AutoGenDungeon { ref mut shops, .. } = gd.region.get_site_mut(sid).content {
+        for id in ids {
+            let shop = if let Some(shop) = shops.get_mut(&id) {
+                shop
+            } else {
+                continue;
+            };
+            if shop.shop_kind.is_empty() {
+                continue;
+            }
+            let shop_gen = ShopGenData {
+                chara_id: id,
+                shop_kind: shop.shop_kind.clone(),
+                selector: String::new(),
+                fence: shop.fence,
+            };
+            crate::game::shop::update_items_on_shop(shop, &shop_gen, 0);
+        }
+    }
+}
+
+/// Process for a map the player has not visited for a long time.
+/// Changes of dungeon floors are processed by update_dungeon_floor.
+fn age_map(gd: &mut GameData) {
+    info!("aging map {:?}", gd.get_current_mapid());
+
+    // Wounded npcs recover while the player is away
+    for cid in npcs_on_map(gd) {
+        let chara = gd.chara.get_mut(cid);
+        chara.heal(chara.attr.max_hp);
+        chara.mp = chara.attr.max_mp;
+    }
 }
 
 pub fn recover_npc(gd: &mut GameData) {
@@ -23,3 +255,77 @@ pub fn recover_npc(gd: &mut GameData) {
         chara.ai.state = AiState::Normal;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn prob_in_days_test() {
+        assert_eq!(prob_in_days(0.0, 10.0), 0.0);
+        assert_eq!(prob_in_days(1.0, 0.5), 1.0);
+        assert_eq!(prob_in_days(0.5, 0.0), 0.0);
+        assert!((prob_in_days(0.5, 1.0) - 0.5).abs() < 1e-6);
+        assert!((prob_in_days(0.5, 2.0) - 0.75).abs() < 1e-6);
+        assert!((prob_in_days(0.1, 3.0) - 0.271).abs() < 1e-6);
+        // Out of range probabilities are clamped
+        assert_eq!(prob_in_days(2.0, 1.0), 1.0);
+        assert_eq!(prob_in_days(-1.0, 1.0), 0.0);
+        // Longer absence makes the event more likely
+        assert!(prob_in_days(0.1, 10.0) > prob_in_days(0.1, 5.0));
+    }
+
+    #[test]
+    fn floor_item_change_test() {
+        use FloorItemChange::*;
+        rng::reseed_with(0);
+        for _ in 0..100 {
+            // Quest items are never lost
+            assert_eq!(floor_item_change(true, 1.0, 1.0, true), Keep);
+            assert_eq!(floor_item_change(false, 1.0, 1.0, true), Decay);
+            assert_eq!(floor_item_change(false, 0.0, 1.0, true), Scavenge);
+            // Items are not scavenged without npcs
+            assert_eq!(floor_item_change(false, 0.0, 1.0, false), Keep);
+            assert_eq!(floor_item_change(false, 0.0, 0.0, true), Keep);
+        }
+
+        let n_decay = (0..10000)
+            .filter(|_| floor_item_change(false, 0.2, 0.0, true) == Decay)
+            .count();
+        assert!(1800 < n_decay && n_decay < 2200, "{}", n_decay);
+    }
+
+    #[test]
+    fn can_reclaim_test() {
+        let aging = DungeonAging {
+            reclaim_days: 30,
+            ..DungeonAging::default()
+        };
+        assert!(can_reclaim(&aging, 30.0, 0));
+        assert!(!can_reclaim(&aging, 29.5, 0));
+        // Hostile npcs defend the floor
+        assert!(!can_reclaim(&aging, 60.0, 1));
+
+        let disabled = DungeonAging::default();
+        assert!(!can_reclaim(&disabled, 1000.0, 0));
+    }
+
+    #[test]
+    fn n_respawn_test() {
+        let aging = DungeonAging {
+            respawn_per_day: 0.5,
+            max_npcs: 10,
+            ..DungeonAging::default()
+        };
+        rng::reseed_with(0);
+        assert_eq!(n_respawn(&aging, 0.0, 0), 0);
+        assert_eq!(n_respawn(&aging, 8.0, 0), 4);
+        // Not respawned over the max
+        assert_eq!(n_respawn(&aging, 8.0, 8), 2);
+        assert_eq!(n_respawn(&aging, 100.0, 3), 7);
+        assert_eq!(n_respawn(&aging, 100.0, 12), 0);
+        // The fraction is respawned by probability
+        let total: u32 = (0..1000).map(|_| n_respawn(&aging, 1.0, 0)).sum();
+        assert!(400 < total && total < 600, "{}", total);
+    }
+}
@@ -0,0 +1,160 @@
+//! Prefab rooms (vaults) made as map templates and placed into generated floors
+
+use crate::game::extrait::*;
+use crate::game::item::gen::{from_item_gen, gen_item_from_idx};
+use crate::map_generator::vault::{Vault, VaultTransform};
+use crate::map_generator::{GeneratedMap, TileKind};
+use common::gamedata::*;
+use common::gobj;
+use common::maptemplate::*;
+use common::objholder::*;
+use geom::*;
+use rules::dungeon_gen::VaultParams;
+
+/// A vault placed in a generated map
+pub struct PlacedVault {
+    pub params: VaultParams,
+    pub top_left: Vec2d,
+    pub transform: VaultTransform,
+}
+
+impl PlacedVault {
+    fn template(&self) -> &'static MapTemplateObject {
+        gobj::get_by_id(&self.params.map_template)
+    }
+
+    fn template_size(&self) -> Vec2d {
+        let t = self.template();
+        Vec2d(t.w as i32, t.h as i32)
+    }
+
+    /// Convert a position in the template to the position in the map
+    fn to_map_pos(&self, pos: Vec2d) -> Vec2d {
+        self.top_left + self.transform.apply(pos, self.template_size())
+    }
+
+    fn bottom_right(&self) -> Vec2d {
+        self.top_left + self.transform.size(self.template_size()) - (1, 1)
+    }
+}
+
+/// Choose vaults by their probabilities and place them into the generated map
+pub fn place_vaults(gm: &mut GeneratedMap, vaults: &[VaultParams], floor: u32) -> Vec<PlacedVault> {
+    let mut placed = Vec::new();
+
+    for params in vaults {
+        if floor < params.min_floor || !rng::gen_bool(params.prob) {
+            continue;
+        }
+        let t: &MapTemplateObject = if let Some(t) = gobj::get_by_id_checked(&params.map_template) {
+            t
+        } else {
+            warn!("unknown map template \"{}\" for vault", params.map_template);
+            continue;
+        };
+
+        let mut tile = Array2d::new(t.w, t.h, TileKind::Floor);
+        for (pos, c) in t.wall.iter_with_idx() {
+            if !c.is_empty() {
+                tile[pos] = TileKind::Wall;
+            }
+        }
+        let vault = Vault {
+            tile,
+            connections: t.entrance.to_vec(),
+        };
+        let transform = VaultTransform::random(params.rotate, params.mirror);
+
+        if let Some(top_left) = vault.place(gm, transform) {
+            placed.push(PlacedVault {
+                params: params.clone(),
+                top_left,
+                transform,
+            });
+        } else {
+            trace!("no space for vault \"{}\"", params.map_template);
+        }
+    }
+
+    placed
+}
+
+/// Copy tiles, walls, decos and items of the vault template to the map
+pub fn stamp_to_map(map: &mut Map, v: &PlacedVault) {
+    let t = v.template();
+
+    for (pos, c) in t.tile.iter_with_idx() {
+        map.tile[v.to_map_pos(pos)].tile = TileLayers::conv_from(*c, &t.tile_table);
+    }
+
+    for (pos, c) in t.wall.iter_with_idx() {
+        let p = v.to_map_pos(pos);
+        let wall_idx = WallIdxPp::conv_from(*c, &t.wall_table);
+        map.tile[p].wall = wall_idx;
+        map.tile[p].wall_hp = if let Some(idx) = wall_idx.idx() {
+            gobj::get_obj(idx).hp
+        } else {
+            0
+        };
+    }
+
+    for (pos, i) in t.deco.iter_with_idx() {
+        map.tile[v.to_map_pos(pos)].deco = i.map(|i| gobj::id_to_idx(&t.deco_table[i as usize]));
+    }
+
+    for (pos, item_gen) in &t.items {
+        if let Some(item) = from_item_gen(item_gen) {
+            map.locate_item(item, v.to_map_pos(*pos), 1);
+        }
+    }
+
+    // Piece patterns are changed by rotation and the surrounding walls
+    map.reset_wall_pp(v.top_left, v.bottom_right());
+}
+
+/// Generate guaranteed treasures and bosses in the placed vaults
+pub fn gen_vault_contents(gd: &mut GameData, mid: MapId, vaults: &[PlacedVault]) {
+    for v in vaults {
+        for id in &v.params.treasure {
+            let idx: ItemIdx = if let Some(idx) = gobj::id_to_idx_checked(id) {
+                idx
+            } else {
+                warn!("unknown item \"{}\" for vault treasure", id);
+                continue;
+            };
+            let map = gd.region.get_map_mut(mid);
+            if let Some(p) = choose_empty_tile_in_vault(map, v) {
+                map.locate_item(gen_item_from_idx(idx, mid.floor()), p, 1);
+            }
+        }
+
+        if let Some(id) = &v.params.boss {
+            let idx: CharaTemplateIdx = if let Some(idx) = gobj::id_to_idx_checked(id) {
+                idx
+            } else {
+                warn!("unknown character template \"{}\" for vault boss", id);
+                continue;
+            };
+            let p = if let Some(p) = choose_empty_tile_in_vault(gd.region.get_map(mid), v) {
+                p
+            } else {
+                continue;
+            };
+            let ct = gobj::get_obj(idx);
+            let chara = crate::game::chara::gen::create_chara(idx, ct.gen_level, None, None);
+            let cid = gd.add_chara_to_map(chara, mid);
+            gd.region.get_map_mut(mid).locate_chara(cid, p);
+        }
+    }
+}
+
+fn choose_empty_tile_in_vault(map: &Map, v: &PlacedVault) -> Option<Vec2d> {
+    let empty_tiles: Vec<Vec2d> = RectIter::new(v.top_left, v.bottom_right())
+        .filter(|p| map.is_empty_tile(*p))
+        .collect();
+    if empty_tiles.is_empty() {
+        None
+    } else {
+        Some(empty_tiles[rng::gen_range(0..empty_tiles.len())])
+    }
+}
@@ -9,6 +9,7 @@ pub fn wall_damage(game: &mut Game<'_>, pos: Vec2d, power: f32) {
     let tile = &mut map.tile[pos];
 
     if tile.wall.is_empty() {
+        crate::game::door::damage(game, pos, power);
         return;
     }
 
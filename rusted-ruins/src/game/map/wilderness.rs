@@ -3,10 +3,12 @@ use crate::game::map::builder::MapBuilder;
 use crate::game::InfoGetter;
 use common::gamedata::*;
 use common::gobj;
+use common::objholder::*;
 use geom::*;
 use once_cell::sync::Lazy;
 use regex::Regex;
-use rules::biome::{BiomeDetail, SubBiomeDetail};
+use rng::SliceRandom;
+use rules::biome::{BiomeDetail, Encounter, EncounterKind, SubBiomeDetail, SubBiomeFeature};
 use rules::RULES;
 
 use super::builder::EntranceMethod;
@@ -16,7 +18,7 @@ pub fn generate_wilderness(
     pos: Vec2d,
     entrance_method: EntranceMethod,
 ) -> Option<Map> {
-    let (biome, _sub_biomes) = get_biome(gd, pos)?;
+    let (biome, sub_biomes) = get_biome(gd, pos)?;
 
     let destination = Destination::MapIdWithPos(
         gd.get_current_mapid(),
@@ -64,16 +66,284 @@ pub fn generate_wilderness(
         }
     }
 
+    // Sub-biome features are blended on the base biome
+    for (name, sub_biome) in &sub_biomes {
+        let dirs = connected_dirs(gd, pos, name);
+        for feature in &sub_biome.features {
+            gen_feature(&mut map, feature, &dirs);
+        }
+    }
+    let (w, h) = map.size();
+    map.reset_wall_pp(Vec2d(0, 0), Vec2d(w as i32 - 1, h as i32 - 1));
+
     // Wilderness map is revealed by default.
     map.reveal(|_| true);
 
     Some(map)
 }
 
-fn get_biome(
-    gd: &GameData,
-    pos: Vec2d,
-) -> Option<(&'static BiomeDetail, Vec<&'static SubBiomeDetail>)> {
+/// Generate an encounter on the wilderness map by the biome and the time of day.
+/// Returns the script id to start after entering.
+pub fn gen_encounter(gd: &mut GameData, mid: MapId, pos: Vec2d) -> Option<String> {
+    let (biome, sub_biomes) = get_biome(gd, pos)?;
+    if !rng::gen_bool(biome.encounter_probability) {
+        return None;
+    }
+
+    let hour = crate::game::time::current_time().into_date().hour;
+    let encounters: Vec<&Encounter> = biome
+        .encounters
+        .iter()
+        .chain(
+            sub_biomes
+                .iter()
+                .flat_map(|(_, sub_biome)| &sub_biome.encounters),
+        )
+        .filter(|encounter| encounter.is_available(hour))
+        .collect();
+    let encounter = encounters
+        .choose_weighted(&mut rng::get_rng(), |encounter| encounter.weight)
+        .ok()?;
+    trace!("Wilderness encounter {:?}", encounter.kind);
+
+    match &encounter.kind {
+        EncounterKind::Npcs { chara, n, faction } => {
+            let center = super::choose_empty_tile(gd.region.get_map(mid))?;
+            for _ in 0..rng::gen_range(n[0]..=n[1].max(n[0])) {
+                let idx = chara
+                    .choose_weighted(&mut rng::get_rng(), |item| item.1)
+                    .ok()?
+                    .0;
+                let p = gd.region.get_map(mid).empty_tile_around(center)?;
+                let ct = gobj::get_obj(idx);
+                let chara =
+                    crate::game::chara::gen::create_chara(idx, ct.gen_level, *faction, None);
+                let cid = gd.add_chara_to_map(chara, mid);
+                gd.region.get_map_mut(mid).locate_chara(cid, p);
+            }
+            game_log!("wilderness-encounter");
+            None
+        }
+        EncounterKind::Items { items } => {
+            let map = gd.region.get_map_mut(mid);
+            let center = super::choose_empty_tile(map)?;
+            for &(idx, n) in items {
+                let item = crate::game::item::gen::gen_item_from_idx(idx, 1);
+                map.locate_item(item, center, n);
+            }
+            None
+        }
+        EncounterKind::Script { id } => Some(id.clone()),
+    }
+}
+
+fn gen_feature(map: &mut Map, feature: &SubBiomeFeature, dirs: &[Direction]) {
+    let size: Vec2d = map.size().into();
+
+    match feature {
+        SubBiomeFeature::River { tile, width, fords } => {
+            let path = path_positions(size, dirs);
+            let r = (width.saturating_sub(1) / 2) as i32;
+            let fords: Vec<Vec2d> = path
+                .choose_multiple(&mut rng::get_rng(), *fords as usize)
+                .copied()
+                .collect();
+            for &c in &path {
+                for p in RectIter::new(c - (r, r), c + (r, r)) {
+                    if !fords
+                        .iter()
+                        .any(|f| (f.0 - p.0).abs().max((f.1 - p.1).abs()) <= r)
+                    {
+                        set_tile(map, p, *tile);
+                    }
+                }
+            }
+        }
+        SubBiomeFeature::Lake { tile, radius } => {
+            let r = rng::gen_range(radius[0]..=radius[1].max(radius[0])) as i32;
+            let c = random_pos(size);
+            for p in RectIter::new(c - (r, r), c + (r, r)) {
+                if c.distance2(p) <= (r * r) as f32 {
+                    set_tile(map, p, *tile);
+                }
+            }
+        }
+        SubBiomeFeature::Outcrop {
+            wall,
+            ore_walls,
+            n,
+            radius,
+        } => {
+            for _ in 0..rng::gen_range(n[0]..=n[1].max(n[0])) {
+                let r = rng::gen_range(radius[0]..=radius[1].max(radius[0])) as i32;
+                let c = random_pos(size);
+                for p in RectIter::new(c - (r, r), c + (r, r)) {
+                    // Rough edges
+                    if c.distance2(p) > (r * r) as f32 || rng::gen_bool(0.2) {
+                        continue;
+                    }
+                    let wall = ore_walls
+                        .iter()
+                        .find(|(_, prob)| rng::gen_bool(*prob))
+                        .map(|(wall, _)| *wall)
+                        .unwrap_or(*wall);
+                    set_wall(map, p, wall);
+                }
+            }
+        }
+        SubBiomeFeature::Ruins {
+            wall,
+            n,
+            size: building_size,
+            remaining,
+            items,
+        } => {
+            for _ in 0..rng::gen_range(n[0]..=n[1].max(n[0])) {
+                let gen_len = || {
+                    rng::gen_range(building_size[0]..=building_size[1].max(building_size[0])) as i32
+                };
+                let building = Vec2d(gen_len(), gen_len());
+                let top_left = random_pos(size - building);
+                let bottom_right = top_left + building - (1, 1);
+                for p in RectIter::new(top_left, bottom_right) {
+                    let is_edge = p.0 == top_left.0
+                        || p.1 == top_left.1
+                        || p.0 == bottom_right.0
+                        || p.1 == bottom_right.1;
+                    if is_edge {
+                        if rng::gen_bool(*remaining) {
+                            set_wall(map, p, *wall);
+                        } else {
+                            clear_tile(map, p);
+                        }
+                    } else {
+                        clear_tile(map, p);
+                        if let Some((idx, _)) = items.iter().find(|(_, prob)| rng::gen_bool(*prob))
+                        {
+                            let item = crate::game::item::gen::gen_item_from_idx(*idx, 1);
+                            map.locate_item(item, p, 1);
+                        }
+                    }
+                }
+            }
+        }
+        SubBiomeFeature::Clearing { n, radius } => {
+            for _ in 0..rng::gen_range(n[0]..=n[1].max(n[0])) {
+                let r = rng::gen_range(radius[0]..=radius[1].max(radius[0])) as i32;
+                let c = random_pos(size);
+                for p in RectIter::new(c - (r, r), c + (r, r)) {
+                    if c.distance2(p) <= (r * r) as f32 {
+                        clear_tile(map, p);
+                    }
+                }
+            }
+        }
+        SubBiomeFeature::Road { tile, width } => {
+            let r = (width.saturating_sub(1) / 2) as i32;
+            for c in path_positions(size, dirs) {
+                for p in RectIter::new(c - (r, r), c + (r, r)) {
+                    set_tile(map, p, *tile);
+                }
+            }
+        }
+    }
+}
+
+/// Positions of a path connecting the map edges in given directions through near the center.
+/// The path crosses the map if less than two directions are given.
+fn path_positions(size: Vec2d, dirs: &[Direction]) -> Vec<Vec2d> {
+    let mut dirs = dirs.to_vec();
+    if dirs.is_empty() {
+        dirs.push(
+            *[Direction::N, Direction::E]
+                .choose(&mut rng::get_rng())
+                .unwrap(),
+        );
+    }
+    if dirs.len() == 1 {
+        let opposite = Direction::new(dirs[0].hdir.reverse(), dirs[0].vdir.reverse());
+        dirs.push(opposite);
+    }
+
+    let c = Vec2d(size.0 / 2, size.1 / 2);
+    let c = c + Vec2d(
+        rng::gen_range(-size.0 / 6..=size.0 / 6),
+        rng::gen_range(-size.1 / 6..=size.1 / 6),
+    );
+    dirs.iter()
+        .flat_map(|dir| LineIter::new(edge_pos(size, *dir), c))
+        .collect()
+}
+
+fn edge_pos(size: Vec2d, dir: Direction) -> Vec2d {
+    match (dir.hdir, dir.vdir) {
+        (HDirection::Left, _) => Vec2d(0, size.1 / 2),
+        (HDirection::Right, _) => Vec2d(size.0 - 1, size.1 / 2),
+        (_, VDirection::Up) => Vec2d(size.0 / 2, 0),
+        _ => Vec2d(size.0 / 2, size.1 - 1),
+    }
+}
+
+fn random_pos(size: Vec2d) -> Vec2d {
+    Vec2d(
+        rng::gen_range(0..size.0.max(1)),
+        rng::gen_range(0..size.1.max(1)),
+    )
+}
+
+/// Tiles around entrances are not changed by features
+fn is_protected(map: &Map, p: Vec2d) -> bool {
+    !map.is_inside(p) || map.entrance.iter().any(|e| e.mdistance(p) <= 1)
+}
+
+fn set_tile(map: &mut Map, p: Vec2d, tile: TileIdx) {
+    if is_protected(map, p) {
+        return;
+    }
+    clear_tile(map, p);
+    map.tile[p].tile = tile.into();
+}
+
+fn set_wall(map: &mut Map, p: Vec2d, wall: WallIdx) {
+    if is_protected(map, p) {
+        return;
+    }
+    let t = &mut map.tile[p];
+    t.wall = WallIdxPp::new(wall);
+    t.wall_hp = gobj::get_obj(wall).hp;
+    t.item_list.clear();
+}
+
+fn clear_tile(map: &mut Map, p: Vec2d) {
+    if is_protected(map, p) {
+        return;
+    }
+    let t = &mut map.tile[p];
+    t.wall = WallIdxPp::empty();
+    t.wall_hp = 0;
+    t.item_list.clear();
+}
+
+/// Directions of adjacent region map tiles which have the same sub-biome
+fn connected_dirs(gd: &GameData, pos: Vec2d, sub_biome_name: &str) -> Vec<Direction> {
+    let map = gd.get_current_map();
+    [Direction::N, Direction::E, Direction::S, Direction::W]
+        .iter()
+        .copied()
+        .filter(|dir| {
+            let p = pos + dir.as_vec();
+            map.is_inside(p)
+                && map.tile[p]
+                    .tile
+                    .iter_idx()
+                    .any(|idx| to_biome_name(gobj::idx_to_id(idx)) == Some(sub_biome_name))
+        })
+        .collect()
+}
+
+type SubBiomes = Vec<(&'static str, &'static SubBiomeDetail)>;
+
+fn get_biome(gd: &GameData, pos: Vec2d) -> Option<(&'static BiomeDetail, SubBiomes)> {
     if !gd.get_current_mapid().is_region_map() {
         return None;
     }
@@ -92,7 +362,7 @@ fn get_biome(
             biome = Some(b);
         }
         if let Some(b) = RULES.biomes.sub_biomes.get(biome_name) {
-            sub_biome.push(b);
+            sub_biome.push((biome_name, b));
         }
     }
 
@@ -105,3 +375,40 @@ fn to_biome_name(name: &str) -> Option<&str> {
         .map(|cap| cap.get(1).map(|m| m.as_str()))
         .flatten()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn path_positions_test() {
+        let size = Vec2d(32, 24);
+        let inside = |p: &Vec2d| 0 <= p.0 && p.0 < size.0 && 0 <= p.1 && p.1 < size.1;
+
+        for seed in 0..10 {
+            rng::reseed_with(seed);
+
+            // One direction crosses the map to the opposite edge
+            let path = path_positions(size, &[Direction::N]);
+            assert!(path.iter().all(inside));
+            assert!(path.contains(&edge_pos(size, Direction::N)));
+            assert!(path.contains(&edge_pos(size, Direction::S)));
+
+            // Two directions connect only the given edges
+            let path = path_positions(size, &[Direction::E, Direction::N]);
+            assert!(path.iter().all(inside));
+            assert!(path.contains(&edge_pos(size, Direction::E)));
+            assert!(path.contains(&edge_pos(size, Direction::N)));
+            assert!(!path.contains(&edge_pos(size, Direction::W)));
+            assert!(!path.contains(&edge_pos(size, Direction::S)));
+
+            // No direction crosses the map vertically or horizontally
+            let path = path_positions(size, &[]);
+            let vertical = path.contains(&edge_pos(size, Direction::N))
+                && path.contains(&edge_pos(size, Direction::S));
+            let horizontal = path.contains(&edge_pos(size, Direction::E))
+                && path.contains(&edge_pos(size, Direction::W));
+            assert!(vertical != horizontal);
+        }
+    }
+}
@@ -2,18 +2,21 @@ mod action;
 pub mod active_skill;
 mod anim_queue;
 mod animation;
+pub mod base;
 pub mod building;
 pub mod chara;
 pub mod command;
 pub mod creation;
 pub mod damage;
 mod debug_command;
+mod door;
 mod dungeon_gen;
 pub mod effect;
-mod faction;
+pub mod faction;
 pub mod frequent_tex;
 mod infogetter;
 pub mod item;
+pub mod livestock;
 pub mod map;
 pub mod newgame;
 mod npc;
@@ -22,15 +25,19 @@ pub mod play_time;
 pub mod playeract;
 pub mod quest;
 mod region;
+mod rest;
 pub mod saveload;
 pub mod script_exec;
 pub mod script_methods;
 pub mod shop;
 pub mod site;
 mod skill;
+mod special_room;
 mod target;
+mod theft;
 mod time;
 mod town;
+mod trap;
 mod turnloop;
 pub mod view;
 
@@ -218,11 +225,19 @@ pub enum DialogOpenRequest {
     ShopBuy {
         cid: CharaId,
     },
-    ShopSell,
+    ShopSell {
+        cid: CharaId,
+    },
+    PartyMember {
+        cid: CharaId,
+    },
     RegisterAsShortcut {
         shortcut: ActionShortcut,
     },
     PickUpItem,
+    Rest,
+    WaitTurns,
+    StealItem,
     Quest,
     GameOver,
 }
@@ -240,9 +255,8 @@ pub mod extrait {
     pub use super::active_skill::BasePowerExt;
     pub use super::chara::status::{CharaStatusExt, CharaStatusOperation};
     pub use super::chara::CharaExt;
-    pub use super::faction::FactionExt;
     pub use super::infogetter::InfoGetter;
-    pub use super::item::{GameDataItemExt, ItemExt, ItemListExt};
+    pub use super::item::{GameDataItemExt, ItemExt, ItemListExt, ItemTimeExt};
     pub use super::map::MapExt;
     pub use super::party::GameDataPartyExt;
     pub use super::play_time::PlayTimeExt;
@@ -58,14 +58,24 @@ impl NewGameBuilder {
         gd.meta.set_save_name(self.player_name.as_ref().unwrap());
 
         super::region::add_region(&mut gd, &RULES.newgame.start_region);
+        for id in &RULES.region_gen.other_regions {
+            super::region::add_region(&mut gd, id);
+        }
+        super::region::add_region_links(&mut gd);
 
         let mid = MapId::RegionMap {
             rid: RegionId::default(),
         };
         gd.set_initial_mapid(mid);
-        let start_pos = RULES.newgame.start_pos;
-
-        super::region::gen_dungeon(&mut gd, mid.rid());
+        let start_pos = super::region::start_pos(&gd, mid.rid());
+
+        for id in
+            std::iter::once(&RULES.newgame.start_region).chain(&RULES.region_gen.other_regions)
+        {
+            if let Some(rid) = gd.region.get_id_by_name(id) {
+                super::region::gen_dungeon(&mut gd, rid);
+            }
+        }
 
         gd.player.set_money(RULES.newgame.start_money as i64);
 
@@ -15,6 +15,10 @@ use rng::*;
 use rules::{npc_ai::*, RULES};
 
 pub fn process_npc_turn(game: &mut Game<'_>, cid: CharaId) {
+    if game.gd.player.party.contains(&cid) {
+        apply_party_order(game, cid);
+    }
+
     match game.gd.chara.get(cid).ai.state {
         AiState::Normal => process_npc_turn_normal(game, cid),
         AiState::Combat { .. } => process_npc_turn_combat(game, cid),
@@ -22,6 +26,25 @@ pub fn process_npc_turn(game: &mut Game<'_>, cid: CharaId) {
     }
 }
 
+/// Change AI state of a party member by the order from the player
+fn apply_party_order(game: &mut Game<'_>, cid: CharaId) {
+    match game.gd.player.party_order(cid) {
+        PartyOrder::AttackTarget => {
+            if let Some(target) = game.target_chara() {
+                if game.gd.chara_pos(target).is_some()
+                    && game.gd.chara_relation(cid, target) == Relationship::Hostile
+                {
+                    game.gd.chara.get_mut(cid).ai.state = AiState::Combat { target };
+                }
+            }
+        }
+        PartyOrder::NoAttack => {
+            game.gd.chara.get_mut(cid).ai.state = AiState::Normal;
+        }
+        PartyOrder::Follow | PartyOrder::Wait => (),
+    }
+}
+
 fn process_npc_turn_normal(game: &mut Game<'_>, cid: CharaId) {
     move_normal(game, cid);
 }
@@ -59,9 +82,12 @@ fn process_npc_turn_combat(game: &mut Game<'_>, cid: CharaId) {
                 shoot_target(game, cid, target);
             }
             CombatActionKind::ActiveSkill => {
-                if let Some(active_skill_id) = ct.active_skills.choose(&mut get_rng()) {
-                    use_active_skill(game, active_skill_id, cid, target);
+                let used = if let Some(active_skill_id) = ct.active_skills.choose(&mut get_rng()) {
+                    use_active_skill(game, active_skill_id, cid, target)
                 } else {
+                    false
+                };
+                if !used {
                     enable_active_skill = false;
                     continue;
                 }
@@ -103,6 +129,9 @@ fn process_npc_turn_search(game: &mut Game<'_>, cid: CharaId) {
 /// Move when normal state or missing target
 fn move_normal(game: &mut Game<'_>, cid: CharaId) {
     if game.gd.player.party.contains(&cid) {
+        if game.gd.player.party_order(cid) == PartyOrder::Wait {
+            return;
+        }
         follow_other(game, cid, CharaId::Player);
     }
 
@@ -186,10 +215,17 @@ fn dir_to_chara(
     let map = gd.get_current_map();
     let chara = gd.chara.get(cid);
 
+    // Npcs open closed doors, but cannot pass locked doors
     let route = geom::PathFinding::new(map.w, map.h, pathfinding_step, |pos| {
-        map.is_passable(chara, pos)
+        map.is_passable(chara, pos) || map.can_open_door(pos)
     })
-    .route(start_pos, target_pos);
+    .route_with_cost(start_pos, target_pos, |pos| {
+        if map.can_open_door(pos) {
+            RULES.door.path_cost
+        } else {
+            0
+        }
+    });
 
     let next_pos = route.and_then(|route| route.get(1).copied())?;
     Some(geom::dir_by_2pos(start_pos, next_pos))
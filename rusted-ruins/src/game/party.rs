@@ -20,9 +20,9 @@ impl GameData {
             < self.available_party_size() as usize
     }
 
-    fn add_chara_to_party(&mut self, mut chara: Chara) -> bool {
+    fn add_chara_to_party(&mut self, mut chara: Chara) -> Option<CharaId> {
         if !self.has_empty_for_party() {
-            return false;
+            return None;
         }
 
         let cid = CharaId::Ally {
@@ -31,7 +31,7 @@ impl GameData {
         chara.faction = FactionId::player();
 
         if !self.player.party.insert(cid) {
-            return false;
+            return None;
         }
 
         game_log!("party-add-chara"; chara=chara);
@@ -44,7 +44,7 @@ impl GameData {
         }
         trace!("added new chara to the player's party");
 
-        true
+        Some(cid)
     }
 
     fn add_cid_to_party(&mut self, cid: CharaId) {
@@ -70,7 +70,7 @@ impl GameData {
         trace!("generating party chara \"{}\" lv.{}", id, lv);
         let idx: CharaTemplateIdx = gobj::id_to_idx(id);
         let chara = crate::game::chara::gen::create_chara(idx, lv, FactionId::player(), None);
-        self.add_chara_to_party(chara)
+        self.add_chara_to_party(chara).is_some()
     }
 }
 
@@ -82,3 +82,122 @@ pub fn resurrect_party_members(gd: &mut GameData) {
         gd.add_cid_to_party(cid);
     }
 }
+
+/// Daily wage to hire given character
+pub fn wage(chara: &Chara) -> i64 {
+    RULES.npc.hire.wage(chara.lv)
+}
+
+/// Hire a character on a map as a party member.
+/// The first wage is paid in advance.
+pub fn hire(gd: &mut GameData, cid: CharaId) -> bool {
+    if !matches!(cid, CharaId::OnSite { .. } | CharaId::OnMap { .. }) {
+        return false;
+    }
+    if !gd.has_empty_for_party() {
+        game_log!("party-full");
+        return false;
+    }
+
+    let wage = wage(gd.chara.get(cid));
+    if !gd.player.has_money(wage) {
+        game_log!("party-hire-lack-of-money"; amount=wage);
+        return false;
+    }
+
+    // The character is removed from its map only after joining the party succeeded
+    let mut chara = gd.chara.get(cid).clone();
+    chara.trigger_talk = None;
+    chara.ai.state = AiState::Normal;
+
+    let new_cid = if let Some(new_cid) = gd.add_chara_to_party(chara) {
+        new_cid
+    } else {
+        return false;
+    };
+    let pos = gd.get_current_map().chara_pos(cid);
+    gd.remove_chara(cid);
+    // Take the place of the hired character if there was no room around the player
+    if let Some(pos) = pos {
+        let map = gd.get_current_map_mut();
+        if map.chara_pos(new_cid).is_none() {
+            map.locate_chara(new_cid, pos);
+        }
+    }
+    let cid = new_cid;
+    gd.player.sub_money(wage);
+    gd.player.employment.insert(
+        cid,
+        Employment {
+            wage,
+            loyalty: RULES.npc.hire.loyalty_initial,
+        },
+    );
+    game_log!("party-hire"; chara=gd.chara.get(cid), wage=wage);
+    true
+}
+
+/// Remove a member from the party. The member leaves the game.
+pub fn dismiss(gd: &mut GameData, cid: CharaId) {
+    game_log!("party-leave"; chara=gd.chara.get(cid));
+    gd.player.party.remove(&cid);
+    gd.player.party_dead.remove(&cid);
+    gd.player.employment.remove(&cid);
+    gd.player.party_orders.remove(&cid);
+    gd.remove_chara(cid);
+}
+
+pub fn set_order(gd: &mut GameData, cid: CharaId, order: PartyOrder) {
+    if order == PartyOrder::Follow {
+        gd.player.party_orders.remove(&cid);
+    } else {
+        gd.player.party_orders.insert(cid, order);
+    }
+    let text_id = match order {
+        PartyOrder::Follow => "party-order-follow",
+        PartyOrder::Wait => "party-order-wait",
+        PartyOrder::AttackTarget => "party-order-attack-target",
+        PartyOrder::NoAttack => "party-order-no-attack",
+    };
+    game_log!(text_id; chara=gd.chara.get(cid));
+}
+
+/// Pay daily wages to hired members.
+/// Unpaid members lose loyalty, and leave the party if it becomes zero.
+pub fn pay_wages(gd: &mut GameData) {
+    let rule = &RULES.npc.hire;
+    let cids: Vec<CharaId> = gd.player.employment.keys().copied().collect();
+    let mut total = 0;
+    let mut leaving = Vec::new();
+
+    for cid in cids {
+        // Dead members do not work
+        if gd.player.party_dead.contains(&cid) {
+            continue;
+        }
+        let wage = gd.player.employment[&cid].wage;
+        let paid = gd.player.has_money(wage);
+        if paid {
+            gd.player.sub_money(wage);
+            total += wage;
+        }
+
+        let employment = gd.player.employment.get_mut(&cid).unwrap();
+        if paid {
+            employment.loyalty = (employment.loyalty + rule.loyalty_paid).min(rule.loyalty_max);
+        } else {
+            employment.loyalty = employment.loyalty.saturating_sub(rule.loyalty_unpaid);
+            game_log!("party-wage-unpaid"; chara=gd.chara.get(cid));
+            if gd.player.employment[&cid].loyalty == 0 {
+                leaving.push(cid);
+            }
+        }
+    }
+
+    if total > 0 {
+        game_log!("party-wage-paid"; amount=total);
+    }
+    for cid in leaving {
+        dismiss(gd, cid);
+    }
+}
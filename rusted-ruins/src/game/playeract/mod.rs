@@ -69,6 +69,35 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
         true
     }
 
+    /// Steal an item owned by others
+    pub fn steal_item(&mut self, il: ItemLocation) -> bool {
+        let gd = self.gd_mut();
+        let item = gd.get_item(il).0;
+
+        if item.flags.contains(ItemFlags::FIXED) {
+            game_log!("item-pick-up-fixed"; item=item);
+            return false;
+        }
+        if item.flags.contains(ItemFlags::PLANT) {
+            game_log!("item-pick-up-plant"; item=item);
+            return false;
+        }
+
+        super::theft::steal_item(gd, il);
+        self.0.finish_player_turn();
+        true
+    }
+
+    /// Give an order to a party member
+    pub fn set_party_order(&mut self, cid: CharaId, order: PartyOrder) {
+        super::party::set_order(self.gd_mut(), cid, order);
+    }
+
+    /// Dismiss a party member
+    pub fn dismiss_party_member(&mut self, cid: CharaId) {
+        super::party::dismiss(self.gd_mut(), cid);
+    }
+
     /// Drop items on tile
     pub fn drop_item(&mut self, il: ItemLocation, n: u32) -> bool {
         let gd = self.gd_mut();
@@ -140,9 +169,20 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
 
     /// Read item, returns continue dialog or not.
     pub fn read_item(&mut self, il: ItemLocation) -> bool {
+        use crate::game::active_skill::LearnActiveSkillResult;
         use crate::game::creation::LearnRecipeResult;
 
         let title = self.gd().get_item(il).0.title().unwrap().to_owned();
+        match crate::game::active_skill::learn_active_skill(self.gd_mut(), il) {
+            LearnActiveSkillResult::Success => {
+                self.0.finish_player_turn();
+                return false;
+            }
+            LearnActiveSkillResult::AlreadyLearned => {
+                return true;
+            }
+            LearnActiveSkillResult::NotSpellbook => (),
+        }
         match crate::game::creation::learn_recipe(self.gd_mut(), il) {
             LearnRecipeResult::Success => {
                 self.0.finish_player_turn();
@@ -194,13 +234,13 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
     }
 
     /// Buy item
-    pub fn buy_item(&mut self, il: ItemLocation) {
-        super::shop::buy_item(self.gd_mut(), il);
+    pub fn buy_item(&mut self, cid: CharaId, il: ItemLocation) {
+        super::shop::buy_item(self.gd_mut(), cid, il);
     }
 
     /// Sell item
-    pub fn sell_item(&mut self, il: ItemLocation) {
-        super::shop::sell_item(self.gd_mut(), il);
+    pub fn sell_item(&mut self, cid: CharaId, il: ItemLocation) {
+        super::shop::sell_item(self.gd_mut(), cid, il);
     }
 
     /// Change specified character's equipment by given item
@@ -214,8 +254,43 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
     }
 
     /// Use active skill. Returns false if the skill cost is not enough.
-    pub fn use_active_skill(&mut self, _active_skill_id: &ActiveSkillId) -> bool {
-        false
+    pub fn use_active_skill(&mut self, active_skill_id: &ActiveSkillId) -> bool {
+        use super::active_skill::use_active_skill;
+        use rules::RULES;
+
+        let active_skill = if let Some(active_skill) = RULES.active_skills.get(active_skill_id) {
+            active_skill
+        } else {
+            return false;
+        };
+
+        let player = self.gd().chara.get(CharaId::Player);
+        if !player.active_skill_available(active_skill_id, active_skill) {
+            game_log_i!("active-skill-unavailable"; chara=player, active_skill=active_skill_id);
+            return false;
+        }
+
+        let target = if let Some(target) = auto_target_for_player(self.0, &active_skill.effect) {
+            target
+        } else {
+            let active_skill_id = active_skill_id.clone();
+            self.0.ui_request.push_back(UiRequest::StartTargeting {
+                effect: active_skill.effect.clone(),
+                callback: Box::new(move |pa, target| {
+                    if use_active_skill(pa.0, &active_skill_id, CharaId::Player, target) {
+                        pa.0.finish_player_turn();
+                    }
+                }),
+            });
+            return true;
+        };
+
+        if use_active_skill(self.0, active_skill_id, CharaId::Player, target) {
+            self.0.finish_player_turn();
+            true
+        } else {
+            false
+        }
     }
 
     /// Try talk to next chara
@@ -255,6 +330,65 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
         }
     }
 
+    /// Try to tame a creature
+    pub fn tame(&mut self, cid: CharaId) {
+        if crate::game::livestock::tame(self.gd_mut(), cid) {
+            self.0.finish_player_turn();
+        }
+    }
+
+    /// Wait for given turns
+    pub fn wait_turns(&mut self, turns: u16) {
+        if crate::game::rest::wait_turns(self.0, turns) {
+            self.0.finish_player_turn();
+        }
+    }
+
+    /// Rest until HP and MP are recovered
+    pub fn rest_until_healed(&mut self) {
+        if crate::game::rest::rest_until_healed(self.0) {
+            self.0.finish_player_turn();
+        }
+    }
+
+    /// Sleep until the next morning
+    pub fn sleep_until_morning(&mut self) {
+        if crate::game::rest::sleep_until_morning(self.0) {
+            self.0.finish_player_turn();
+        }
+    }
+
+    /// Search hidden traps and secret passages around the player
+    pub fn search(&mut self) {
+        game_log_i!("search-start");
+        let range = rules::RULES.trap.search_range;
+        if crate::game::trap::search(self.0, range, 1.0) == 0 {
+            game_log!("search-nothing");
+        }
+        self.0.finish_player_turn();
+    }
+
+    /// Close the door on the tile
+    pub fn close_door(&mut self, pos: Vec2d) {
+        if crate::game::door::close(self.0, pos) {
+            self.0.finish_player_turn();
+        }
+    }
+
+    /// Try to pick the lock of the door on the tile
+    pub fn pick_lock(&mut self, pos: Vec2d) {
+        if crate::game::door::pick_lock(self.0, pos) {
+            self.0.finish_player_turn();
+        }
+    }
+
+    /// Feed a livestock with food in the inventory
+    pub fn feed_livestock(&mut self, cid: CharaId) {
+        if crate::game::livestock::feed(self.gd_mut(), cid) {
+            self.0.finish_player_turn();
+        }
+    }
+
     /// Advance current talk. Give player's choice if the talk has choices.
     /// If returns new text, continue talk dialog.
     pub fn advance_talk(&mut self, choice: Option<u32>) -> AdvanceScriptResult {
@@ -305,13 +439,14 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
 
         match cid {
             Some(cid) if cid != CharaId::Player => {
-                let scanned = self
-                    .gd()
-                    .chara
-                    .get(cid)
-                    .status
-                    .iter()
-                    .any(|status| *status == CharaStatus::Scanned);
+                let scanned = self.gd().player.party.contains(&cid)
+                    || self
+                        .gd()
+                        .chara
+                        .get(cid)
+                        .status
+                        .iter()
+                        .any(|status| *status == CharaStatus::Scanned);
 
                 if scanned {
                     self.0
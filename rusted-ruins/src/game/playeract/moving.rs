@@ -5,6 +5,7 @@ use crate::game::{action, DialogOpenRequest, InfoGetter};
 use crate::text::ToText;
 use common::gamedata::*;
 use geom::*;
+use rules::RULES;
 
 impl<'a, 's> DoPlayerAction<'a, 's> {
     pub fn try_move(&mut self, dir: Direction) {
@@ -107,6 +108,14 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
 
         // Use stairs
         if dir.is_none() {
+            let special_tile_kind =
+                self.gd().get_current_map().tile[self.gd().player_pos()].special;
+            if let SpecialTileKind::Port { rid } | SpecialTileKind::Portal { rid } =
+                special_tile_kind
+            {
+                self.travel_to_region(special_tile_kind, rid, dialog);
+                return;
+            }
             let (dest, msg) = {
                 let gd = self.gd_mut();
                 let mid = gd.get_current_mapid();
@@ -214,6 +223,60 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
         }
     }
 
+    /// Travel to another region by ship or portal
+    fn travel_to_region(
+        &mut self,
+        special_tile_kind: SpecialTileKind,
+        rid: RegionId,
+        dialog: bool,
+    ) {
+        let gd = self.gd();
+        let region = gd.region.get(rid);
+        let link = RULES
+            .region_gen
+            .link(&gd.get_current_region().name, &region.name);
+        let (fare, hours) = link.map_or((0, 0), |link| (link.fare, link.hours));
+        let (dest, msg) = match special_tile_kind {
+            SpecialTileKind::Port { .. } => (
+                Destination::Ship(rid),
+                ui_txt_format!("dialog-sail"; region=region, fare=fare),
+            ),
+            _ => (
+                Destination::Portal(rid),
+                ui_txt_format!("dialog-enter-portal"; region=region),
+            ),
+        };
+
+        let cb = Box::new(move |pa: &mut DoPlayerAction<'_, '_>, result: bool| {
+            if !result {
+                return;
+            }
+            if !pa.gd().player.has_money(fare as i64) {
+                game_log!("travel-not-enough-money");
+                return;
+            }
+            pa.gd_mut().player.sub_money(fare as i64);
+            let player = pa.gd().chara.get(CharaId::Player);
+            let region = pa.gd().region.get(rid);
+            match dest {
+                Destination::Ship(_) => {
+                    game_log!("travel-ship"; player=player, region=region);
+                }
+                _ => {
+                    game_log!("travel-portal"; player=player, region=region);
+                }
+            }
+            crate::game::map::switch_map(pa.0, dest);
+            crate::game::time::advance_game_time_by_secs(pa.0, hours as u64 * 60 * 60);
+        });
+        if dialog {
+            self.0
+                .request_dialog_open(DialogOpenRequest::YesNo { callback: cb, msg });
+        } else {
+            cb(self, true);
+        }
+    }
+
     pub fn enter_wilderness(&mut self, pos: Vec2d) {
         if let Some(map) = crate::game::map::wilderness::generate_wilderness(
             self.gd(),
@@ -234,7 +297,12 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
                 "wilderness",
                 site_content,
             );
+            let script =
+                crate::game::map::wilderness::gen_encounter(self.gd_mut(), wilderness_mid, pos);
             crate::game::map::switch_map(self.0, Destination::MapId(wilderness_mid));
+            if let Some(script) = script {
+                self.0.start_script(&script, None, None);
+            }
         } else {
             warn!("cannot generate wilderness map for given position");
         }
@@ -7,6 +7,7 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
         let gd = self.gd_mut();
         let player = gd.chara.get_mut(CharaId::Player);
         player.hp = player.attr.max_hp;
+        player.mp = player.attr.max_mp;
 
         let (mid, pos) = gd
             .region
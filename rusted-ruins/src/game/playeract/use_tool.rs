@@ -64,7 +64,7 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
             }
             ToolEffect::Mine => {
                 let map = self.0.gd.get_current_map();
-                if map.tile[pos].wall.is_empty() {
+                if map.tile[pos].wall.is_empty() && map.tile[pos].door.is_none() {
                     return;
                 }
                 if !pos.is_adjacent(player_pos) {
@@ -90,6 +90,16 @@ impl<'a, 's> DoPlayerAction<'a, 's> {
                 audio::play_sound("mining");
                 self.0.finish_player_turn();
             }
+            ToolEffect::Disarm => {
+                if !pos.is_adjacent(player_pos) {
+                    game_log!("disarm-not-adjacent-tile");
+                    return;
+                }
+                trace!("disarming at {}", &pos);
+                if crate::game::trap::disarm(self.0, pos) {
+                    self.0.finish_player_turn();
+                }
+            }
         }
     }
 }
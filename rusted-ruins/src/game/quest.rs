@@ -1,22 +1,35 @@
 //! Quest handlings
 
-use super::chara::gen::choose_npc_chara_template;
+use super::chara::gen::{choose_npc_chara_template, create_chara};
+use super::extrait::*;
+use super::item::gen::gen_item_from_idx;
+use super::map::choose_empty_tile;
 use super::Game;
+use crate::text::obj_txt;
 use common::gamedata::*;
-use common::objholder::CharaTemplateIdx;
+use common::gobj;
+use common::obj::StoryQuestObject;
+use common::objholder::{CharaTemplateIdx, ItemIdx};
+use rng::SliceRandom;
+use rules::quest_template::{QuestKindTemplate, QuestTemplate};
 use rules::RULES;
 
-/// Update quest list of current town
-pub fn update_town_quest(gd: &mut GameData) {
-    let mid = gd.get_current_mapid();
-    let town = match gd.region.get_site_mut(mid.sid()).content {
-        SiteContent::Town { ref mut town } => town,
+/// Update quest list of the town
+pub fn update_town_quest(gd: &mut GameData, sid: SiteId) {
+    let town_id = match gd.region.get_site(sid).content {
+        SiteContent::Town { ref town } => town.id().to_owned(),
         _ => unreachable!(),
     };
 
-    town.quests.clear();
+    let quests: Vec<Quest> = (0..RULES.quest.n_town_quests)
+        .filter_map(|_| gen_quest(gd, sid, &town_id))
+        .collect();
 
-    town.quests.push(gen_quest());
+    let town = match gd.region.get_site_mut(sid).content {
+        SiteContent::Town { ref mut town } => town,
+        _ => unreachable!(),
+    };
+    town.quests = quests;
 }
 
 /// Returns available quest in the current town
@@ -38,61 +51,446 @@ pub fn undertake_quest(game: &mut Game<'_>, i: u32) {
         _ => unreachable!(),
     };
 
-    let quest = town.quests.remove(i as usize);
+    let mut quest = town.quests.remove(i as usize);
+
+    match quest.kind {
+        QuestKind::Deliver { idx, n, .. } => {
+            let mut item = gen_item_from_idx(idx, 1);
+            item.attrs.push(ItemAttr::QuestItem);
+            game.gd
+                .get_item_list_mut(ItemListLocation::PLAYER)
+                .append(item, n);
+        }
+        QuestKind::Escort {
+            ref mut cid, idx, ..
+        } => {
+            let lv = game.gd.chara.get(CharaId::Player).lv;
+            let chara = create_chara(idx, lv, FactionId::player(), None);
+            *cid = game.gd.add_chara_to_party(chara);
+            if cid.is_none() {
+                // Put the quest back to the board so that it can be undertaken later
+                game_log!("quest-escort-party-full");
+                if let SiteContent::Town { ref mut town } =
+                    game.gd.region.get_site_mut(mid.sid()).content
+                {
+                    town.quests.insert(i as usize, quest);
+                }
+                return;
+            }
+        }
+        _ => (),
+    }
+
     game.gd.quest.start_new_quest(quest);
 }
 
-/// Generate an quest
-fn gen_quest() -> Quest {
+/// Faction quests are offered only to players with good reputation in the faction
+fn faction_quest_unlocked(gd: &GameData, template: &QuestTemplate) -> bool {
+    if let Some(faction) = template.faction {
+        let min_relation = template
+            .min_relation
+            .unwrap_or(RULES.faction.relation_friend);
+        gd.faction.get(faction) >= min_relation
+    } else {
+        true
+    }
+}
+
+/// Generate a quest offered in the given town
+fn gen_quest(gd: &GameData, sid: SiteId, town_id: &str) -> Option<Quest> {
+    let lv = gd.chara.get(CharaId::Player).lv;
+    let templates: Vec<(&String, &QuestTemplate)> = RULES
+        .quest_templates
+        .iter()
+        .filter(|(_, template)| {
+            template.min_level <= lv
+                && (template.towns.is_empty() || template.towns.iter().any(|t| t == town_id))
+                && faction_quest_unlocked(gd, template)
+        })
+        .collect();
+    let (_, (id, template)) = rng::choose(&templates, |(_, template)| template.weight)?;
+
+    let kind = gen_quest_kind(gd, sid, &template.kind, lv)?;
+
+    let factor = (1.0 + lv as f32 * RULES.quest.reward_level_factor)
+        * RULES
+            .town
+            .quest_reward_factor
+            .get(town_id)
+            .copied()
+            .unwrap_or(1.0);
     let reward = Reward {
-        money: 1000,
-        item: Vec::new(),
+        money: (template.reward as f32 * factor) as i64,
+        item: template
+            .reward_items
+            .iter()
+            .filter_map(|id| gobj::id_to_idx_checked(id))
+            .collect(),
     };
+    let deadline = template
+        .deadline_days
+        .map(|days| gd.time.current_time() + Duration::from_days(days.into()));
 
-    Quest::SlayMonsters {
+    Some(Quest {
+        id: (*id).clone(),
+        kind,
         reward,
-        idx: choose_npc_chara_template(&RULES.quest.slay_race_probability, 1).unwrap(),
-        goal: 10,
-        killed: 0,
+        deadline,
+    })
+}
+
+fn gen_quest_kind(
+    gd: &GameData,
+    sid: SiteId,
+    template: &QuestKindTemplate,
+    lv: u32,
+) -> Option<QuestKind> {
+    let kind = match template {
+        QuestKindTemplate::SlayMonsters { goal } => QuestKind::SlayMonsters {
+            idx: choose_npc_chara_template(&RULES.quest.slay_race_probability, lv.max(1))?,
+            goal: *goal,
+            killed: 0,
+        },
+        QuestKindTemplate::Fetch { items, n } => QuestKind::Fetch {
+            idx: choose_id(items)?,
+            n: *n,
+        },
+        QuestKindTemplate::Deliver { items, n } => QuestKind::Deliver {
+            idx: choose_id(items)?,
+            n: *n,
+            destination: choose_site(gd, sid, |other, _| {
+                other.kind == SiteKind::Town && other != sid
+            })?,
+        },
+        QuestKindTemplate::Escort { charas } => QuestKind::Escort {
+            cid: None,
+            idx: choose_id(charas)?,
+            destination: choose_site(gd, sid, |other, _| {
+                other.kind == SiteKind::Town && other != sid
+            })?,
+        },
+        QuestKindTemplate::Explore { floor } => {
+            let (sid, floor) = choose_dungeon(gd, sid, *floor)?;
+            QuestKind::Explore { sid, floor }
+        }
+        QuestKindTemplate::Retrieve { items, floor } => {
+            let idx = choose_id(items)?;
+            let (sid, floor) = choose_dungeon(gd, sid, *floor)?;
+            QuestKind::Retrieve {
+                idx,
+                sid,
+                floor,
+                placed: false,
+            }
+        }
+        QuestKindTemplate::Craft { items, n } => QuestKind::Craft {
+            idx: choose_id(items)?,
+            goal: *n,
+            crafted: 0,
+        },
+    };
+    Some(kind)
+}
+
+fn choose_id<T: common::objholder::ObjectIndex>(ids: &[String]) -> Option<T> {
+    let id = ids.choose(&mut rng::get_rng())?;
+    let idx = gobj::id_to_idx_checked(id);
+    if idx.is_none() {
+        warn!("unknown object id \"{}\" in quest templates", id);
     }
+    idx
+}
+
+/// Choose a site in the same region
+fn choose_site<F: FnMut(SiteId, &Site) -> bool>(
+    gd: &GameData,
+    sid: SiteId,
+    mut f: F,
+) -> Option<SiteId> {
+    let sids: Vec<SiteId> = gd
+        .region
+        .get(sid.rid)
+        .iter_sites()
+        .filter(|(sid, site)| f(*sid, site))
+        .map(|(sid, _)| sid)
+        .collect();
+    sids.choose(&mut rng::get_rng()).copied()
+}
+
+/// Choose a dungeon and its floor. The floor is limited by the deepest floor of the dungeon.
+fn choose_dungeon(gd: &GameData, sid: SiteId, floor: u32) -> Option<(SiteId, u32)> {
+    let sid = choose_site(gd, sid, |sid, site| {
+        sid.kind == SiteKind::AutoGenDungeon && site.max_floor() > 0
+    })?;
+    let max_floor = gd.region.get_site(sid).max_floor();
+    Some((sid, floor.clamp(1, max_floor)))
 }
 
 pub fn count_slayed_monster(gd: &mut GameData, t: CharaTemplateIdx) {
     for (state, quest) in gd.quest.iter_mut() {
-        match quest {
-            Quest::SlayMonsters {
-                idx, goal, killed, ..
-            } => {
-                if *state == QuestState::Active && *idx == t {
-                    *killed += 1;
-                    if *killed == *goal {
-                        *state = QuestState::Completed;
-                        // Log
-                        game_log!("quest-complete-slay_monsters"; monster=idx, n=goal);
-                    }
+        if let QuestKind::SlayMonsters { idx, goal, killed } = &mut quest.kind {
+            if *state == QuestState::Active && *idx == t {
+                *killed += 1;
+                if *killed == *goal {
+                    *state = QuestState::Completed;
+                    // Log
+                    game_log!("quest-complete-slay_monsters"; monster=idx, n=goal);
                 }
             }
         }
     }
 }
 
+pub fn count_crafted_item(gd: &mut GameData, t: ItemIdx) {
+    for (state, quest) in gd.quest.iter_mut() {
+        if let QuestKind::Craft { idx, goal, crafted } = &mut quest.kind {
+            if *state == QuestState::Active && *idx == t {
+                *crafted += 1;
+                if *crafted == *goal {
+                    *state = QuestState::Completed;
+                    game_log!("quest-complete"; quest=quest);
+                }
+            }
+        }
+    }
+}
+
+/// Process quests related to the current map after the player arrived
+pub fn arrive_at_map(gd: &mut GameData) {
+    let mid = gd.get_current_mapid();
+    if mid.is_region_map() {
+        return;
+    }
+    let current_sid = mid.sid();
+    let current_floor = mid.floor() + 1;
+
+    let mut quest_holder = std::mem::take(&mut gd.quest);
+    for (state, quest) in quest_holder.iter_mut() {
+        if *state != QuestState::Active {
+            continue;
+        }
+        match quest.kind {
+            QuestKind::Deliver {
+                idx,
+                n,
+                destination,
+            } if destination == current_sid => {
+                if consume_quest_items(gd, idx, n, true) {
+                    *state = QuestState::Completed;
+                    game_log!("quest-complete"; quest=quest);
+                }
+            }
+            QuestKind::Escort {
+                cid: Some(cid),
+                destination,
+                ..
+            } if destination == current_sid && gd.player.party.contains(&cid) => {
+                remove_escort(gd, cid);
+                *state = QuestState::Completed;
+                game_log!("quest-complete"; quest=quest);
+            }
+            QuestKind::Explore { sid, floor } if sid == current_sid && current_floor >= floor => {
+                *state = QuestState::Completed;
+                game_log!("quest-complete"; quest=quest);
+            }
+            QuestKind::Retrieve {
+                idx,
+                sid,
+                floor,
+                ref mut placed,
+            } if sid == current_sid && floor == current_floor && !*placed => {
+                if let Some(pos) = choose_empty_tile(gd.get_current_map()) {
+                    let mut item = gen_item_from_idx(idx, current_floor);
+                    item.attrs.push(ItemAttr::QuestItem);
+                    gd.add_item_on_tile(pos, item, 1);
+                    *placed = true;
+                }
+            }
+            _ => (),
+        }
+    }
+    gd.quest = quest_holder;
+}
+
+/// Fail quests that are expired, or cannot be completed
+pub fn check_quests(gd: &mut GameData) {
+    let now = gd.time.current_time();
+
+    let mut quest_holder = std::mem::take(&mut gd.quest);
+    for (state, quest) in quest_holder.iter_mut() {
+        if *state != QuestState::Active {
+            continue;
+        }
+        let expired = quest
+            .deadline
+            .map(|deadline| deadline < now)
+            .unwrap_or(false);
+        let site_lost = match quest.kind {
+            QuestKind::Explore { sid, .. } | QuestKind::Retrieve { sid, .. } => {
+                gd.region.get_site_checked(sid).is_none()
+            }
+            _ => false,
+        };
+        if expired || site_lost {
+            fail_quest(gd, state, quest);
+        }
+    }
+    gd.quest = quest_holder;
+}
+
+/// Fail escort quests when the escorted character is dead
+pub fn escort_dead(gd: &mut GameData, dead_cid: CharaId) {
+    let mut quest_holder = std::mem::take(&mut gd.quest);
+    for (state, quest) in quest_holder.iter_mut() {
+        match quest.kind {
+            QuestKind::Escort { cid: Some(cid), .. }
+                if cid == dead_cid && *state == QuestState::Active =>
+            {
+                fail_quest(gd, state, quest);
+            }
+            _ => (),
+        }
+    }
+    gd.quest = quest_holder;
+}
+
+fn fail_quest(gd: &mut GameData, state: &mut QuestState, quest: &Quest) {
+    *state = QuestState::Failed;
+    if let QuestKind::Escort { cid: Some(cid), .. } = quest.kind {
+        remove_escort(gd, cid);
+    }
+    game_log!("quest-failed"; quest=quest);
+}
+
+/// Remove the escorted character from the party and the game
+fn remove_escort(gd: &mut GameData, cid: CharaId) {
+    let in_party = gd.player.party.remove(&cid);
+    let dead = gd.player.party_dead.remove(&cid);
+    if in_party || dead {
+        gd.remove_chara(cid);
+    }
+}
+
+/// Remove the items required by a quest from the player's inventory if there are enough.
+/// Items given or placed for quests are used only for the quests that need them.
+fn consume_quest_items(gd: &mut GameData, idx: ItemIdx, n: u32, quest_item: bool) -> bool {
+    let il = gd.get_item_list_mut(ItemListLocation::PLAYER);
+    let required = |item: &Item| item.idx == idx && item.is_quest_item() == quest_item;
+    if il.count_if(required) < n {
+        return false;
+    }
+    il.consume_if(n, required);
+    true
+}
+
+/// Complete quests that need items, and receive rewards of completed quests
 pub fn receive_rewards(gd: &mut GameData) -> bool {
     let mut money = 0;
+    let mut items = Vec::new();
+    let mut relations = Vec::new();
     let mut exist_completed_quest = false;
 
-    for (state, quest) in gd.quest.iter_mut() {
+    let mut quest_holder = std::mem::take(&mut gd.quest);
+    for (state, quest) in quest_holder.iter_mut() {
+        if *state == QuestState::Active {
+            let (idx, n, quest_item) = match quest.kind {
+                QuestKind::Fetch { idx, n } => (idx, n, false),
+                QuestKind::Retrieve { idx, .. } => (idx, 1, true),
+                _ => continue,
+            };
+            if consume_quest_items(gd, idx, n, quest_item) {
+                *state = QuestState::Completed;
+                game_log!("quest-complete"; quest=quest);
+            }
+        }
+
         if *state == QuestState::Completed {
             exist_completed_quest = true;
             let reward = quest.reward();
             money += reward.money;
+            items.extend_from_slice(&reward.item);
+            if let Some(template) = RULES.quest_templates.get(&quest.id) {
+                match template.faction {
+                    Some(faction) if template.reward_relation != 0 => {
+                        relations.push((faction, template.reward_relation));
+                    }
+                    _ => (),
+                }
+            }
             *state = QuestState::RewardReceived;
         }
     }
+    gd.quest = quest_holder;
+    gd.quest.remove_failed();
 
     if exist_completed_quest {
         gd.quest.remove_reward_received();
         gd.player.add_money(money);
         game_log!("quest-reward-receive-money"; money=money);
+        let lv = gd.chara.get(CharaId::Player).lv;
+        for idx in items {
+            let item = gen_item_from_idx(idx, lv);
+            game_log!("quest-reward-receive-item"; item=item);
+            gd.get_item_list_mut(ItemListLocation::PLAYER)
+                .append(item, 1);
+        }
+        for (faction, value) in relations {
+            crate::game::faction::change_relation(gd, faction, value);
+        }
     }
     exist_completed_quest
 }
+
+/// Advance the story quest to the given stage. The quest is started if it is not started yet,
+/// and it can be started only from the first stage.
+/// Returns false if the transition is not allowed.
+pub fn advance_story_quest(gd: &mut GameData, id: &str, stage: &str) -> bool {
+    let obj: &StoryQuestObject = if let Some(obj) = gobj::get_by_id_checked(id) {
+        obj
+    } else {
+        warn!("unknown story quest \"{}\"", id);
+        return false;
+    };
+    let next_stage = if let Some(next_stage) = obj.stage(stage) {
+        next_stage
+    } else {
+        warn!("unknown stage \"{}\" for story quest \"{}\"", stage, id);
+        return false;
+    };
+
+    let quest_name = obj_txt(id);
+    if let Some(quest) = gd.quest.story_quest(id) {
+        if quest.completed {
+            warn!("story quest \"{}\" is already completed", id);
+            return false;
+        }
+        if let Some(current_stage) = obj.stage(&quest.stage) {
+            if !current_stage.next.is_empty() && !current_stage.next.iter().any(|s| s == stage) {
+                warn!(
+                    "story quest \"{}\" cannot advance from \"{}\" to \"{}\"",
+                    id, quest.stage, stage
+                );
+                return false;
+            }
+        }
+        game_log!("story-quest-updated"; quest=quest_name);
+    } else {
+        // The quest must start from the first stage
+        if obj.stages.first().map(|first| first.name.as_str()) != Some(stage) {
+            warn!(
+                "story quest \"{}\" is not started and cannot begin with \"{}\"",
+                id, stage
+            );
+            return false;
+        }
+        game_log!("story-quest-start"; quest=quest_name);
+    }
+
+    let now = gd.time.current_time();
+    gd.quest
+        .set_story_quest_stage(id, stage, now, next_stage.end);
+    if next_stage.end {
+        game_log!("story-quest-complete"; quest=quest_name);
+    }
+    true
+}
@@ -1,13 +1,22 @@
+use super::extrait::*;
 use super::map::choose_empty_tile;
 use super::saveload::{gen_box_id, get_map_dir};
 use common::basic::MAX_AUTO_GEN_DUNGEONS;
 use common::gamedata::*;
 use common::gobj;
+use common::objholder::SpecialTileIdx;
 use common::regiongen::*;
+use geom::Vec2d;
+use map_generator::world::Terrain;
 use rng::*;
+use rules::region_gen::{ProceduralRegion, RegionLinkKind};
 use rules::RULES;
 
 pub fn add_region(gd: &mut GameData, id: &str) {
+    if let Some(pr) = RULES.region_gen.regions.get(id) {
+        add_procedural_region(gd, id, pr);
+        return;
+    }
     let rg: &RegionGenObject = gobj::get_by_id(id);
 
     let map = if let Some(map) = super::map::from_template::from_template_id(&rg.id, false) {
@@ -22,6 +31,101 @@ pub fn add_region(gd: &mut GameData, id: &str) {
     add_sites_from_genobj(gd, rg, rid);
 }
 
+/// Generate a region from noise. The same seed generates the same region.
+fn add_procedural_region(gd: &mut GameData, id: &str, pr: &ProceduralRegion) {
+    let next_seed: u64 = get_rng().gen();
+    let seed = pr.seed.unwrap_or_else(|| get_rng().gen());
+    info!("Generating region \"{}\" from seed {}", id, seed);
+    reseed_with(seed);
+
+    let mut param = pr.world.clone();
+    param.n_towns = pr.towns.len() as u32;
+    let world = param.generate();
+
+    let mut map = Map::new(param.w, param.h);
+    for p in world.terrain.iter_idx() {
+        let terrain_tile =
+            if let Some(terrain_tile) = RULES.region_gen.terrain.get(&world.terrain[p]) {
+                terrain_tile
+            } else {
+                warn!("Tile for terrain {:?} is not specified", world.terrain[p]);
+                continue;
+            };
+        let tile = &mut map.tile[p];
+        tile.tile = terrain_tile.tile.into();
+        if let Some(wall) = terrain_tile.wall {
+            tile.wall = WallIdxPp::new(wall);
+            tile.wall_hp = gobj::get_obj(wall).hp;
+        }
+        let overlay = if world.road[p] {
+            RULES.region_gen.road_tile
+        } else if world.river[p] {
+            RULES.region_gen.river_tile
+        } else {
+            None
+        };
+        if let Some(overlay) = overlay {
+            tile.tile[1] = TileIdxPp::new(overlay);
+            tile.wall = WallIdxPp::empty();
+            tile.wall_hp = 0;
+        }
+    }
+    map.music = pr.music.clone();
+
+    let region = Region::new(id, map, gen_box_id(gd));
+    let rid = gd.region.add_region(region);
+
+    for (town_id, &pos) in pr.towns.iter().zip(&world.towns) {
+        super::town::add_town(gd, rid, pos, town_id);
+        info!("Created new a town \"{}\" at {} in {:?}", town_id, pos, rid);
+    }
+    if world.towns.len() < pr.towns.len() {
+        warn!("No space for {} towns", pr.towns.len() - world.towns.len());
+    }
+
+    let mut occupied = Vec::new();
+    for (site_gen_id, terrains) in &pr.others {
+        let pos = world.choose_site_pos(&occupied, |terrain| {
+            if terrains.contains(&terrain) {
+                1.0
+            } else {
+                0.0
+            }
+        });
+        let pos = if let Some(pos) = pos {
+            pos
+        } else {
+            warn!("No space for site \"{}\"", site_gen_id);
+            continue;
+        };
+        occupied.push(pos);
+        super::site::gen::add_site_from_obj(gd, rid, pos, site_gen_id);
+        info!(
+            "Created new an other site \"{}\" at {} in {:?}",
+            site_gen_id, pos, rid
+        );
+    }
+
+    reseed_with(next_seed);
+}
+
+/// The position where the player starts in the region
+pub fn start_pos(gd: &GameData, rid: RegionId) -> Vec2d {
+    let region = gd.region.get(rid);
+    if !RULES.region_gen.regions.contains_key(&region.name) {
+        return RULES.newgame.start_pos;
+    }
+
+    // Start from the first town on procedurally generated regions
+    region
+        .iter_sites()
+        .filter(|(sid, _)| sid.kind == SiteKind::Town)
+        .min_by_key(|(sid, _)| sid.n)
+        .and_then(|(sid, _)| gd.region.get_site_pos(sid))
+        .and_then(|pos| region.get_map().empty_tile_around(pos))
+        .unwrap_or(RULES.newgame.start_pos)
+}
+
 /// Generate dungeons up to the max
 pub fn gen_dungeon_max(gd: &mut GameData, rid: RegionId) {
     info!("gen dungeons");
@@ -45,9 +149,30 @@ pub fn gen_dungeon(gd: &mut GameData, rid: RegionId) {
     let mid = MapId::from(rid);
     gd.region.preload_map(mid, get_map_dir(gd));
 
+    let dungeon_kind = RULES
+        .region_gen
+        .params
+        .get(&gd.region.get(rid).name)
+        .and_then(|params| {
+            params
+                .dungeon_kinds
+                .choose_weighted(&mut get_rng(), |item| item.1)
+                .ok()
+        })
+        .map(|item| item.0)
+        .unwrap_or_else(|| {
+            RULES
+                .dungeon_gen
+                .keys()
+                .collect::<Vec<_>>()
+                .choose(&mut get_rng())
+                .copied()
+                .copied()
+                .unwrap()
+        });
     let pos = {
         let region_map = gd.region.get_map(mid);
-        match choose_empty_tile(region_map) {
+        match choose_dungeon_pos(region_map, dungeon_kind) {
             Some(pos) => pos,
             None => {
                 warn!("Dungeon generation failed: No empty tile");
@@ -55,16 +180,8 @@ pub fn gen_dungeon(gd: &mut GameData, rid: RegionId) {
             }
         }
     };
-    let dungeon_kind = RULES
-        .dungeon_gen
-        .keys()
-        .collect::<Vec<_>>()
-        .choose(&mut get_rng())
-        .copied()
-        .copied()
-        .unwrap();
 
-    super::dungeon_gen::add_dungeon_site(gd, dungeon_kind, pos);
+    super::dungeon_gen::add_dungeon_site(gd, rid, dungeon_kind, pos);
 
     let region_map = gd.region.get_map_mut(mid);
     let site_symbol_kind = RULES.dungeon_gen[&dungeon_kind].symbol;
@@ -73,6 +190,94 @@ pub fn gen_dungeon(gd: &mut GameData, rid: RegionId) {
     };
 }
 
+/// Choose a tile for the dungeon by the terrain affinity
+fn choose_dungeon_pos(map: &Map, dungeon_kind: DungeonKind) -> Option<Vec2d> {
+    let affinity = if let Some(affinity) = RULES.region_gen.dungeon_affinity.get(&dungeon_kind) {
+        affinity
+    } else {
+        return choose_empty_tile(map);
+    };
+    choose_tile_by_terrain(map, |terrain| {
+        affinity
+            .iter()
+            .find(|(t, _)| *t == terrain)
+            .map_or(0.0, |(_, weight)| *weight)
+    })
+    .or_else(|| choose_empty_tile(map))
+}
+
+/// Choose an empty tile on the region map with weights by its terrain
+fn choose_tile_by_terrain<F: Fn(Terrain) -> f32>(map: &Map, weight: F) -> Option<Vec2d> {
+    let candidates: Vec<(Vec2d, f32)> = map
+        .tile
+        .iter_idx()
+        .filter(|pos| map.is_empty_tile(*pos) && map.tile[*pos].special.is_none())
+        .filter_map(|pos| {
+            let terrain = RULES
+                .region_gen
+                .terrain_by_tile(map.tile[pos].tile[0].idx()?)?;
+            Some((pos, weight(terrain)))
+        })
+        .collect();
+
+    candidates
+        .choose_weighted(&mut get_rng(), |(_, weight)| *weight)
+        .ok()
+        .map(|(pos, _)| *pos)
+}
+
+/// Place ports and portals on region maps for the links between regions
+pub fn add_region_links(gd: &mut GameData) {
+    for link in &RULES.region_gen.links {
+        let (a, b) = match (
+            gd.region.get_id_by_name(&link.regions[0]),
+            gd.region.get_id_by_name(&link.regions[1]),
+        ) {
+            (Some(a), Some(b)) => (a, b),
+            _ => {
+                warn!("Unknown regions for link {:?}", link.regions);
+                continue;
+            }
+        };
+
+        for (i, &(rid, dest)) in [(a, b), (b, a)].iter().enumerate() {
+            let map = gd.region.get_map_mut(MapId::from(rid));
+            let pos =
+                link.pos[i].or_else(|| match link.kind {
+                    RegionLinkKind::Ship => choose_tile_by_terrain(map, |terrain| {
+                        if terrain == Terrain::Coast {
+                            1.0
+                        } else {
+                            0.0
+                        }
+                    })
+                    .or_else(|| choose_empty_tile(map)),
+                    RegionLinkKind::Portal => choose_empty_tile(map),
+                });
+            let pos = if let Some(pos) = pos {
+                pos
+            } else {
+                warn!("No space for the link to {:?} in {:?}", dest, rid);
+                continue;
+            };
+            let special = match link.kind {
+                RegionLinkKind::Ship => SpecialTileKind::Port { rid: dest },
+                RegionLinkKind::Portal => SpecialTileKind::Portal { rid: dest },
+            };
+            // The link works without the object, but it is not drawn on the region map
+            let obj_id = special.obj_id().unwrap();
+            if gobj::id_to_idx_checked::<SpecialTileIdx>(obj_id).is_none() {
+                warn!(
+                    "The link to {:?} in {:?} is not drawn without \"{}\"",
+                    dest, rid, obj_id
+                );
+            }
+            map.tile[pos].special = special;
+            info!("Created new a link to {:?} at {} in {:?}", dest, pos, rid);
+        }
+    }
+}
+
 fn add_sites_from_genobj(gd: &mut GameData, rg: &RegionGenObject, rid: RegionId) {
     // Add towns
     for &(ref site_gen_id, pos) in &rg.towns {
@@ -0,0 +1,204 @@
+//! Waiting, resting and sleeping of the player
+
+use crate::game::extrait::*;
+use crate::game::{Game, InfoGetter};
+use common::gamedata::*;
+use rules::RULES;
+
+/// Returns the kind of rest if the character is resting
+pub fn resting(chara: &Chara) -> Option<RestKind> {
+    chara.status.iter().find_map(|s| match s {
+        CharaStatus::Work {
+            work: Work::Rest { kind },
+            ..
+        } => Some(*kind),
+        _ => None,
+    })
+}
+
+fn is_sleeping(chara: &Chara) -> bool {
+    matches!(resting(chara), Some(RestKind::Sleep { .. }))
+}
+
+fn is_too_hungry(chara: &Chara) -> bool {
+    chara
+        .status
+        .iter()
+        .any(|s| matches!(s, CharaStatus::Weak | CharaStatus::Starving))
+}
+
+fn hostile_in_view(game: &Game<'_>) -> bool {
+    let gd = &game.gd;
+    gd.get_charas_on_map().into_iter().any(|cid| {
+        gd.chara_relation(CharaId::Player, cid) == Relationship::Hostile
+            && gd
+                .chara_pos(cid)
+                .map(|pos| game.view_map.get_tile_visible(pos))
+                .unwrap_or(false)
+    })
+}
+
+/// Start resting. Returns false if the player cannot rest now.
+fn start_rest(game: &mut Game<'_>, kind: RestKind, turns: u16) -> bool {
+    let player = game.gd.chara.get(CharaId::Player);
+    if is_too_hungry(player) {
+        game_log!("rest-too-hungry"; chara=player);
+        return false;
+    }
+    if hostile_in_view(game) {
+        game_log!("rest-hostile-in-view");
+        return false;
+    }
+
+    let player = game.gd.chara.get_mut(CharaId::Player);
+    match kind {
+        RestKind::Wait => {
+            game_log!("rest-start-wait"; chara=player, turns=turns);
+        }
+        RestKind::UntilHealed => {
+            game_log!("rest-start"; chara=player);
+        }
+        RestKind::Sleep { .. } => {
+            game_log!("rest-start-sleep"; chara=player);
+        }
+    }
+    player.add_status(CharaStatus::Work {
+        turn_left: turns,
+        needed_turn: turns,
+        work: Work::Rest { kind },
+    });
+    true
+}
+
+pub fn wait_turns(game: &mut Game<'_>, turns: u16) -> bool {
+    let turns = turns.min(RULES.rest.max_turns);
+    turns > 0 && start_rest(game, RestKind::Wait, turns)
+}
+
+pub fn rest_until_healed(game: &mut Game<'_>) -> bool {
+    let player = game.gd.chara.get(CharaId::Player);
+    if player.hp >= player.attr.max_hp && player.mp >= player.attr.max_mp {
+        game_log!("rest-no-need"; chara=player);
+        return false;
+    }
+    start_rest(game, RestKind::UntilHealed, RULES.rest.max_turns)
+}
+
+/// Sleep until the next morning. A bed next to the player improves recovery.
+pub fn sleep_until_morning(game: &mut Game<'_>) -> bool {
+    let now = crate::game::time::current_time().as_secs();
+    let wake_up = RULES.rest.wake_up_hour as u64 * SECS_PER_HOUR;
+    let secs_in_day = now % SECS_PER_DAY;
+    let until = if secs_in_day < wake_up {
+        now - secs_in_day + wake_up
+    } else {
+        now - secs_in_day + SECS_PER_DAY + wake_up
+    };
+    let kind = RestKind::Sleep {
+        until: Time::from_secs(until),
+        bed_quality: crate::game::base::search_bed(&game.gd),
+    };
+    start_rest(game, kind, RULES.rest.max_turns)
+}
+
+/// Check the player's rest before the turn.
+/// Resting is interrupted by hostiles in view or hunger, and finished when its purpose is completed.
+pub fn update_rest(game: &mut Game<'_>) {
+    let player = game.gd.chara.get(CharaId::Player);
+    let kind = if let Some(kind) = resting(player) {
+        kind
+    } else {
+        return;
+    };
+
+    if hostile_in_view(game) {
+        interrupt_rest(&mut game.gd, "rest-interrupted-hostile");
+        return;
+    }
+    let player = game.gd.chara.get(CharaId::Player);
+    if is_too_hungry(player) {
+        interrupt_rest(&mut game.gd, "rest-interrupted-hungry");
+        return;
+    }
+
+    let finished = match kind {
+        RestKind::Wait => false,
+        RestKind::UntilHealed => player.hp >= player.attr.max_hp && player.mp >= player.attr.max_mp,
+        RestKind::Sleep { until, .. } => crate::game::time::current_time() >= until,
+    };
+    if finished {
+        let player = game.gd.chara.get_mut(CharaId::Player);
+        for s in player.status.iter_mut() {
+            if let CharaStatus::Work {
+                work: Work::Rest { .. },
+                turn_left,
+                ..
+            } = s
+            {
+                *turn_left = 0;
+            }
+        }
+    }
+}
+
+/// Stop the player's rest with given log message
+pub fn interrupt_rest(gd: &mut GameData, log_id: &str) {
+    let player = gd.chara.get_mut(CharaId::Player);
+    if resting(player).is_none() {
+        return;
+    }
+    player.status.retain(|s| {
+        !matches!(
+            s,
+            CharaStatus::Work {
+                work: Work::Rest { .. },
+                ..
+            }
+        )
+    });
+    game_log!(log_id; chara=player);
+}
+
+/// Called when the rest status expired
+pub fn finish_rest(gd: &mut GameData, cid: CharaId, kind: RestKind) {
+    let chara = gd.chara.get(cid);
+    match kind {
+        RestKind::Wait | RestKind::UntilHealed => {
+            game_log!("rest-finish"; chara=chara);
+        }
+        RestKind::Sleep { .. } => {
+            game_log!("rest-wake-up"; chara=chara);
+        }
+    }
+}
+
+/// Factor for game time passing per turn
+pub fn time_factor(gd: &GameData) -> f32 {
+    if is_sleeping(gd.chara.get(CharaId::Player)) {
+        RULES.rest.time_factor_sleep
+    } else {
+        1.0
+    }
+}
+
+/// Factor for HP and MP regeneration
+pub fn regeneration_factor(chara: &Chara) -> f32 {
+    match resting(chara) {
+        Some(RestKind::Sleep { bed_quality, .. }) => {
+            let quality = bed_quality.unwrap_or(0).max(0) as f32;
+            RULES.rest.regeneration_factor_sleep
+                + RULES.rest.regeneration_factor_per_bed_quality * quality
+        }
+        Some(_) => RULES.rest.regeneration_factor_rest,
+        None => 1.0,
+    }
+}
+
+/// Factor for sp consumption
+pub fn sp_consumption_factor(chara: &Chara) -> f32 {
+    if is_sleeping(chara) {
+        RULES.rest.sp_consumption_factor_sleep
+    } else {
+        1.0
+    }
+}
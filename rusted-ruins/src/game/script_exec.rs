@@ -70,15 +70,33 @@ impl<'s> Game<'s> {
                 } else {
                     return AdvanceScriptResult::Quit;
                 };
+                if crate::game::faction::refuses_trade(&self.gd, cid) {
+                    game_log!("shop-refuse"; chara=self.gd.chara.get(cid));
+                    self.gd.script_exec.clear();
+                    return AdvanceScriptResult::Quit;
+                }
+                crate::game::shop::refill_unset_funds(&mut self.gd, cid);
                 self.request_dialog_open(DialogOpenRequest::ShopBuy { cid });
                 AdvanceScriptResult::Continue
             }
             ScriptYield::ShopSell => {
-                self.request_dialog_open(DialogOpenRequest::ShopSell);
+                let cid = if let Some(cid) = self.gd.script_exec.target_chara {
+                    cid
+                } else {
+                    return AdvanceScriptResult::Quit;
+                };
+                if crate::game::faction::refuses_trade(&self.gd, cid) {
+                    game_log!("shop-refuse"; chara=self.gd.chara.get(cid));
+                    self.gd.script_exec.clear();
+                    return AdvanceScriptResult::Quit;
+                }
+                crate::game::shop::refill_unset_funds(&mut self.gd, cid);
+                self.request_dialog_open(DialogOpenRequest::ShopSell { cid });
                 AdvanceScriptResult::Continue
             }
             ScriptYield::Quest => {
-                crate::game::quest::update_town_quest(&mut self.gd);
+                let sid = self.gd.get_current_mapid().sid();
+                crate::game::town::update_town(&mut self.gd, sid);
                 self.request_dialog_open(DialogOpenRequest::Quest);
                 AdvanceScriptResult::Continue
             }
@@ -12,6 +12,18 @@ pub fn init() {
             crate::game::region::gen_dungeon_max(gd, mid.rid());
         },
         gen_party_chara: |gd, id, lv| gd.gen_party_chara(id, lv),
+        hire: |gd| {
+            if let Some(cid) = gd.script_exec.target_chara {
+                crate::game::party::hire(gd, cid)
+            } else {
+                false
+            }
+        },
+        hire_wage: |gd| {
+            let cid = gd.script_exec.target_chara?;
+            Some(crate::game::party::wage(gd.chara.get(cid)))
+        },
+        quest_advance: crate::game::quest::advance_story_quest,
         receive_quest_rewards: crate::game::quest::receive_rewards,
         receive_item: |gd, id, n| {
             let item = crate::game::item::gen::gen_item_from_id(id, 1);
@@ -5,10 +5,75 @@ use common::item_selector::ItemSelector;
 use common::sitegen::ShopGenData;
 use rules::RULES;
 
-pub fn buy_item(gd: &mut GameData, il: ItemLocation) {
-    let price = gd.get_item(il).0.price();
+/// Shop price of an item with its breakdown.
+/// Adjustments are ratios and positive values are favorable to the player.
+pub struct ShopPrice {
+    pub base: i64,
+    pub cha: f32,
+    pub skill: f32,
+    pub relation: f32,
+    pub supply: f32,
+    pub total: i64,
+}
+
+/// Price to buy an item from the shop of given character
+pub fn buying_price(gd: &GameData, cid: CharaId, item: &Item) -> ShopPrice {
+    shop_price(gd, cid, item, true)
+}
+
+/// Price to sell an item to the shop of given character
+pub fn selling_price(gd: &GameData, cid: CharaId, item: &Item) -> ShopPrice {
+    shop_price(gd, cid, item, false)
+}
+
+fn shop_price(gd: &GameData, cid: CharaId, item: &Item, buying: bool) -> ShopPrice {
+    let r = &RULES.town.price;
+    let player = gd.chara.get(CharaId::Player);
+    let shopkeeper = gd.chara.get(cid);
+
+    let base = if buying {
+        item.price()
+    } else if item.stolen().is_some() {
+        (item.selling_price() as f32 * RULES.theft.fence_price_ratio) as i64
+    } else {
+        item.selling_price()
+    };
+    let cha = player.attr.cha as f32 * r.cha_factor;
+    let skill = player.skill_level(SkillKind::Negotiation) as f32 * r.skill_factor;
+    let relation = i16::from(gd.faction.get(shopkeeper.faction)) as f32 * r.relation_factor;
+    // Oversupplied items are cheap for both buying and selling
+    let supply = gd.get_shop(cid).supply(item.obj().kind.rough()) as f32 * r.supply_factor;
+    let supply = if buying { supply } else { -supply };
+
+    let adjustment = cha + skill + relation + supply;
+    let total = if buying {
+        r.apply(base, adjustment, true)
+    } else {
+        r.apply_selling(base, adjustment, shop_price(gd, cid, item, true).total)
+    };
+
+    ShopPrice {
+        base,
+        cha,
+        skill,
+        relation,
+        supply,
+        total,
+    }
+}
+
+pub fn buy_item(gd: &mut GameData, cid: CharaId, il: ItemLocation) {
+    let item = gd.get_item(il).0;
+    let price = buying_price(gd, cid, item).total;
+    let kind = item.obj().kind.rough();
+
     if gd.player.has_money(price) {
         gd.player.sub_money(price);
+        let shop = gd.get_shop_mut(cid);
+        shop.funds += price;
+        change_supply(shop, kind, -RULES.town.price.supply_per_trade);
+        let shop_level = shop.level;
+        crate::game::town::add_trade(gd, price);
         gd.move_item(
             il,
             ItemListLocation::Chara {
@@ -16,32 +81,92 @@ pub fn buy_item(gd: &mut GameData, il: ItemLocation) {
             },
             1,
         );
-        gd.chara.get_mut(CharaId::Player).update();
+        let player = gd.chara.get_mut(CharaId::Player);
+        player.add_skill_exp(SkillKind::Negotiation, RULES.exp.negotiation, shop_level);
+        player.update();
     } else {
         game_log!("shop-lack-of-money"; chara=gd.chara.get(CharaId::Player));
     }
 }
 
-pub fn sell_item(gd: &mut GameData, il: ItemLocation) {
-    let price = gd.get_item(il).0.selling_price();
+pub fn sell_item(gd: &mut GameData, cid: CharaId, il: ItemLocation) {
+    let item = gd.get_item(il).0;
+    let price = selling_price(gd, cid, item).total;
+    let kind = item.obj().kind.rough();
+
+    if item.stolen().is_some() && !gd.get_shop(cid).fence {
+        game_log!("shop-refuse-stolen"; chara=gd.chara.get(cid));
+        return;
+    }
+
+    let shop = gd.get_shop_mut(cid);
+    if shop.funds < price {
+        game_log!("shop-lack-of-funds"; chara=gd.chara.get(cid));
+        return;
+    }
+    shop.funds -= price;
+    change_supply(shop, kind, RULES.town.price.supply_per_trade);
+    let shop_level = shop.level;
     gd.player.add_money(price);
     gd.remove_item(il, 1);
-    gd.chara.get_mut(CharaId::Player).update();
+    let player = gd.chara.get_mut(CharaId::Player);
+    player.add_skill_exp(SkillKind::Negotiation, RULES.exp.negotiation, shop_level);
+    player.update();
+}
+
+fn change_supply(shop: &mut Shop, kind: ItemKindRough, diff: i32) {
+    let max = RULES.town.price.supply_max;
+    let supply = (shop.supply(kind) + diff).clamp(-max, max);
+    shop.supply.insert(kind, supply);
 }
 
-/// Update items on a shop
-pub fn update_items_on_shop(shop: &mut Shop, shop_gen: &ShopGenData) {
+/// Update items on a shop and refill its funds.
+/// Shop level and item quality are raised by the town economy level.
+pub fn update_items_on_shop(shop: &mut Shop, shop_gen: &ShopGenData, economy_level: u32) {
     shop.items.clear();
 
     let n_gen_item = rng::gen_range(RULES.town.min_shop_items..RULES.town.max_shop_items);
+    let level = shop.level + economy_level * RULES.town.economy_shop_level;
+    let quality = (economy_level as f32 * RULES.town.economy_item_quality) as i32;
+
+    let r = &RULES.town.price;
+    shop.funds = restock_funds(level);
+    for supply in shop.supply.values_mut() {
+        *supply = (*supply as f32 * r.supply_restock_decay) as i32;
+    }
+    shop.supply.retain(|_, supply| *supply != 0);
 
     for _ in 0..n_gen_item {
-        if let Some(item) = gen_shop_item(shop.level, shop_gen) {
+        if let Some(mut item) = gen_shop_item(level, shop_gen) {
+            item.quality.base += quality;
             shop.items.append(item, 1);
         }
     }
 }
 
+/// Funds of a shop refilled on restock
+fn restock_funds(level: u32) -> i64 {
+    let r = &RULES.town.price;
+    r.funds_base + r.funds_per_level * level as i64
+}
+
+/// Shops loaded from older saves have no funds, so refill them as on restock
+pub fn refill_unset_funds(gd: &mut GameData, cid: CharaId) {
+    let economy_level = match cid {
+        CharaId::OnSite { sid, .. } => gd
+            .region
+            .get_site(sid)
+            .town()
+            .map_or(0, |town| town.economy_level),
+        _ => 0,
+    };
+    let shop = gd.get_shop_mut(cid);
+    if shop.funds == Shop::FUNDS_UNSET {
+        let level = shop.level + economy_level * RULES.town.economy_shop_level;
+        shop.funds = restock_funds(level);
+    }
+}
+
 /// Generate new item at shops
 fn gen_shop_item(floor_level: u32, shop_gen: &ShopGenData) -> Option<Item> {
     let item_selector: ItemSelector = if shop_gen.shop_kind.is_empty() {
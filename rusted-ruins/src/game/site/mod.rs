@@ -5,4 +5,19 @@ use common::gamedata::*;
 
 /// Additional Site method
 #[extend::ext(pub)]
-impl Site {}
+impl Site {
+    /// Returns town data if this site is a town
+    fn town(&self) -> Option<&Town> {
+        match &self.content {
+            SiteContent::Town { town } => Some(town),
+            _ => None,
+        }
+    }
+
+    fn town_mut(&mut self) -> Option<&mut Town> {
+        match &mut self.content {
+            SiteContent::Town { town } => Some(town),
+            _ => None,
+        }
+    }
+}
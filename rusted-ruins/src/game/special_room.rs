@@ -0,0 +1,233 @@
+//! Special rooms on themed dungeon floors
+
+use crate::game::extrait::*;
+use crate::game::item::gen::gen_item_by_weight;
+use crate::map_generator::RoomRect;
+use common::gamedata::*;
+use common::gobj;
+use common::item_selector::ItemSelector;
+use common::objholder::CharaTemplateIdx;
+use common::sitegen::ShopGenData;
+use geom::*;
+use rng::SliceRandom;
+use rules::dungeon_gen::{FloorTheme, SpecialRoom, SpecialRoomKind};
+use rules::RULES;
+use std::collections::HashMap;
+
+/// Choose rooms and special room kinds by the theme, and generate their contents
+pub fn gen_special_rooms(
+    gd: &mut GameData,
+    mid: MapId,
+    theme: &FloorTheme,
+    rooms: &[RoomRect],
+    nrp: &HashMap<String, f32>,
+    item_gen_weight: &[(ItemSelector, f32)],
+    faction: FactionId,
+) {
+    if theme.special_rooms.is_empty() {
+        return;
+    }
+    let [min, max] = theme.n_special_rooms;
+    let n = rng::gen_range(min..=max.max(min));
+
+    let mut candidates = candidate_rooms(gd.region.get_map(mid), rooms);
+    candidates.shuffle(&mut rng::get_rng());
+
+    for _ in 0..n {
+        if candidates.is_empty() {
+            return;
+        }
+        let special_room = &theme
+            .special_rooms
+            .choose_weighted(&mut rng::get_rng(), |item| item.1)
+            .unwrap()
+            .0;
+        let i = if special_room.kind == SpecialRoomKind::TrappedCorridor {
+            candidates
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, room)| room.size().0.min(room.size().1))
+                .unwrap()
+                .0
+        } else {
+            0
+        };
+        let room = candidates.remove(i);
+        gen_special_room(gd, mid, special_room, room, nrp, item_gen_weight, faction);
+    }
+}
+
+/// Rooms with stairs or entrances are not used
+fn candidate_rooms(map: &Map, rooms: &[RoomRect]) -> Vec<RoomRect> {
+    rooms
+        .iter()
+        .filter(|room| {
+            room.iter()
+                .all(|p| map.tile[p].special.is_none() && !map.entrance.contains(&p))
+        })
+        .copied()
+        .collect()
+}
+
+fn gen_special_room(
+    gd: &mut GameData,
+    mid: MapId,
+    special_room: &SpecialRoom,
+    room: RoomRect,
+    nrp: &HashMap<String, f32>,
+    item_gen_weight: &[(ItemSelector, f32)],
+    faction: FactionId,
+) {
+    trace!("Special room {:?} at {:?}", special_room.kind, room);
+    let floor_level = mid.floor();
+
+    let map = gd.region.get_map_mut(mid);
+    super::dungeon_gen::set_decos(map, room.iter(), &special_room.decos);
+    if special_room.kind == SpecialRoomKind::TrappedCorridor {
+        let prob = RULES.trap.gen_probability_trapped_corridor;
+        super::trap::place_traps(map, room.iter(), prob, faction);
+    }
+
+    // Items
+    let item_gen_weight = if special_room.item_gen_weight.is_empty() {
+        item_gen_weight
+    } else {
+        &special_room.item_gen_weight
+    };
+    let [min, max] = special_room.n_item;
+    for _ in 0..rng::gen_range(min..=max.max(min)) {
+        let map = gd.region.get_map_mut(mid);
+        let p = if let Some(p) = choose_floor_tile_in_room(map, room) {
+            p
+        } else {
+            break;
+        };
+        if let Some(item) = gen_item_by_weight(floor_level, item_gen_weight) {
+            map.locate_item(item, p, 1);
+        }
+    }
+
+    // Npcs
+    let nrp = if special_room.npc_race_probability.is_empty() {
+        nrp
+    } else {
+        &special_room.npc_race_probability
+    };
+    let [min, max] = special_room.n_npc;
+    let n_npc = rng::gen_range(min..=max.max(min));
+    super::map::gen_npcs_by_race(gd, mid, n_npc, floor_level, nrp, |map| {
+        choose_empty_tile_in_room(map, room)
+    });
+
+    if special_room.kind == SpecialRoomKind::Merchant {
+        gen_merchant(gd, mid, special_room, room);
+    }
+}
+
+/// Generate a wandering merchant with a shop
+fn gen_merchant(gd: &mut GameData, mid: MapId, special_room: &SpecialRoom, room: RoomRect) {
+    let idx: CharaTemplateIdx = if let Some(idx) = gobj::id_to_idx_checked(&special_room.merchant) {
+        idx
+    } else {
+        warn!("unknown merchant character \"{}\"", special_room.merchant);
+        return;
+    };
+    if !RULES.town.shop_kinds.contains_key(&special_room.shop_kind) {
+        warn!("unknown shop kind \"{}\"", special_room.shop_kind);
+        return;
+    }
+    let p = if let Some(p) = choose_empty_tile_in_room(gd.region.get_map(mid), room) {
+        p
+    } else {
+        return;
+    };
+
+    let sid = mid.sid();
+    let shops = match gd.region.get_site_mut(sid).content {
+        SiteContent::AutoGenDungeon { ref mut shops, .. } => shops,
+        _ => {
+            return;
+        }
+    };
+    let id = shops.keys().max().map(|id| id + 1).unwrap_or(0);
+    let shop_gen = ShopGenData {
+        chara_id: id,
+        shop_kind: special_room.shop_kind.clone(),
+        selector: String::new(),
+        fence: false,
+    };
+    let mut shop = Shop::new(mid.floor());
+    shop.shop_kind = special_room.shop_kind.clone();
+    crate::game::shop::update_items_on_shop(&mut shop, &shop_gen, 0);
+    shops.insert(id, shop);
+
+    let ct = gobj::get_obj(idx);
+    let mut chara = crate::game::chara::gen::create_chara(idx, ct.gen_level, None, None);
+    chara.ai.initial_pos = p;
+    if !special_room.talk_script.is_empty() {
+        chara.trigger_talk = Some(special_room.talk_script.clone());
+    }
+    let cid = CharaId::OnSite { sid, id };
+    gd.add_chara(cid, chara);
+    gd.region.get_map_mut(mid).locate_chara(cid, p);
+}
+
+fn choose_floor_tile_in_room(map: &Map, room: RoomRect) -> Option<Vec2d> {
+    let tiles: Vec<Vec2d> = room
+        .iter()
+        .filter(|p| map.tile[*p].wall.is_empty() && map.tile[*p].special.is_none())
+        .collect();
+    tiles.choose(&mut rng::get_rng()).copied()
+}
+
+fn choose_empty_tile_in_room(map: &Map, room: RoomRect) -> Option<Vec2d> {
+    let tiles: Vec<Vec2d> = room.iter().filter(|p| map.is_empty_tile(*p)).collect();
+    tiles.choose(&mut rng::get_rng()).copied()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn room(x0: i32, y0: i32, x1: i32, y1: i32) -> RoomRect {
+        RoomRect {
+            top_left: Vec2d(x0, y0),
+            bottom_right: Vec2d(x1, y1),
+        }
+    }
+
+    #[test]
+    fn special_room_bounds() {
+        let mut map = Map::new(24, 16);
+        let rooms = [
+            room(1, 1, 5, 4),
+            room(8, 1, 12, 6),
+            room(15, 2, 20, 5),
+            room(2, 9, 9, 14),
+        ];
+        map.tile[Vec2d(10, 3)].special = SpecialTileKind::Stairs {
+            dest_floor: 1,
+            kind: StairsKind::DownStairs,
+        };
+        map.entrance.push(Vec2d(15, 2));
+
+        // Rooms with stairs or entrances are not chosen
+        let candidates = candidate_rooms(&map, &rooms);
+        assert_eq!(candidates, vec![rooms[0], rooms[3]]);
+
+        // Contents are placed inside the room, avoiding special tiles
+        let r = rooms[3];
+        map.tile[Vec2d(4, 10)].special = SpecialTileKind::SecretPassage;
+        for seed in 0..20 {
+            rng::reseed_with(seed);
+            let p = choose_floor_tile_in_room(&map, r).unwrap();
+            assert!(r.contains(p));
+            assert_ne!(p, Vec2d(4, 10));
+        }
+
+        // No tile is chosen in a fully occupied room
+        let r = room(20, 10, 20, 10);
+        map.tile[Vec2d(20, 10)].special = SpecialTileKind::SecretPassage;
+        assert_eq!(choose_floor_tile_in_room(&map, r), None);
+    }
+}
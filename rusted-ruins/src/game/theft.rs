@@ -0,0 +1,87 @@
+//! Stealing items owned by other factions
+
+use crate::game::extrait::*;
+use crate::game::faction::change_relation;
+use crate::game::InfoGetter;
+use common::gamedata::*;
+use common::gobj;
+use common::obj::SiteGenObject;
+use rules::RULES;
+
+/// The faction that owns items on the current map
+fn owner_faction(gd: &GameData) -> FactionId {
+    let mid = gd.get_current_mapid();
+    if mid.is_region_map() {
+        return FactionId::unknown();
+    }
+    gd.region
+        .get_site(mid.sid())
+        .id
+        .as_ref()
+        .and_then(|id| gobj::get_by_id_checked::<SiteGenObject>(id))
+        .map(|sg| sg.default_faction_id)
+        .unwrap_or_else(FactionId::unknown)
+}
+
+/// Steal an owned item. Returns true if a witness noticed it.
+pub fn steal_item(gd: &mut GameData, il: ItemLocation) -> bool {
+    let owner = owner_faction(gd);
+    let mut item = gd.remove_item_and_get(il, 1);
+    item.flags.remove(ItemFlags::OWNED);
+    item.attrs.push(ItemAttr::Stolen(owner));
+    game_log!("theft-steal"; chara=gd.chara.get(CharaId::Player), item=item);
+    gd.get_item_list_mut(ItemListLocation::PLAYER)
+        .append(item, 1);
+
+    gd.chara.get_mut(CharaId::Player).update();
+    let witness = find_witness(gd);
+
+    if let Some(witness) = witness {
+        game_log!("theft-witnessed"; chara=gd.chara.get(witness));
+        change_relation(gd, owner, RULES.theft.relvar_witnessed);
+        true
+    } else {
+        let player = gd.chara.get_mut(CharaId::Player);
+        let conceal = player.skill_level(SkillKind::Conceal);
+        player.add_skill_exp(SkillKind::Conceal, RULES.theft.exp, conceal);
+        false
+    }
+}
+
+/// Characters who can see the player try to notice a theft.
+/// The probability is calculated by their Detection skill and the player's Conceal skill.
+fn find_witness(gd: &GameData) -> Option<CharaId> {
+    let player_pos = gd.player_pos();
+    let conceal = gd
+        .chara
+        .get(CharaId::Player)
+        .skill_level(SkillKind::Conceal)
+        .max(1);
+
+    for cid in gd.get_charas_on_map() {
+        if cid == CharaId::Player || gd.player.party.contains(&cid) {
+            continue;
+        }
+        let chara = gd.chara.get(cid);
+        if chara
+            .status
+            .iter()
+            .any(|s| matches!(s, CharaStatus::Asleep { .. }))
+            || !gd.target_visible(cid, CharaId::Player)
+        {
+            continue;
+        }
+
+        let view_range = chara.attr.view_range.max(1);
+        let distance = gd.chara_pos(cid).unwrap().mdistance(player_pos);
+        let detection = chara.skill_level(SkillKind::Detection).max(1);
+        let distance_factor = 1.0 - (distance as f32 / (view_range + 1) as f32);
+        let p =
+            (detection as f32 / conceal as f32) * distance_factor * RULES.theft.detection_factor;
+
+        if p >= 1.0 || rng::gen_bool(p.max(0.0)) {
+            return Some(cid);
+        }
+    }
+    None
+}
@@ -42,6 +42,8 @@ pub fn advance_game_time_by_clock(game: &mut Game<'_>, advanced_clock: u32) {
     } else {
         RULES.params.minutes_per_turn_normal
     };
+    // Time passes faster while sleeping
+    let minutes_per_turn = minutes_per_turn * crate::game::rest::time_factor(&game.gd);
     const AVERAGE_CLOCK_PER_TURN: u32 = WAIT_TIME_NUMERATOR / 100;
     let advanced_secs =
         minutes_per_turn * 60.0 * advanced_clock as f32 / AVERAGE_CLOCK_PER_TURN as f32;
@@ -63,11 +65,15 @@ pub fn advance_game_time_by_secs(game: &mut Game<'_>, advanced_secs: u64) {
     if before.minute / 10 != now.minute / 10 || duration_s >= SECS_PER_MIN * 10 {
         info!("time update process (10 minutes)");
         crate::game::item::time::update_item_time(&mut game.gd);
+        crate::game::quest::check_quests(&mut game.gd);
     }
 
     // day
     if before.day != now.day || duration_s >= SECS_PER_DAY {
         info!("time update process (day)");
+        crate::game::town::update_towns_daily(&mut game.gd);
+        crate::game::party::pay_wages(&mut game.gd);
+        crate::game::base::update_bases_daily(&mut game.gd);
     }
 }
 
@@ -1,7 +1,9 @@
+use crate::game::extrait::*;
 use common::gamedata::*;
 use common::gobj;
 use common::obj::SiteGenObject;
 use geom::Vec2d;
+use rules::RULES;
 
 /// Create town from SiteGenObect and add it to region map
 pub fn add_town(gd: &mut GameData, rid: RegionId, pos: Vec2d, town_id: &str) {
@@ -23,14 +25,12 @@ pub fn add_town(gd: &mut GameData, rid: RegionId, pos: Vec2d, town_id: &str) {
         };
 
         for shop_gen_data in &sg.shops {
-            let shop = Shop {
-                items: ItemList::default(),
-                level: 1,
-            };
+            let mut shop = Shop::new(1);
+            shop.fence = shop_gen_data.fence;
             town.add_shop(shop, shop_gen_data.chara_id);
         }
     }
-    update_shops(gd, sid, sg);
+    update_town(gd, sid);
 }
 
 /// Update shop states
@@ -45,9 +45,99 @@ pub fn update_shops(gd: &mut GameData, sid: SiteId, sg: &SiteGenObject) {
             return;
         }
     };
+    let economy_level = town.economy_level;
 
-    for (i, shop) in town.iter_shops_mut().enumerate() {
-        let shop_gen = &sg.shops[i];
-        update_items_on_shop(shop, shop_gen);
+    for shop_gen in &sg.shops {
+        if let Some(shop) = town.get_shop_mut(shop_gen.chara_id) {
+            update_items_on_shop(shop, shop_gen, economy_level);
+        }
+    }
+}
+
+/// Daily update for all towns
+pub fn update_towns_daily(gd: &mut GameData) {
+    let mut sids = Vec::new();
+    gd.region.visit_all_sites(|sid, _| {
+        if sid.kind == SiteKind::Town {
+            sids.push(sid);
+        }
+    });
+
+    for sid in sids {
+        grow_economy(gd, sid, RULES.town.economy_growth_per_day);
+        update_town(gd, sid);
+    }
+}
+
+/// Restock shops and refresh the quest board if their intervals have passed
+pub fn update_town(gd: &mut GameData, sid: SiteId) {
+    let now = gd.time.current_time();
+    let site = gd.region.get_site(sid);
+    let town = if let Some(town) = site.town() {
+        town
+    } else {
+        return;
+    };
+    let restock = interval_passed(town.last_restock, now, RULES.town.restock_days);
+    let update_quest = interval_passed(town.last_quest_update, now, RULES.town.quest_update_days);
+
+    if restock {
+        if let Some(sg) = site
+            .id
+            .as_ref()
+            .and_then(|id| gobj::get_by_id_checked::<SiteGenObject>(id))
+        {
+            update_shops(gd, sid, sg);
+        }
+        let town = gd.region.get_site_mut(sid).town_mut().unwrap();
+        town.last_restock = Some(now);
+    }
+
+    if update_quest {
+        crate::game::quest::update_town_quest(gd, sid);
+        let town = gd.region.get_site_mut(sid).town_mut().unwrap();
+        town.last_quest_update = Some(now);
+    }
+}
+
+fn interval_passed(last: Option<Time>, now: Time, days: u32) -> bool {
+    if let Some(last) = last {
+        now.duration_from(last) >= Duration::from_days(days.into())
+    } else {
+        true
+    }
+}
+
+/// Add growth points to the economy of the town
+pub fn grow_economy(gd: &mut GameData, sid: SiteId, exp: u32) {
+    let town = if let Some(town) = gd.region.get_site_mut(sid).town_mut() {
+        town
+    } else {
+        return;
+    };
+
+    town.economy_exp += exp;
+    loop {
+        if town.economy_level >= RULES.town.economy_max_level {
+            town.economy_exp = 0;
+            break;
+        }
+        let required = RULES.town.economy_exp_per_level * (town.economy_level + 1);
+        if town.economy_exp < required {
+            break;
+        }
+        town.economy_exp -= required;
+        town.economy_level += 1;
+        info!("economy level of {:?} grew to {}", sid, town.economy_level);
+    }
+}
+
+/// Money spent at shops in the current town helps its economy
+pub fn add_trade(gd: &mut GameData, money: i64) {
+    let mid = gd.get_current_mapid();
+    if mid.is_region_map() || mid.sid().kind != SiteKind::Town {
+        return;
     }
+    let exp = (money as f32 * RULES.town.economy_trade_factor) as u32;
+    grow_economy(gd, mid.sid(), exp);
 }
@@ -0,0 +1,309 @@
+//! Traps and hidden features on maps
+
+use super::damage::*;
+use super::extrait::*;
+use super::{Game, InfoGetter};
+use common::gamedata::*;
+use common::gobj;
+use common::objholder::SpecialTileIdx;
+use geom::*;
+use rng::SliceRandom;
+use rules::RULES;
+
+/// Called after a character moved onto the tile
+pub fn step_on(game: &mut Game<'_>, cid: CharaId, pos: Vec2d) {
+    let special = game.gd.get_current_map().tile[pos].special;
+    let (kind, hidden) = match special {
+        SpecialTileKind::Trap { kind, hidden, .. } => (kind, hidden),
+        _ => {
+            return;
+        }
+    };
+
+    let chara = game.gd.chara.get(cid);
+    // Characters know the positions of their own traps
+    if special.is_trap_of(chara.faction) {
+        return;
+    }
+    if !hidden && !rng::gen_bool(RULES.trap.trigger_prob_revealed) {
+        game_log_i!("trap-avoided"; chara=chara, trap=kind);
+        return;
+    }
+    trigger(game, cid, pos, kind);
+}
+
+/// Trigger the trap on the tile. The effect is applied to the given character.
+pub fn trigger(game: &mut Game<'_>, cid: CharaId, pos: Vec2d, kind: TrapKind) {
+    reveal(game, pos);
+    let floor_level = game.gd.get_current_mapid().floor();
+    let chara = game.gd.chara.get_mut(cid);
+    game_log_i!("trap-triggered"; chara=chara, trap=kind);
+
+    match kind {
+        TrapKind::Damage => {
+            let damage = RULES.trap.damage_base + RULES.trap.damage_per_floor * floor_level as f32;
+            let damage = rng::gen_range(damage / 2.0..=damage) as i32;
+            do_damage(game, cid, damage.max(1), CharaDamageKind::Trap, None);
+        }
+        TrapKind::Poison => {
+            chara.add_status(CharaStatus::Poisoned);
+            game_log_i!("poisoned"; chara=chara);
+        }
+        TrapKind::Teleport => {
+            let map = game.gd.get_current_map_mut();
+            if let Some(p) = super::map::choose_empty_tile(map) {
+                map.locate_chara(cid, p);
+                game_log_i!("trap-teleported"; chara=game.gd.chara.get(cid));
+            }
+        }
+        TrapKind::Alarm => {
+            game_log_i!("trap-alarm-ringing");
+            for other in game.gd.get_charas_on_map() {
+                if other == cid || game.gd.chara_relation(other, cid) != Relationship::Hostile {
+                    continue;
+                }
+                game.gd.chara.get_mut(other).ai.state = AiState::Combat { target: cid };
+            }
+        }
+        TrapKind::Pit => {
+            if cid == CharaId::Player && fall_to_lower_floor(game) {
+                return;
+            }
+            let damage = RULES.trap.pit_damage as i32;
+            do_damage(game, cid, damage.max(1), CharaDamageKind::Trap, None);
+        }
+    }
+}
+
+/// Move the player to a random tile on the lower floor.
+/// Returns false if the current map has no lower floor.
+fn fall_to_lower_floor(game: &mut Game<'_>) -> bool {
+    let mid = game.gd.get_current_mapid();
+    if mid.is_region_map() || mid.sid().kind != SiteKind::AutoGenDungeon {
+        return false;
+    }
+    let next_floor = mid.floor() + 1;
+    if next_floor >= game.gd.region.get_site(mid.sid()).max_floor() {
+        return false;
+    }
+
+    game_log!("trap-fall"; chara=game.gd.chara.get(CharaId::Player));
+    super::map::switch_map(game, Destination::Floor(next_floor));
+    let map = game.gd.get_current_map_mut();
+    if let Some(p) = super::map::choose_empty_tile(map) {
+        map.locate_chara(CharaId::Player, p);
+    }
+    let damage = RULES.trap.pit_damage as i32;
+    do_damage(
+        game,
+        CharaId::Player,
+        damage.max(1),
+        CharaDamageKind::Trap,
+        None,
+    );
+    super::view::update_view_map(game);
+    true
+}
+
+/// Make the hidden feature on the tile visible.
+/// Secret passages become passable.
+fn reveal(game: &mut Game<'_>, pos: Vec2d) {
+    let map = game.gd.get_current_map_mut();
+    match map.tile[pos].special {
+        SpecialTileKind::Trap { ref mut hidden, .. } => {
+            *hidden = false;
+        }
+        SpecialTileKind::SecretPassage => {
+            map.tile[pos].special = SpecialTileKind::None;
+            map.erase_wall(pos);
+        }
+        _ => (),
+    }
+}
+
+/// Search hidden features around the player by a Detection skill check.
+/// Returns the number of found features.
+pub fn search(game: &mut Game<'_>, range: i32, factor: f32) -> u32 {
+    let center = game.gd.player_pos();
+    let floor_level = game.gd.get_current_mapid().floor();
+    let detection = game
+        .gd
+        .chara
+        .get(CharaId::Player)
+        .skill_level(SkillKind::Detection)
+        .max(1) as f32;
+    let p = (factor * detection / (detection + difficulty(floor_level))).clamp(0.0, 1.0);
+    let mut n_found = 0;
+
+    for pos in RectIter::new(center - (range, range), center + (range, range)) {
+        let map = game.gd.get_current_map();
+        if !map.is_inside(pos) || !map.tile[pos].special.is_hidden() || !rng::gen_bool(p) {
+            continue;
+        }
+        let special = map.tile[pos].special;
+        reveal(game, pos);
+        match special {
+            SpecialTileKind::Trap { kind, .. } => {
+                game_log!("search-found-trap"; trap=kind);
+            }
+            _ => {
+                game_log!("search-found-secret-passage");
+            }
+        }
+        n_found += 1;
+    }
+
+    if n_found > 0 {
+        let player = game.gd.chara.get_mut(CharaId::Player);
+        player.add_skill_exp(
+            SkillKind::Detection,
+            RULES.trap.exp_detect * n_found,
+            floor_level,
+        );
+        super::view::update_view_map(game);
+    }
+    n_found
+}
+
+/// Try to disarm the revealed trap on the tile by Lockpicking skill.
+/// If failed, the trap may be triggered.
+pub fn disarm(game: &mut Game<'_>, pos: Vec2d) -> bool {
+    let kind = match game.gd.get_current_map().tile[pos].special {
+        SpecialTileKind::Trap {
+            kind,
+            hidden: false,
+            ..
+        } => kind,
+        _ => {
+            game_log!("trap-disarm-no-trap");
+            return false;
+        }
+    };
+    let floor_level = game.gd.get_current_mapid().floor();
+    let player = game.gd.chara.get_mut(CharaId::Player);
+    let skill = player.skill_level(SkillKind::Lockpicking).max(1) as f32;
+    let p = RULES.trap.disarm_factor * skill / (skill + difficulty(floor_level));
+
+    if p >= 1.0 || rng::gen_bool(p.max(0.0)) {
+        game_log!("trap-disarmed"; chara=player, trap=kind);
+        player.add_skill_exp(SkillKind::Lockpicking, RULES.trap.exp_disarm, floor_level);
+        game.gd.get_current_map_mut().tile[pos].special = SpecialTileKind::None;
+    } else if rng::gen_bool(RULES.trap.disarm_fail_trigger_prob) {
+        trigger(game, CharaId::Player, pos, kind);
+    } else {
+        game_log!("trap-disarm-failed"; chara=player, trap=kind);
+    }
+    true
+}
+
+fn difficulty(floor_level: u32) -> f32 {
+    RULES.trap.difficulty_base + RULES.trap.difficulty_per_floor * floor_level as f32
+}
+
+/// Place hidden traps and secret passages on a generated dungeon floor
+pub fn gen_traps(map: &mut Map, faction: FactionId) {
+    let positions: Vec<Vec2d> = map.tile.iter_idx().collect();
+    place_traps(map, positions, RULES.trap.gen_probability, faction);
+
+    // Secret passages are walls between two floor tiles
+    let mut candidates: Vec<Vec2d> = map
+        .tile
+        .iter_idx()
+        .filter(|p| {
+            let is_floor = |p: Vec2d| map.is_inside(p) && map.tile[p].wall.is_empty();
+            !map.tile[*p].wall.is_empty()
+                && map.tile[*p].special.is_none()
+                && ((is_floor(*p + (-1, 0)) && is_floor(*p + (1, 0)))
+                    || (is_floor(*p + (0, -1)) && is_floor(*p + (0, 1))))
+        })
+        .collect();
+    candidates.shuffle(&mut rng::get_rng());
+    let [min, max] = RULES.trap.n_secret_passages;
+    let n = rng::gen_range(min..=max.max(min)) as usize;
+    for p in candidates.into_iter().take(n) {
+        map.tile[p].special = SpecialTileKind::SecretPassage;
+    }
+}
+
+/// Place hidden traps on empty floor tiles by given probability
+pub fn place_traps<I: IntoIterator<Item = Vec2d>>(
+    map: &mut Map,
+    positions: I,
+    prob: f32,
+    faction: FactionId,
+) {
+    // Revealed traps cannot be drawn without the object
+    if RULES.trap.kinds.is_empty()
+        || gobj::id_to_idx_checked::<SpecialTileIdx>(TRAP_OBJ_ID).is_none()
+    {
+        return;
+    }
+    let positions = trap_positions(positions, prob, |p| {
+        map.is_empty_tile(p) && !map.entrance.contains(&p)
+    });
+    for p in positions {
+        let kind = RULES
+            .trap
+            .kinds
+            .choose_weighted(&mut rng::get_rng(), |item| item.1)
+            .unwrap()
+            .0;
+        map.tile[p].special = SpecialTileKind::Trap {
+            kind,
+            faction,
+            hidden: true,
+        };
+    }
+}
+
+/// Choose positions where traps can be placed by given probability
+fn trap_positions<I: IntoIterator<Item = Vec2d>, F: Fn(Vec2d) -> bool>(
+    positions: I,
+    prob: f32,
+    can_place: F,
+) -> Vec<Vec2d> {
+    positions
+        .into_iter()
+        .filter(|p| can_place(*p) && rng::gen_bool(prob))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn trap_positions_test() {
+        let positions: Vec<Vec2d> = RectIter::new((0, 0), (99, 99)).collect();
+        let can_place = |p: Vec2d| p.0 % 2 == 0;
+        rng::reseed_with(0);
+
+        assert!(trap_positions(positions.clone(), 0.0, can_place).is_empty());
+
+        let all = trap_positions(positions.clone(), 1.0, can_place);
+        assert_eq!(all.len(), 5000);
+        assert!(all.iter().all(|p| can_place(*p)));
+
+        // About 10% of placeable positions get traps
+        let some = trap_positions(positions.clone(), 0.1, can_place);
+        assert!(some.iter().all(|p| can_place(*p)));
+        assert!(400 < some.len() && some.len() < 600, "{}", some.len());
+
+        assert!(trap_positions(positions, 1.0, |_| false).is_empty());
+    }
+
+    #[test]
+    fn own_faction_trap() {
+        let faction = FactionId::new("bandit").unwrap();
+        let trap = SpecialTileKind::Trap {
+            kind: TrapKind::Damage,
+            faction,
+            hidden: true,
+        };
+        // Members of the faction avoid the trap, others step on it
+        assert!(trap.is_trap_of(faction));
+        assert!(!trap.is_trap_of(FactionId::player()));
+        assert!(!SpecialTileKind::SecretPassage.is_trap_of(faction));
+        assert!(!SpecialTileKind::None.is_trap_of(faction));
+    }
+}
@@ -105,6 +105,7 @@ fn remove_dying_charas(game: &mut Game<'_>) -> bool {
             if game.gd.player.party.contains(&cid) {
                 game.gd.player.party.remove(&cid);
                 game.gd.player.party_dead.insert(cid);
+                crate::game::quest::escort_dead(&mut game.gd, cid);
             }
         } else {
             match chara.ai.state {
@@ -68,7 +68,7 @@ pub fn update_view_map(game: &mut Game<'_>) {
 
         for p in LineIter::new(player_pos, pos).skip(1) {
             view_map.visible[p] = true;
-            if !map.tile[p].wall.is_empty() {
+            if blocks_view(&map.tile[p]) {
                 break;
             }
         }
@@ -77,10 +77,15 @@ pub fn update_view_map(game: &mut Game<'_>) {
 
 pub fn calc_visual_distance(map: &Map, orig: Vec2d, dist: Vec2d) -> Option<i32> {
     for pos in LineIter::new(orig, dist) {
-        if !map.tile[pos].wall.is_empty() {
+        if blocks_view(&map.tile[pos]) {
             return None;
         }
     }
 
     Some(dist.mdistance(orig))
 }
+
+/// Walls and closed doors block vision
+pub fn blocks_view(tile: &TileInfo) -> bool {
+    !tile.wall.is_empty() || matches!(tile.door, Some(door) if door.state.is_closed())
+}
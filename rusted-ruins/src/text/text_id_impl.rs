@@ -2,20 +2,16 @@ use super::ToTextId;
 
 use common::gamedata::*;
 
-impl ToTextId for CharaStatus {
+impl ToTextId for Element {
     fn to_textid(&self) -> &'static str {
         match *self {
-            CharaStatus::Hungry => "chara_status-hungry",
-            CharaStatus::Weak => "chara_status-weak",
-            CharaStatus::Starving => "chara_status-starving",
-            CharaStatus::Burdened => "chara_status-burdened",
-            CharaStatus::Stressed => "chara_status-stressed",
-            CharaStatus::Strained => "chara_status-strained",
-            CharaStatus::Overloaded => "chara_status-overloaded",
-            CharaStatus::Scanned => "chara_status-scanned",
-            CharaStatus::Asleep { .. } => "chara_status-asleep",
-            CharaStatus::Poisoned => "chara_status-poisoned",
-            CharaStatus::Work { .. } => "chara_status-work",
+            Element::None => "element-none",
+            Element::Physical => "element-physical",
+            Element::Fire => "element-fire",
+            Element::Cold => "element-cold",
+            Element::Shock => "element-shock",
+            Element::Poison => "element-poison",
+            Element::Spirit => "element-spirit",
         }
     }
 }
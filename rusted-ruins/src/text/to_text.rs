@@ -45,7 +45,7 @@ impl ToText for Site {
         }
 
         match self.content {
-            SiteContent::AutoGenDungeon { dungeon_kind } => {
+            SiteContent::AutoGenDungeon { dungeon_kind, .. } => {
                 misc_txt(&format!("dungeon_kind-{}", dungeon_kind.as_str())).into()
             }
             SiteContent::Town { ref town } => text::obj_txt(town.id()).into(),
@@ -80,6 +80,10 @@ impl ToText for Item {
             }
         }
 
+        if self.stolen().is_some() {
+            text.push_str(&format!(" ({})", ui_txt("item-stolen")));
+        }
+
         if let Some((material_name, _)) = self.material() {
             text.push_str(&format!(" ({})", super::prefix::material(material_name)))
         }
@@ -131,6 +135,12 @@ impl ToText for CharaTemplateIdx {
     }
 }
 
+impl ToText for ItemIdx {
+    fn to_text(&self) -> Cow<'_, str> {
+        obj_txt(gobj::idx_to_id(*self)).into()
+    }
+}
+
 impl ToText for Chara {
     fn to_text(&self) -> Cow<'_, str> {
         use crate::game::chara::CharaExt;
@@ -151,6 +161,46 @@ impl ToText for CharaTrait {
     }
 }
 
+impl ToText for CharaStatus {
+    fn to_text(&self) -> Cow<'_, str> {
+        let id = match self {
+            CharaStatus::Hungry => "chara_status-hungry",
+            CharaStatus::Weak => "chara_status-weak",
+            CharaStatus::Starving => "chara_status-starving",
+            CharaStatus::Burdened => "chara_status-burdened",
+            CharaStatus::Stressed => "chara_status-stressed",
+            CharaStatus::Strained => "chara_status-strained",
+            CharaStatus::Overloaded => "chara_status-overloaded",
+            CharaStatus::Scanned => "chara_status-scanned",
+            CharaStatus::Asleep { .. } => "chara_status-asleep",
+            CharaStatus::Poisoned => "chara_status-poisoned",
+            CharaStatus::Work { .. } => "chara_status-work",
+            CharaStatus::Defined { id, stack, .. } => {
+                let s = misc_txt(&format!("chara_status-{}", id));
+                return if *stack > 1 {
+                    format!("{} x{}", s, stack).into()
+                } else {
+                    s.into()
+                };
+            }
+        };
+        misc_txt(id).into()
+    }
+}
+
+impl ToText for TrapKind {
+    fn to_text(&self) -> Cow<'_, str> {
+        let id = match self {
+            TrapKind::Damage => "trap-damage",
+            TrapKind::Poison => "trap-poison",
+            TrapKind::Teleport => "trap-teleport",
+            TrapKind::Alarm => "trap-alarm",
+            TrapKind::Pit => "trap-pit",
+        };
+        misc_txt(id).into()
+    }
+}
+
 impl ToText for CharaModifier {
     fn to_text(&self) -> Cow<'_, str> {
         match self {
@@ -161,6 +211,10 @@ impl ToText for CharaModifier {
             CharaModifier::Wil(value) => format!("WIL {:+}", value),
             CharaModifier::Cha(value) => format!("CHA {:+}", value),
             CharaModifier::Spd(value) => format!("SPD {:+}", value),
+            CharaModifier::ViewRange(value) => format!("VIEW {:+}", value),
+            CharaModifier::ElementProtection(element, value) => {
+                format!("{} {:+}%", element.to_text(), value)
+            }
         }
         .into()
     }
@@ -196,6 +250,7 @@ impl ToText for Command {
             OpenExitWin => "command-open_exit_win",
             OpenGameInfoWin => "command-open_game_info_win",
             OpenHelpWin => "command-open_help_win",
+            OpenQuestWin => "command-open_quest_win",
             OpenStatusWin => "command-open_status_win",
             OpenItemMenu => "command-open_item_menu",
             PickUpItem => "command-pick_up_item",
@@ -218,13 +273,18 @@ impl ToText for Command {
 
 impl ToText for Quest {
     fn to_text(&self) -> Cow<'_, str> {
-        match self {
-            Quest::SlayMonsters { idx, .. } => {
-                let mut table = fluent::FluentArgs::new();
-                table.add("monster", fluent::FluentValue::String(idx.to_text()));
-                crate::text::misc_txt_with_args("quest-slay_monsters", Some(&table)).into()
+        match self.kind {
+            QuestKind::SlayMonsters { idx, .. } => {
+                misc_txt_format!("quest-slay_monsters"; monster=idx)
             }
+            QuestKind::Fetch { idx, .. } => misc_txt_format!("quest-fetch"; item=idx),
+            QuestKind::Deliver { idx, .. } => misc_txt_format!("quest-deliver"; item=idx),
+            QuestKind::Escort { idx, .. } => misc_txt_format!("quest-escort"; chara=idx),
+            QuestKind::Explore { .. } => misc_txt("quest-explore"),
+            QuestKind::Retrieve { idx, .. } => misc_txt_format!("quest-retrieve"; item=idx),
+            QuestKind::Craft { idx, .. } => misc_txt_format!("quest-craft"; item=idx),
         }
+        .into()
     }
 }
 
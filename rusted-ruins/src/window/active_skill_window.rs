@@ -1,5 +1,6 @@
 use super::commonuse::*;
 use super::widget::*;
+use crate::game::extrait::*;
 use crate::text::ToText;
 use common::gamedata::*;
 use rules::RULES;
@@ -8,7 +9,7 @@ use rules::RULES;
 pub struct ActiveSkillWindow {
     rect: Rect,
     cid: CharaId,
-    list: ListWidget<(TextCache, TextCache)>,
+    list: ListWidget<(TextCache, TextCache, TextCache)>,
     active_skills: Vec<ActiveSkillId>,
     escape_click: bool,
 }
@@ -46,13 +47,25 @@ impl ActiveSkillWindow {
                 cost.push_str(&format!("MP {} ", active_skill.cost_mp));
             }
 
+            let cooldown_left = chara.active_skill_cooldown(active_skill_id);
+            let cooldown = if cooldown_left > 0 {
+                format!("CD {}/{}", cooldown_left, active_skill.cooldown)
+            } else if active_skill.cooldown > 0 {
+                format!("CD {}", active_skill.cooldown)
+            } else {
+                "".into()
+            };
+
+            let color = if chara.active_skill_available(active_skill_id, active_skill) {
+                UI_CFG.color.normal_font
+            } else {
+                UI_CFG.color.disabled_font
+            };
+
             items.push((
-                TextCache::new(
-                    active_skill_id.to_text(),
-                    FontKind::M,
-                    UI_CFG.color.normal_font,
-                ),
-                TextCache::new(cost, FontKind::M, UI_CFG.color.normal_font),
+                TextCache::new(active_skill_id.to_text(), FontKind::M, color),
+                TextCache::new(cost, FontKind::M, color),
+                TextCache::new(cooldown, FontKind::M, color),
             ));
         }
 
@@ -5,6 +5,7 @@ use super::msg_dialog;
 use super::read_window;
 use super::status_window;
 use super::talk_window;
+use super::text_input_dialog;
 use super::DialogWindow;
 use crate::game::{DialogOpenRequest, Game};
 use common::gamedata::CharaId;
@@ -28,17 +29,37 @@ pub fn create_dialog_from_request(
             Box::new(item_info_window::ItemInfoWindow::new(il, game))
         }
         DialogOpenRequest::CharaStatus { cid } => {
-            Box::new(status_window::create_status_window_group(game, cid, false))
+            // Party members' equipment can be changed by the player
+            let changeable = game.gd.player.party.contains(&cid);
+            Box::new(status_window::create_status_window_group(
+                game, cid, changeable,
+            ))
         }
         DialogOpenRequest::Read { title } => Box::new(read_window::ReadWindow::new(&title)),
         DialogOpenRequest::ShopBuy { cid } => {
             Box::new(ItemWindow::new(ItemWindowMode::ShopBuy { cid }, game))
         }
-        DialogOpenRequest::ShopSell => Box::new(ItemWindow::new(ItemWindowMode::ShopSell, game)),
+        DialogOpenRequest::ShopSell { cid } => {
+            Box::new(ItemWindow::new(ItemWindowMode::ShopSell { cid }, game))
+        }
+        DialogOpenRequest::PartyMember { cid } => super::party_menu::create_menu(game, cid),
         DialogOpenRequest::RegisterAsShortcut { shortcut } => {
             Box::new(super::register_shortcut_dialog::RegisterShortcutDialog::new(shortcut))
         }
         DialogOpenRequest::PickUpItem => Box::new(ItemWindow::new(ItemWindowMode::PickUp, game)),
+        DialogOpenRequest::Rest => super::rest_menu::create_menu(),
+        DialogOpenRequest::WaitTurns => {
+            let mut win = text_input_dialog::TextInputDialog::new();
+            win.set_callback(|pa, s| {
+                if let Ok(turns) = s.trim().parse::<u16>() {
+                    pa.wait_turns(turns);
+                } else {
+                    game_log!("rest-invalid-turns");
+                }
+            });
+            Box::new(win)
+        }
+        DialogOpenRequest::StealItem => Box::new(ItemWindow::new(ItemWindowMode::Steal, game)),
         DialogOpenRequest::Quest => Box::new(super::quest_window::QuestWindow::new(game)),
         DialogOpenRequest::GameOver => Box::new(super::exit_window::GameOverWindow::new()),
     })
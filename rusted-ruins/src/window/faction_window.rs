@@ -2,13 +2,18 @@ use super::commonuse::*;
 use super::widget::*;
 use crate::config::UI_CFG;
 use crate::context::textrenderer::FontKind;
+use crate::game::faction::rank_text;
 use crate::text::{ui_txt, ToText};
 use common::gamedata::*;
+use rules::faction::FactionStance;
+use rules::RULES;
 
 /// Faction viewer
 pub struct FactionWindow {
     rect: Rect,
-    list: ListWidget<(TextCache, LabelWidget)>,
+    list: ListWidget<(TextCache, LabelWidget, LabelWidget)>,
+    factions: Vec<FactionId>,
+    stance_label: LabelWidget,
     escape_click: bool,
 }
 
@@ -17,7 +22,8 @@ impl FactionWindow {
         let rect: Rect = UI_CFG.info_window.rect.into();
         let cfg = &UI_CFG.faction_window;
 
-        let column1_width = rect.width() - cfg.column_pos[1] as u32 - UI_CFG.vscroll_widget.width;
+        let column1_width = cfg.column_pos[2] as u32 - cfg.column_pos[1] as u32;
+        let column2_width = rect.width() - cfg.column_pos[2] as u32 - UI_CFG.vscroll_widget.width;
         let header = (
             TextCache::new(
                 ui_txt("list_header-faction"),
@@ -26,6 +32,11 @@ impl FactionWindow {
             ),
             LabelWidget::new(
                 Rect::new(0, 0, column1_width, UI_CFG.list_widget.h_row_default),
+                ui_txt("list_header-reputation"),
+                FontKind::M,
+            ),
+            LabelWidget::new(
+                Rect::new(0, 0, column2_width, UI_CFG.list_widget.h_row_default),
                 &ui_txt("list_header-relation"),
                 FontKind::M,
             ),
@@ -38,33 +49,92 @@ impl FactionWindow {
             header,
         );
 
-        let items: Vec<_> = gd
+        let mut factions: Vec<(FactionId, FactionRelation)> = gd
             .faction
             .iter()
-            .filter_map(|(id, relation)| {
-                if id.as_str().starts_with('!') {
-                    return None;
-                }
+            .filter(|(id, _)| !id.as_str().starts_with('!'))
+            .map(|(id, relation)| (*id, *relation))
+            .collect();
+        factions.sort();
 
+        let items: Vec<_> = factions
+            .iter()
+            .map(|(id, relation)| {
                 let faction = TextCache::new(id.to_text(), FontKind::M, UI_CFG.color.normal_font);
+                let rank = LabelWidget::new(
+                    Rect::new(0, 0, column1_width, UI_CFG.list_widget.h_row_default),
+                    rank_text(*relation),
+                    FontKind::M,
+                );
                 let relation = format!("{}", Into::<i16>::into(*relation));
                 let relation = LabelWidget::new(
-                    Rect::new(0, 0, column1_width, UI_CFG.list_widget.h_row_default),
+                    Rect::new(0, 0, column2_width, UI_CFG.list_widget.h_row_default),
                     &relation,
                     FontKind::M,
                 )
                 .right();
-                Some((faction, relation))
+                (faction, rank, relation)
             })
             .collect();
 
         list.set_items(items);
 
-        FactionWindow {
+        let stance_label = LabelWidget::wrapped(
+            (
+                0i32,
+                (cfg.n_row as i32 + 1) * UI_CFG.list_widget.h_row_default as i32,
+                rect.width(),
+                0,
+            ),
+            "",
+            FontKind::M,
+            rect.width(),
+        );
+
+        let mut faction_window = FactionWindow {
             rect,
             list,
+            factions: factions.into_iter().map(|(id, _)| id).collect(),
+            stance_label,
             escape_click: false,
+        };
+        faction_window.update_stance_label();
+        faction_window
+    }
+
+    /// Show allies and enemies of the selected faction
+    fn update_stance_label(&mut self) {
+        let faction =
+            if let Some(faction) = self.factions.get(self.list.get_current_choice() as usize) {
+                *faction
+            } else {
+                self.stance_label.set_text("");
+                return;
+            };
+
+        let stances = RULES.faction.stances(faction);
+        let mut text = String::new();
+        for (stance, text_id) in &[
+            (FactionStance::Ally, "label_text-faction-ally"),
+            (FactionStance::Rival, "label_text-faction-rival"),
+            (FactionStance::War, "label_text-faction-war"),
+        ] {
+            let factions: Vec<String> = stances
+                .iter()
+                .filter(|(_, s)| s == stance)
+                .map(|(other, _)| other.to_text().into_owned())
+                .collect();
+            if factions.is_empty() {
+                continue;
+            }
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            text.push_str(&ui_txt(text_id));
+            text.push_str(": ");
+            text.push_str(&factions.join(", "));
         }
+        self.stance_label.set_text(text);
     }
 }
 
@@ -77,6 +147,7 @@ impl Window for FactionWindow {
     ) {
         draw_window_border(context, self.rect);
         self.list.draw(context);
+        self.stance_label.draw(context);
     }
 }
 
@@ -89,7 +160,15 @@ impl DialogWindow for FactionWindow {
         check_escape_click!(self, command);
         let command = command.relative_to(self.rect);
 
-        self.list.process_command(&command);
+        if let Some(response) = self.list.process_command(&command) {
+            match response {
+                ListWidgetResponse::SelectionChanged(_) | ListWidgetResponse::Scrolled => {
+                    self.update_stance_label();
+                }
+                _ => (),
+            }
+            return DialogResult::Continue;
+        }
 
         match command {
             Command::Cancel => DialogResult::Close,
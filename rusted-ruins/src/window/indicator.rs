@@ -7,6 +7,7 @@ use crate::text::ToText;
 use common::gamedata::*;
 use common::gobj;
 use common::obj::UiImgObject;
+use common::objholder::UiImgIdx;
 use rules::RULES;
 
 #[derive(Clone, Copy, Debug)]
@@ -221,6 +222,7 @@ impl Window for TimeInfo {
 
 pub struct StatusInfo {
     labels: Vec<LabelWidget>,
+    icons: Vec<ImageWidget>,
     status: Vec<CharaStatus>,
 }
 
@@ -228,6 +230,7 @@ impl StatusInfo {
     pub fn new() -> StatusInfo {
         StatusInfo {
             labels: Vec::new(),
+            icons: Vec::new(),
             status: Vec::new(),
         }
     }
@@ -240,13 +243,30 @@ impl StatusInfo {
             self.status.clone_from(&player_chara.status);
 
             self.labels.clear();
+            self.icons.clear();
             for (i, status) in self.status.iter().enumerate() {
+                let y = rect.y - rect.h * i as i32;
                 let label = LabelWidget::bordered(
-                    Rect::new(rect.x, rect.y - rect.h * i as i32, 1, 1),
-                    &crate::text::to_txt(status),
+                    Rect::new(rect.x, y, 1, 1),
+                    status.to_text(),
                     FontKind::T,
                 );
                 self.labels.push(label);
+
+                // Icon for status effects defined in rules
+                if let CharaStatus::Defined { id, .. } = status {
+                    let icon = RULES
+                        .status_effects
+                        .get(id)
+                        .map(|status_effect| status_effect.icon.as_str())
+                        .filter(|icon| !icon.is_empty())
+                        .and_then(gobj::id_to_idx_checked::<UiImgIdx>);
+                    if let Some(idx) = icon {
+                        let icon_rect = Rect::new(rect.x - rect.h, y, rect.h as u32, rect.h as u32);
+                        self.icons
+                            .push(ImageWidget::new(icon_rect, ImageIdx::UiImg(idx)));
+                    }
+                }
             }
         }
     }
@@ -265,5 +285,8 @@ impl Window for StatusInfo {
         for label in self.labels.iter_mut() {
             label.draw(context);
         }
+        for icon in self.icons.iter_mut() {
+            icon.draw(context);
+        }
     }
 }
@@ -6,6 +6,7 @@ use crate::config::UI_CFG;
 use crate::draw::border::draw_window_border;
 use crate::game::extrait::*;
 use crate::game::item::filter::*;
+use crate::game::shop::{buying_price, selling_price};
 use crate::game::{DialogOpenRequest, Game, InfoGetter};
 use crate::text::ToText;
 use common::gamedata::*;
@@ -16,6 +17,7 @@ pub type ActionCallback = dyn FnMut(&mut DoPlayerAction<'_, '_>, ItemLocation) -
 pub enum ItemWindowMode {
     List,
     PickUp,
+    Steal,
     Drop,
     Throw,
     Drink,
@@ -32,7 +34,9 @@ pub enum ItemWindowMode {
         ill: ItemListLocation,
         id: UniqueId,
     },
-    ShopSell,
+    ShopSell {
+        cid: CharaId,
+    },
     ShopBuy {
         cid: CharaId,
     },
@@ -255,8 +259,15 @@ impl ItemWindow {
             DialogResult::Close
         };
 
+        // Party members are equipped from the player's inventory
+        let ill = if pa.gd().player.party.contains(&cid) {
+            ItemListLocation::PLAYER
+        } else {
+            ItemListLocation::Chara { cid }
+        };
+
         ItemWindow::new_select(
-            ItemListLocation::Chara { cid },
+            ill,
             ItemFilter::new().equip_slot_kind(slot.0),
             Box::new(equip_selected_item),
             pa,
@@ -275,21 +286,26 @@ impl ItemWindow {
         match &self.mode {
             ItemWindowMode::List => {
                 let filtered_list = gd.get_filtered_item_list(ill_player, ItemFilter::all());
-                self.update_list(filtered_list);
+                self.update_list(gd, filtered_list);
             }
             ItemWindowMode::PickUp => {
                 let filtered_list = gd.get_filtered_item_list(ill_ground, ItemFilter::all());
-                self.update_list(filtered_list);
+                self.update_list(gd, filtered_list);
+            }
+            ItemWindowMode::Steal => {
+                let filter = ItemFilter::new().flags(ItemFlags::OWNED);
+                let filtered_list = gd.get_filtered_item_list(ill_ground, filter);
+                self.update_list(gd, filtered_list);
             }
             ItemWindowMode::Drop => {
                 let filtered_list = gd.get_filtered_item_list(ill_player, ItemFilter::all());
-                self.update_list(filtered_list);
+                self.update_list(gd, filtered_list);
             }
             ItemWindowMode::Throw => {
                 let player_str = gd.chara.get(CharaId::Player).attr.str;
                 let filter = ItemFilter::new().throwable(Some(player_str));
                 let filtered_list = gd.get_filtered_item_list(ill_player, filter);
-                self.update_list(filtered_list);
+                self.update_list(gd, filtered_list);
             }
             ItemWindowMode::Drink => {
                 let filtered_list = gd.get_merged_filtered_item_list(
@@ -297,7 +313,7 @@ impl ItemWindow {
                     ill_player,
                     ItemFilter::new().drinkable(true),
                 );
-                self.update_list(filtered_list);
+                self.update_list(gd, filtered_list);
             }
             ItemWindowMode::Eat => {
                 let filtered_list = gd.get_merged_filtered_item_list(
@@ -305,7 +321,7 @@ impl ItemWindow {
                     ill_player,
                     ItemFilter::new().eatable(true),
                 );
-                self.update_list(filtered_list);
+                self.update_list(gd, filtered_list);
             }
             ItemWindowMode::Use => {
                 let filtered_list = gd.get_merged_filtered_item_list(
@@ -313,7 +329,7 @@ impl ItemWindow {
                     ill_player,
                     ItemFilter::new().usable(true),
                 );
-                self.update_list(filtered_list);
+                self.update_list(gd, filtered_list);
             }
             ItemWindowMode::Release => {
                 let filtered_list = gd.get_merged_filtered_item_list(
@@ -321,7 +337,7 @@ impl ItemWindow {
                     ill_player,
                     ItemFilter::new().kind_rough(ItemKindRough::MagicDevice),
                 );
-                self.update_list(filtered_list);
+                self.update_list(gd, filtered_list);
             }
             ItemWindowMode::Read => {
                 let filtered_list = gd.get_merged_filtered_item_list(
@@ -329,7 +345,7 @@ impl ItemWindow {
                     ill_player,
                     ItemFilter::new().readable(true),
                 );
-                self.update_list(filtered_list);
+                self.update_list(gd, filtered_list);
             }
             ItemWindowMode::Open => {
                 let filtered_list = gd.get_merged_filtered_item_list(
@@ -337,7 +353,7 @@ impl ItemWindow {
                     ill_player,
                     ItemFilter::new().container(true),
                 );
-                self.update_list(filtered_list);
+                self.update_list(gd, filtered_list);
             }
             ItemWindowMode::Put { ill, id } => {
                 let il = gd.find_container_item(*ill, *id).unwrap();
@@ -365,35 +381,35 @@ impl ItemWindow {
 
                 let filtered_list =
                     gd.get_merged_filtered_item_list(ill_ground, ill_player, item_filter);
-                self.update_list(filtered_list);
+                self.update_list(gd, filtered_list);
             }
             ItemWindowMode::Take { ill, id } => {
                 let il = gd.find_container_item(*ill, *id).unwrap();
-                let ill_in_container = ItemListLocation::in_container(il);
+                let ill_in_container = crate::game::base::container_item_list(gd, il);
                 let filtered_list = gd.get_filtered_item_list(ill_in_container, ItemFilter::new());
-                self.update_list(filtered_list);
+                self.update_list(gd, filtered_list);
             }
             ItemWindowMode::ShopBuy { cid } => {
                 let ill = ItemListLocation::Shop { cid: *cid };
                 let filtered_list = gd.get_filtered_item_list(ill, ItemFilter::new());
-                self.update_list(filtered_list);
+                self.update_list(gd, filtered_list);
             }
-            ItemWindowMode::ShopSell => {
+            ItemWindowMode::ShopSell { .. } => {
                 let ill = ItemListLocation::Chara {
                     cid: CharaId::Player,
                 };
                 let filtered_list = gd.get_filtered_item_list(ill, ItemFilter::new());
-                self.update_list(filtered_list);
+                self.update_list(gd, filtered_list);
             }
             ItemWindowMode::Select { ill, filter, .. } => {
                 let filtered_list = gd.get_filtered_item_list(*ill, filter.clone());
-                self.update_list(filtered_list);
+                self.update_list(gd, filtered_list);
             }
         }
         self.update_label(gd);
     }
 
-    fn update_list(&mut self, list: FilteredItemList<'_>) {
+    fn update_list(&mut self, gd: &GameData, list: FilteredItemList<'_>) {
         self.list.set_n_item(list.clone().count() as u32);
 
         let mode = &self.mode;
@@ -414,8 +430,12 @@ impl ItemWindow {
 
             // Information displayed in the right column
             let additional_info = match mode {
-                ItemWindowMode::ShopBuy { .. } => format!("{}G", item.price()),
-                ItemWindowMode::ShopSell => format!("{}G", item.selling_price()),
+                ItemWindowMode::ShopBuy { cid } => {
+                    format!("{}G", buying_price(gd, *cid, item).total)
+                }
+                ItemWindowMode::ShopSell { cid } => {
+                    format!("{}G", selling_price(gd, *cid, item).total)
+                }
                 _ => format!("{:.1}kg", w / 1000.0),
             };
 
@@ -444,13 +464,41 @@ impl ItemWindow {
             capacity / 1000.0
         ));
 
-        match self.mode {
-            ItemWindowMode::ShopBuy { .. } | ItemWindowMode::ShopSell { .. } => {
-                self.info_label1
-                    .set_text(&format!("{} G", gd.player.money()));
-            }
-            _ => (),
-        }
+        let cid = match self.mode {
+            ItemWindowMode::ShopBuy { cid } | ItemWindowMode::ShopSell { cid } => cid,
+            _ => return,
+        };
+        let funds = gd.get_shop(cid).funds;
+        let money = gd.player.money();
+        self.info_label1
+            .set_text(&ui_txt_format!("shop-funds"; funds=funds, money=money));
+
+        // Show the price breakdown of the selected item instead of weight
+        let il = if let Some(il) = self
+            .item_locations
+            .get(self.list.get_current_choice() as usize)
+        {
+            *il
+        } else {
+            return;
+        };
+        let item = gd.get_item(il).0;
+        let (price, buying) = if let ItemWindowMode::ShopBuy { .. } = self.mode {
+            (buying_price(gd, cid, item), true)
+        } else {
+            (selling_price(gd, cid, item), false)
+        };
+        let effect = |adjustment: f32| {
+            let adjustment = if buying { -adjustment } else { adjustment };
+            format!("{:+.0}%", adjustment * 100.0)
+        };
+        let cha = effect(price.cha);
+        let skill = effect(price.skill);
+        let relation = effect(price.relation);
+        let supply = effect(price.supply);
+        self.info_label0.set_text(&ui_txt_format!(
+            "shop-price-breakdown";
+            base=price.base, cha=cha, skill=skill, relation=relation, supply=supply));
     }
 
     fn do_action_for_item(
@@ -472,6 +520,13 @@ impl ItemWindow {
                     DialogResult::Close
                 }
             }
+            ItemWindowMode::Steal => {
+                if pa.steal_item(il) {
+                    DialogResult::CloseAll
+                } else {
+                    DialogResult::Continue
+                }
+            }
             ItemWindowMode::Drop => {
                 pa.drop_item(il, 1);
                 self.update_by_mode(pa.gd());
@@ -517,18 +572,19 @@ impl ItemWindow {
             }
             ItemWindowMode::Put { ill, id } => {
                 let container_il = pa.gd().find_container_item(ill, id).unwrap();
-                let ill_in_container = ItemListLocation::in_container(container_il);
+                let ill_in_container =
+                    crate::game::base::container_item_list(pa.gd(), container_il);
                 pa.move_item(il, ill_in_container, 1);
                 self.update_by_mode(pa.gd());
                 DialogResult::Continue
             }
-            ItemWindowMode::ShopBuy { .. } => {
-                pa.buy_item(il);
+            ItemWindowMode::ShopBuy { cid } => {
+                pa.buy_item(cid, il);
                 self.update_by_mode(pa.gd());
                 DialogResult::Continue
             }
-            ItemWindowMode::ShopSell => {
-                pa.sell_item(il);
+            ItemWindowMode::ShopSell { cid } => {
+                pa.sell_item(cid, il);
                 self.update_by_mode(pa.gd());
                 DialogResult::Continue
             }
@@ -611,7 +667,9 @@ impl DialogWindow for ItemWindow {
                 ListWidgetResponse::Scrolled => {
                     self.update_by_mode(pa.gd());
                 }
-                _ => (),
+                ListWidgetResponse::SelectionChanged(_) => {
+                    self.update_label(pa.gd());
+                }
             }
             return DialogResult::Continue;
         }
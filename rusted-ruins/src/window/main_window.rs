@@ -187,7 +187,7 @@ impl MainWindow {
     ) {
         let center = game.gd.player_pos();
         let range = crate::game::effect::effect_to_range(&effect, center);
-        let draw_info = TargetModeDrawInfo { range };
+        let draw_info = TargetModeDrawInfo { range, effect };
         self.mode = MainWindowMode::Target {
             callback,
             draw_info,
@@ -24,10 +24,12 @@ mod minimap;
 mod misc_window;
 mod msg_dialog;
 mod newgame_window;
+mod party_menu;
 mod progress_bar;
 mod quest_window;
 mod read_window;
 mod register_shortcut_dialog;
+mod rest_menu;
 mod sidebar;
 mod start_window;
 mod status_window;
@@ -554,6 +556,10 @@ impl<'sdl, 't, 's> WindowManager<'sdl, 't, 's> {
                 let dialog = Box::new(game_info_window::create_game_info_window_group(pa.game()));
                 self.push_dialog_window(dialog);
             }
+            Command::OpenQuestWin => {
+                let dialog = Box::new(quest_window::QuestJournalWindow::new(pa.gd()));
+                self.push_dialog_window(dialog);
+            }
             Command::PickUpItem => {
                 if !pa.gd().item_on_player_tile().is_empty() {
                     let item_window = ItemWindow::new(ItemWindowMode::PickUp, pa.game());
@@ -0,0 +1,54 @@
+use super::commonuse::*;
+use crate::game::DialogOpenRequest;
+use common::gamedata::*;
+
+/// Menu to give orders to a party member
+pub fn create_menu(game: &Game<'_>, cid: CharaId) -> Box<dyn DialogWindow> {
+    let mut text_ids = vec![];
+    let mut callbacks: Vec<Box<dyn FnMut(&mut DoPlayerAction<'_, '_>) + 'static>> = vec![];
+
+    let orders = [
+        (PartyOrder::Follow, "party-menu-follow"),
+        (PartyOrder::Wait, "party-menu-wait"),
+        (PartyOrder::AttackTarget, "party-menu-attack-target"),
+        (PartyOrder::NoAttack, "party-menu-no-attack"),
+    ];
+    let current_order = game.gd.player.party_order(cid);
+    for (order, text_id) in orders {
+        if order == current_order {
+            continue;
+        }
+        text_ids.push(text_id);
+        callbacks.push(Box::new(move |pa: &mut DoPlayerAction<'_, '_>| {
+            pa.set_party_order(cid, order);
+        }));
+    }
+
+    text_ids.push("party-menu-status");
+    callbacks.push(Box::new(move |pa: &mut DoPlayerAction<'_, '_>| {
+        pa.request_dialog_open(DialogOpenRequest::CharaStatus { cid });
+    }));
+
+    // Only hired members can be dismissed
+    if game.gd.player.employment.contains_key(&cid) {
+        text_ids.push("party-menu-dismiss");
+        callbacks.push(Box::new(move |pa: &mut DoPlayerAction<'_, '_>| {
+            let chara = pa.gd().chara.get(cid);
+            let msg = ui_txt_format!("dialog-dismiss_party_member"; chara=chara);
+            pa.request_dialog_open(DialogOpenRequest::YesNo {
+                callback: Box::new(move |pa, result| {
+                    if result {
+                        pa.dismiss_party_member(cid);
+                    }
+                }),
+                msg,
+            });
+        }));
+    }
+
+    Box::new(super::choose_window::ChooseWindow::menu(
+        WindowPos::CENTER,
+        text_ids,
+        callbacks,
+    ))
+}
@@ -3,8 +3,10 @@ use super::msg_dialog::MsgDialog;
 use super::widget::*;
 use crate::draw::border::draw_window_border;
 use crate::game::quest::available_quests;
-use crate::text::ToText;
-use common::gamedata::Quest;
+use crate::text::{misc_txt, obj_txt, ToText};
+use common::gamedata::{GameData, Quest, QuestKind, QuestState, SiteId, StoryQuest};
+use common::gobj;
+use common::obj::StoryQuestObject;
 
 pub struct QuestWindow {
     rect: Rect,
@@ -60,7 +62,8 @@ impl QuestWindow {
             self.description.set_text("");
         } else {
             let q = &available_quests(&game.gd)[self.list.get_current_choice() as usize];
-            self.description.set_text(&quest_decription_text(q));
+            self.description
+                .set_text(quest_decription_text(&game.gd, q));
         }
     }
 }
@@ -103,7 +106,7 @@ impl DialogWindow for QuestWindow {
                     self.dialog = None;
                     if n == 0 {
                         // Undertake quest
-                        pa.undertake_quest(n);
+                        pa.undertake_quest(self.list.get_current_choice());
                         self.update(pa.game())
                     }
                 }
@@ -122,7 +125,9 @@ impl DialogWindow for QuestWindow {
                         |_, n| DialogResult::CloseWithValue(DialogCloseValue::Index(n)),
                     ));
                 }
-                ListWidgetResponse::Scrolled => {}
+                ListWidgetResponse::SelectionChanged(_) | ListWidgetResponse::Scrolled => {
+                    self.update(pa.game());
+                }
                 _ => (),
             }
             return DialogResult::Continue;
@@ -139,10 +144,231 @@ impl DialogWindow for QuestWindow {
     }
 }
 
-fn quest_decription_text(quest: &Quest) -> String {
-    match quest {
-        Quest::SlayMonsters { idx, goal, .. } => {
+/// Quests the player has undertaken
+#[derive(Clone, Copy)]
+enum JournalEntry {
+    Story(usize),
+    Town(usize),
+}
+
+/// Shows objectives and history of the player's quests
+pub struct QuestJournalWindow {
+    rect: Rect,
+    list: TextListWidget,
+    description: LabelWidget,
+    entries: Vec<JournalEntry>,
+    escape_click: bool,
+}
+
+impl QuestJournalWindow {
+    pub fn new(gd: &GameData) -> QuestJournalWindow {
+        let rect = UI_CFG.quest_window.rect.into();
+        let mut w = QuestJournalWindow {
+            rect,
+            list: TextListWidget::new(
+                (0i32, 0i32, rect.w as u32, rect.h as u32),
+                vec![6],
+                UI_CFG.quest_window.n_row,
+                true,
+            ),
+            description: LabelWidget::wrapped(
+                (
+                    0i32,
+                    (UI_CFG.quest_window.n_row as i32 + 1)
+                        * UI_CFG.list_widget.h_row_default as i32,
+                    rect.width(),
+                    0,
+                ),
+                "",
+                FontKind::M,
+                rect.width(),
+            ),
+            entries: Vec::new(),
+            escape_click: false,
+        };
+        w.update_list(gd);
+        w
+    }
+
+    fn update_list(&mut self, gd: &GameData) {
+        self.entries = (0..gd.quest.iter_story_quests().count())
+            .map(JournalEntry::Story)
+            .chain((0..gd.quest.iter().count()).map(JournalEntry::Town))
+            .collect();
+
+        let rows: Vec<TextCache> = gd
+            .quest
+            .iter_story_quests()
+            .map(|quest| {
+                let mut text = obj_txt(&quest.id);
+                if quest.completed {
+                    text.push(' ');
+                    text.push_str(&misc_txt("quest_state-completed"));
+                }
+                text
+            })
+            .chain(gd.quest.iter().map(|(state, quest)| {
+                let mut text = quest.to_text().into_owned();
+                let state = match state {
+                    QuestState::Completed => Some("quest_state-completed"),
+                    QuestState::Failed => Some("quest_state-failed"),
+                    _ => None,
+                };
+                if let Some(state) = state {
+                    text.push(' ');
+                    text.push_str(&misc_txt(state));
+                }
+                text
+            }))
+            .map(|text| TextCache::new(text, FontKind::M, UI_CFG.color.normal_font))
+            .collect();
+        self.list.set_items(rows);
+        self.update_description(gd);
+    }
+
+    fn update_description(&mut self, gd: &GameData) {
+        let entry = self
+            .entries
+            .get(self.list.get_current_choice() as usize)
+            .copied();
+        let text = match entry {
+            Some(JournalEntry::Story(i)) => {
+                story_quest_description_text(gd.quest.iter_story_quests().nth(i).unwrap())
+            }
+            Some(JournalEntry::Town(i)) => {
+                quest_decription_text(gd, &gd.quest.iter().nth(i).unwrap().1)
+            }
+            None => String::new(),
+        };
+        self.description.set_text(text);
+    }
+}
+
+impl Window for QuestJournalWindow {
+    fn draw(
+        &mut self,
+        context: &mut Context<'_, '_, '_, '_>,
+        _game: &Game<'_>,
+        _anim: Option<(&Animation, u32)>,
+    ) {
+        draw_window_border(context, self.rect);
+        self.list.draw(context);
+        self.description.draw(context);
+    }
+}
+
+impl DialogWindow for QuestJournalWindow {
+    fn process_command(
+        &mut self,
+        command: &Command,
+        pa: &mut DoPlayerAction<'_, '_>,
+    ) -> DialogResult {
+        check_escape_click!(self, command);
+
+        let command = command.relative_to(self.rect);
+        if let Some(response) = self.list.process_command(&command) {
+            match response {
+                ListWidgetResponse::SelectionChanged(_) | ListWidgetResponse::Scrolled => {
+                    self.update_description(pa.gd());
+                }
+                _ => (),
+            }
+            return DialogResult::Continue;
+        }
+
+        match command {
+            Command::Cancel => DialogResult::Close,
+            _ => DialogResult::Continue,
+        }
+    }
+
+    fn draw_mode(&self) -> WindowDrawMode {
+        WindowDrawMode::SkipUnderWindows
+    }
+}
+
+/// Current objective and journal entries of the passed stages
+fn story_quest_description_text(quest: &StoryQuest) -> String {
+    let obj: Option<&StoryQuestObject> = gobj::get_by_id_checked(&quest.id);
+    let obj = if let Some(obj) = obj {
+        obj
+    } else {
+        return String::new();
+    };
+
+    let mut text = String::new();
+    if let Some(stage) = obj.stage(&quest.stage) {
+        text.push_str(&misc_txt(&stage.objective));
+    }
+    for (time, stage) in quest.history.iter().rev() {
+        if let Some(stage) = obj.stage(stage) {
+            let date = time.into_date();
+            text.push('\n');
+            text.push_str(&format!(
+                "{}/{}/{} {}",
+                date.year,
+                date.month,
+                date.day,
+                misc_txt(&stage.journal)
+            ));
+        }
+    }
+    text
+}
+
+fn quest_decription_text(gd: &GameData, quest: &Quest) -> String {
+    let site = |sid: SiteId| {
+        gd.region
+            .get_site_checked(sid)
+            .map(|site| site.to_text().into_owned())
+            .unwrap_or_default()
+    };
+
+    let mut text = match quest.kind {
+        QuestKind::SlayMonsters { idx, goal, .. } => {
             misc_txt_format!("desc-quest-slay_monsters"; monster=idx, n=goal)
         }
+        QuestKind::Fetch { idx, n } => misc_txt_format!("desc-quest-fetch"; item=idx, n=n),
+        QuestKind::Deliver {
+            idx,
+            n,
+            destination,
+        } => {
+            let site = site(destination);
+            misc_txt_format!("desc-quest-deliver"; item=idx, n=n, site=site)
+        }
+        QuestKind::Escort {
+            idx, destination, ..
+        } => {
+            let site = site(destination);
+            misc_txt_format!("desc-quest-escort"; chara=idx, site=site)
+        }
+        QuestKind::Explore { sid, floor } => {
+            let site = site(sid);
+            misc_txt_format!("desc-quest-explore"; site=site, floor=floor)
+        }
+        QuestKind::Retrieve {
+            idx, sid, floor, ..
+        } => {
+            let site = site(sid);
+            misc_txt_format!("desc-quest-retrieve"; item=idx, site=site, floor=floor)
+        }
+        QuestKind::Craft { idx, goal, .. } => {
+            misc_txt_format!("desc-quest-craft"; item=idx, n=goal)
+        }
+    };
+
+    text.push('\n');
+    text.push_str(&misc_txt_format!("desc-quest-reward"; money=quest.reward.money));
+    if let Some(deadline) = quest.deadline {
+        let now = gd.time.current_time();
+        let days = if deadline > now {
+            deadline.duration_from(now).days()
+        } else {
+            0
+        };
+        text.push('\n');
+        text.push_str(&misc_txt_format!("desc-quest-deadline"; days=days));
     }
+    text
 }
@@ -0,0 +1,28 @@
+use super::commonuse::*;
+use crate::game::DialogOpenRequest;
+
+/// Menu to choose how to rest
+pub fn create_menu() -> Box<dyn DialogWindow> {
+    let text_ids = vec![
+        "rest-menu-wait",
+        "rest-menu-until-healed",
+        "rest-menu-sleep",
+    ];
+    let callbacks: Vec<Box<dyn FnMut(&mut DoPlayerAction<'_, '_>) + 'static>> = vec![
+        Box::new(|pa: &mut DoPlayerAction<'_, '_>| {
+            pa.request_dialog_open(DialogOpenRequest::WaitTurns);
+        }),
+        Box::new(|pa: &mut DoPlayerAction<'_, '_>| {
+            pa.rest_until_healed();
+        }),
+        Box::new(|pa: &mut DoPlayerAction<'_, '_>| {
+            pa.sleep_until_morning();
+        }),
+    ];
+
+    Box::new(super::choose_window::ChooseWindow::menu(
+        WindowPos::CENTER,
+        text_ids,
+        callbacks,
+    ))
+}
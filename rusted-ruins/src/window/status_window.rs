@@ -68,6 +68,7 @@ pub struct StatusWindow {
     lv_label: LabelWidget,
     hp_label: LabelWidget,
     sp_label: LabelWidget,
+    mp_label: LabelWidget,
     str_label: LabelWidget,
     vit_label: LabelWidget,
     dex_label: LabelWidget,
@@ -77,6 +78,7 @@ pub struct StatusWindow {
     spd_label: LabelWidget,
     carry_label: LabelWidget,
     travel_speed_label: LabelWidget,
+    element_protection_labels: Vec<LabelWidget>,
     escape_click: bool,
 }
 
@@ -117,6 +119,11 @@ impl StatusWindow {
             &format!("SP  {:2.0}", chara.sp),
             FontKind::MonoM,
         );
+        let mp_label = LabelWidget::new(
+            cfg.mp_label_rect,
+            &format!("MP  {} / {}", chara.mp, chara.attr.max_mp),
+            FontKind::MonoM,
+        );
         let str_label = LabelWidget::new(
             cfg.str_label_rect,
             &format!("STR  {}", chara.attr.str),
@@ -170,6 +177,23 @@ impl StatusWindow {
             ),
             FontKind::S,
         );
+        let element_protection_labels = ELEMENTS
+            .iter()
+            .enumerate()
+            .map(|(i, e)| {
+                let mut rect: Rect = cfg.element_protection_label_rect.into();
+                rect.y += cfg.element_protection_label_h * i as i32;
+                LabelWidget::new(
+                    rect,
+                    &format!(
+                        "{}  {:+}%",
+                        e.to_text(),
+                        chara.tm.element_protection[*e].clamp(-100, 100)
+                    ),
+                    FontKind::S,
+                )
+            })
+            .collect();
         StatusWindow {
             rect,
             image,
@@ -178,6 +202,7 @@ impl StatusWindow {
             lv_label,
             hp_label,
             sp_label,
+            mp_label,
             str_label,
             vit_label,
             dex_label,
@@ -187,6 +212,7 @@ impl StatusWindow {
             spd_label,
             carry_label,
             travel_speed_label,
+            element_protection_labels,
             escape_click: false,
         }
     }
@@ -206,6 +232,7 @@ impl Window for StatusWindow {
         self.lv_label.draw(context);
         self.hp_label.draw(context);
         self.sp_label.draw(context);
+        self.mp_label.draw(context);
         self.str_label.draw(context);
         self.vit_label.draw(context);
         self.dex_label.draw(context);
@@ -215,6 +242,9 @@ impl Window for StatusWindow {
         self.spd_label.draw(context);
         self.carry_label.draw(context);
         self.travel_speed_label.draw(context);
+        for label in self.element_protection_labels.iter_mut() {
+            label.draw(context);
+        }
     }
 }
 
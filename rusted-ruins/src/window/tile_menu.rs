@@ -42,6 +42,18 @@ pub fn create_menu(
                     pa.goto_next_floor(Direction::NONE, false);
                 }));
             }
+            Some(SpecialTileKind::Port { .. }) => {
+                text_ids.push("tile-menu-sail");
+                callbacks.push(Box::new(move |pa: &mut DoPlayerAction<'_, '_>| {
+                    pa.goto_next_floor(Direction::NONE, true);
+                }));
+            }
+            Some(SpecialTileKind::Portal { .. }) => {
+                text_ids.push("tile-menu-enter-portal");
+                callbacks.push(Box::new(move |pa: &mut DoPlayerAction<'_, '_>| {
+                    pa.goto_next_floor(Direction::NONE, true);
+                }));
+            }
             _ => (),
         }
         match t.boundary {
@@ -65,6 +77,17 @@ pub fn create_menu(
                 pa.request_dialog_open(DialogOpenRequest::PickUpItem);
             }));
         }
+        if game
+            .gd
+            .item_on_player_tile()
+            .iter()
+            .any(|(item, _)| item.flags.contains(ItemFlags::OWNED))
+        {
+            text_ids.push("tile-menu-steal");
+            callbacks.push(Box::new(move |pa: &mut DoPlayerAction<'_, '_>| {
+                pa.request_dialog_open(DialogOpenRequest::StealItem);
+            }));
+        }
     }
 
     // Same tile or adjacent tile
@@ -104,6 +127,65 @@ pub fn create_menu(
         }
     }
 
+    if player_same_tile {
+        text_ids.push("tile-menu-rest");
+        callbacks.push(Box::new(move |pa: &mut DoPlayerAction<'_, '_>| {
+            pa.request_dialog_open(DialogOpenRequest::Rest);
+        }));
+        if crate::game::base::search_bed(&game.gd).is_some() {
+            text_ids.push("tile-menu-sleep");
+            callbacks.push(Box::new(move |pa: &mut DoPlayerAction<'_, '_>| {
+                pa.sleep_until_morning();
+            }));
+        }
+        if !is_region_map {
+            text_ids.push("tile-menu-search");
+            callbacks.push(Box::new(move |pa: &mut DoPlayerAction<'_, '_>| {
+                pa.search();
+            }));
+        }
+    }
+
+    // Adjacent doors
+    if tile.is_adjacent(player_pos) {
+        match game.gd.get_current_map().tile[tile]
+            .door
+            .map(|door| door.state)
+        {
+            Some(DoorState::Open) => {
+                text_ids.push("tile-menu-close-door");
+                callbacks.push(Box::new(move |pa: &mut DoPlayerAction<'_, '_>| {
+                    pa.close_door(tile);
+                }));
+            }
+            Some(DoorState::Locked) => {
+                text_ids.push("tile-menu-pick-lock");
+                callbacks.push(Box::new(move |pa: &mut DoPlayerAction<'_, '_>| {
+                    pa.pick_lock(tile);
+                }));
+            }
+            _ => (),
+        }
+    }
+
+    // Adjacent creatures
+    if let Some(cid) = t.chara {
+        if tile.is_adjacent(player_pos) {
+            if crate::game::livestock::is_tameable(&game.gd, cid) {
+                text_ids.push("tile-menu-tame");
+                callbacks.push(Box::new(move |pa: &mut DoPlayerAction<'_, '_>| {
+                    pa.tame(cid);
+                }));
+            }
+            if crate::game::livestock::is_livestock(&game.gd, cid) {
+                text_ids.push("tile-menu-feed");
+                callbacks.push(Box::new(move |pa: &mut DoPlayerAction<'_, '_>| {
+                    pa.feed_livestock(cid);
+                }));
+            }
+        }
+    }
+
     // In region map
     if player_same_tile && is_region_map && t.move_symbol.is_none() {
         text_ids.push("tile-menu-enter-wilderness");
@@ -112,6 +194,15 @@ pub fn create_menu(
         }));
     }
 
+    if let Some(cid) = t.chara {
+        if game.gd.player.party.contains(&cid) {
+            text_ids.push("tile-menu-party-member");
+            callbacks.push(Box::new(move |pa: &mut DoPlayerAction<'_, '_>| {
+                pa.request_dialog_open(DialogOpenRequest::PartyMember { cid });
+            }));
+        }
+    }
+
     if !player_same_tile && t.chara.is_some() {
         text_ids.push("tile-menu-target");
         callbacks.push(Box::new(move |pa: &mut DoPlayerAction<'_, '_>| {
@@ -80,6 +80,9 @@ pub struct GameMethods {
     pub has_item: fn(&GameData, &str) -> Option<u32>,
     pub gen_dungeons: fn(&mut GameData),
     pub gen_party_chara: fn(&mut GameData, &str, u32) -> bool,
+    pub hire: fn(&mut GameData) -> bool,
+    pub hire_wage: fn(&GameData) -> Option<i64>,
+    pub quest_advance: fn(&mut GameData, &str, &str) -> bool,
     pub receive_quest_rewards: fn(&mut GameData) -> bool,
     pub receive_item: fn(&mut GameData, &str, u32),
     pub receive_money: fn(&mut GameData, u32),
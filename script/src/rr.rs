@@ -123,6 +123,30 @@ mod _rr {
         with_gd_mut(|gd| call_game_method!(gen_party_chara)(gd, id.as_ref(), lv))
     }
 
+    #[pyfunction]
+    fn hire() -> bool {
+        with_gd_mut(|gd| call_game_method!(hire)(gd))
+    }
+
+    #[pyfunction]
+    fn hire_wage() -> Option<i64> {
+        with_gd(|gd| call_game_method!(hire_wage)(gd))
+    }
+
+    #[pyfunction]
+    fn quest_advance(id: PyStrRef, stage: PyStrRef) -> bool {
+        with_gd_mut(|gd| call_game_method!(quest_advance)(gd, id.as_ref(), stage.as_ref()))
+    }
+
+    #[pyfunction]
+    fn quest_stage(id: PyStrRef) -> Option<String> {
+        with_gd(|gd| {
+            gd.quest
+                .story_quest(id.as_ref())
+                .map(|quest| quest.stage.clone())
+        })
+    }
+
     #[pyfunction]
     fn receive_quest_rewards() -> bool {
         with_gd_mut(|gd| call_game_method!(receive_quest_rewards)(gd))